@@ -9,6 +9,9 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// Sign-off used when a cover letter has no `signature` and no server-wide default is set
+pub const DEFAULT_SIGNATURE: &str = "Sincerely";
+
 /// A complete cover letter document
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[schemars(description = "A professional cover letter document")]
@@ -49,7 +52,7 @@ pub struct ContactInfo {
     /// Full name
     pub name: String,
 
-    /// Email address
+    /// Professional email address, ideally based on your name (e.g., jane.doe@example.com)
     #[schemars(email)]
     pub email: String,
 
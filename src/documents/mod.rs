@@ -9,3 +9,88 @@ pub mod resume;
 
 pub use cover_letter::CoverLetter;
 pub use resume::Resume;
+
+/// MIME type of every generated document
+pub const PDF_MIME_TYPE: &str = "application/pdf";
+
+/// Output conventions of a generated document type
+///
+/// Storage, downloads, and the `get_document_types` registry read these, so
+/// every type is named and served the same way.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DocumentType {
+    /// Registry name (e.g. `cover_letter`)
+    pub name: &'static str,
+    /// Name used in messages (e.g. "Cover letter")
+    pub label: &'static str,
+    /// Default filename, with `*` standing for the author's name
+    pub filename_pattern: &'static str,
+    /// MIME type of the generated file
+    pub mime_type: &'static str,
+    /// Example document as JSON, compiled at startup to check the template
+    pub example: &'static str,
+}
+
+impl DocumentType {
+    pub const RESUME: DocumentType = DocumentType {
+        name: "resume",
+        label: "Resume",
+        filename_pattern: "*_Resume.pdf",
+        mime_type: PDF_MIME_TYPE,
+        example: include_str!("../../tests/fixtures/full_resume.json"),
+    };
+
+    pub const COVER_LETTER: DocumentType = DocumentType {
+        name: "cover_letter",
+        label: "Cover letter",
+        filename_pattern: "*_Cover_Letter.pdf",
+        mime_type: PDF_MIME_TYPE,
+        example: include_str!("../../tests/fixtures/sample_cover_letter.json"),
+    };
+
+    /// Every implemented document type
+    pub const ALL: &'static [DocumentType] = &[Self::RESUME, Self::COVER_LETTER];
+
+    /// Default filename for a document by `author` ("Jane_Smith_Resume.pdf")
+    ///
+    /// Only letters and digits of the name are kept, words joined by `_`.
+    /// Without a usable name the pattern's `*_` is dropped ("Resume.pdf").
+    pub fn default_filename(&self, author: &str) -> String {
+        let words: Vec<String> = author
+            .split_whitespace()
+            .map(|word| {
+                word.chars()
+                    .filter(|c| c.is_alphanumeric())
+                    .collect::<String>()
+            })
+            .filter(|word| !word.is_empty())
+            .collect();
+
+        if words.is_empty() {
+            self.filename_pattern.replacen("*_", "", 1)
+        } else {
+            self.filename_pattern.replacen('*', &words.join("_"), 1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_filename_follows_pattern() {
+        assert_eq!(
+            DocumentType::RESUME.default_filename("Jane  Smith"),
+            "Jane_Smith_Resume.pdf"
+        );
+        assert_eq!(
+            DocumentType::COVER_LETTER.default_filename("José O'Neil"),
+            "José_ONeil_Cover_Letter.pdf"
+        );
+        assert_eq!(
+            DocumentType::COVER_LETTER.default_filename(" ?! "),
+            "Cover_Letter.pdf"
+        );
+    }
+}
@@ -9,6 +9,48 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// Names of the standard resume sections, in default rendering order
+pub const SECTION_NAMES: [&str; 8] = [
+    "education",
+    "experience",
+    "projects",
+    "certifications",
+    "awards",
+    "publications",
+    "skills",
+    "languages",
+];
+
+/// Canonical display names for common profile networks, keyed by the
+/// lowercase alphanumeric form of the name (so "Linked In" matches "linkedin")
+pub const KNOWN_NETWORKS: &[(&str, &str)] = &[
+    ("linkedin", "LinkedIn"),
+    ("github", "GitHub"),
+    ("gitlab", "GitLab"),
+    ("bitbucket", "Bitbucket"),
+    ("stackoverflow", "Stack Overflow"),
+    ("twitter", "Twitter"),
+    ("x", "X"),
+    ("mastodon", "Mastodon"),
+    ("bluesky", "Bluesky"),
+    ("medium", "Medium"),
+    ("substack", "Substack"),
+    ("devto", "DEV"),
+    ("youtube", "YouTube"),
+    ("dribbble", "Dribbble"),
+    ("behance", "Behance"),
+    ("kaggle", "Kaggle"),
+    ("leetcode", "LeetCode"),
+    ("googlescholar", "Google Scholar"),
+    ("orcid", "ORCID"),
+    ("researchgate", "ResearchGate"),
+    ("website", "Website"),
+    ("personalwebsite", "Website"),
+    ("homepage", "Website"),
+    ("portfolio", "Portfolio"),
+    ("blog", "Blog"),
+];
+
 /// A complete resume document
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[schemars(description = "A complete resume/CV document")]
@@ -47,6 +89,17 @@ pub struct Resume {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub publications: Vec<Publication>,
 
+    /// Free-form sections not modeled by the schema
+    #[serde(
+        rename = "customSections",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    #[schemars(
+        description = "Additional free-form sections (e.g., 'Volunteering', 'Speaking'). Rendered after the standard sections unless their title is listed in sectionOrder. Titles must be non-empty and unique."
+    )]
+    pub custom_sections: Vec<CustomSection>,
+
     /// Custom section ordering
     #[serde(
         rename = "sectionOrder",
@@ -54,7 +107,7 @@ pub struct Resume {
         skip_serializing_if = "Option::is_none"
     )]
     #[schemars(
-        description = "Custom section ordering. Array of section names to display in order. Valid sections: 'education', 'experience', 'projects', 'certifications', 'awards', 'publications', 'skills', 'languages'. If not specified, uses default order. Omit a section from the list to hide it."
+        description = "Custom section ordering. Array of section names to display in order. Valid sections: 'education', 'experience', 'projects', 'certifications', 'awards', 'publications', 'skills', 'languages', plus the titles of any customSections. If not specified, uses default order. Omit a section from the list to hide it."
     )]
     pub section_order: Option<Vec<String>>,
 
@@ -67,7 +120,8 @@ pub struct Resume {
     #[schemars(
         description = "Custom section titles. Object mapping section names to custom titles. For example: {\"publications\": \"Related Publications\", \"skills\": \"Core Competencies\"}. Valid section names: 'education', 'experience', 'projects', 'certifications', 'awards', 'publications', 'skills', 'languages'."
     )]
-    pub section_titles: Option<std::collections::HashMap<String, String>>,
+    // A sorted map, so identical titles always serialize to identical source
+    pub section_titles: Option<std::collections::BTreeMap<String, String>>,
 
     /// Show name header on subsequent pages
     #[serde(
@@ -92,6 +146,194 @@ pub struct Resume {
     pub show_page_numbers: Option<bool>,
 }
 
+impl Resume {
+    /// Rewrite recognized profile networks to their canonical display names
+    pub fn normalize_profiles(&mut self) {
+        for profile in &mut self.basics.profiles {
+            if let Some(canonical) = profile.canonical_network() {
+                profile.network = canonical.to_string();
+            }
+        }
+    }
+
+    /// Replace the name, email, phone, and location with placeholders and drop
+    /// profile links, leaving the rest of the resume intact
+    pub fn redact_contact(&mut self) {
+        let basics = &mut self.basics;
+        basics.name = REDACTED_NAME.to_string();
+        basics.email = REDACTED_EMAIL.to_string();
+        basics.phone = basics.phone.as_ref().map(|_| REDACTED_PHONE.to_string());
+        basics.location = basics
+            .location
+            .as_ref()
+            .map(|_| REDACTED_LOCATION.to_string());
+        basics.profiles.clear();
+    }
+
+    /// Keep at most `max` highlights on each work, education, and project entry
+    ///
+    /// Returns the path of every shortened list with its original length.
+    pub fn truncate_highlights(&mut self, max: usize) -> Vec<(String, usize)> {
+        let lists = self
+            .work
+            .iter_mut()
+            .enumerate()
+            .map(|(i, w)| (format!("work[{}].highlights", i), &mut w.highlights))
+            .chain(
+                self.education
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(i, e)| (format!("education[{}].highlights", i), &mut e.highlights)),
+            )
+            .chain(
+                self.projects
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(i, p)| (format!("projects[{}].highlights", i), &mut p.highlights)),
+            );
+
+        let mut truncated = Vec::new();
+        for (path, highlights) in lists {
+            if highlights.len() > max {
+                truncated.push((path, highlights.len()));
+                highlights.truncate(max);
+            }
+        }
+        truncated
+    }
+
+    /// Merge skill categories with the same name and drop repeated keywords
+    ///
+    /// Names and keywords are compared case-insensitively, ignoring
+    /// surrounding whitespace. The first occurrence of each is kept, in place;
+    /// a category left without keywords by the deduplication is removed.
+    pub fn dedupe_skills(&mut self) {
+        let mut merged: Vec<Skill> = Vec::new();
+        for skill in self.skills.drain(..) {
+            let key = skill.name.trim().to_lowercase();
+            match merged
+                .iter_mut()
+                .find(|m| m.name.trim().to_lowercase() == key)
+            {
+                Some(existing) => existing.keywords.extend(skill.keywords),
+                None => merged.push(skill),
+            }
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        merged.retain_mut(|skill| {
+            let had_keywords = !skill.keywords.is_empty();
+            skill
+                .keywords
+                .retain(|keyword| seen.insert(keyword.trim().to_lowercase()));
+            !had_keywords || !skill.keywords.is_empty()
+        });
+        self.skills = merged;
+    }
+
+    /// Canonical form for storage: surrounding whitespace trimmed from every
+    /// text value, blank optional fields removed, and recognized profile
+    /// networks given their canonical names
+    pub fn normalize(&mut self) {
+        // Trimming keeps every value's type, so converting back only fails on
+        // inputs that could not have been deserialized in the first place
+        let mut value = serde_json::to_value(&*self).expect("resume serializes to JSON");
+        trim_strings(&mut value);
+        if let Ok(trimmed) = serde_json::from_value(value) {
+            *self = trimmed;
+        }
+        self.clear_empty_fields();
+        self.normalize_profiles();
+    }
+
+    /// Treat blank optional text fields (e.g. `"phone": ""`) as absent, so the
+    /// template never renders an empty line or a stray separator for them
+    pub fn clear_empty_fields(&mut self) {
+        let basics = &mut self.basics;
+        clear_if_blank(&mut basics.phone);
+        clear_if_blank(&mut basics.location);
+        clear_if_blank(&mut basics.summary);
+
+        for w in &mut self.work {
+            clear_if_blank(&mut w.logo);
+            clear_if_blank(&mut w.location);
+            clear_if_blank(&mut w.start_date);
+            clear_if_blank(&mut w.end_date);
+        }
+        for e in &mut self.education {
+            clear_if_blank(&mut e.degree);
+            clear_if_blank(&mut e.field_of_study);
+            clear_if_blank(&mut e.location);
+            clear_if_blank(&mut e.start_date);
+            clear_if_blank(&mut e.end_date);
+            clear_if_blank(&mut e.gpa);
+        }
+        for p in &mut self.projects {
+            clear_if_blank(&mut p.description);
+            clear_if_blank(&mut p.url);
+            clear_if_blank(&mut p.start_date);
+            clear_if_blank(&mut p.end_date);
+        }
+        for c in &mut self.certifications {
+            clear_if_blank(&mut c.issuer);
+            clear_if_blank(&mut c.date);
+            clear_if_blank(&mut c.url);
+        }
+        for a in &mut self.awards {
+            clear_if_blank(&mut a.awarder);
+            clear_if_blank(&mut a.date);
+            clear_if_blank(&mut a.summary);
+        }
+        for l in &mut self.languages {
+            clear_if_blank(&mut l.fluency);
+        }
+        for p in &mut self.publications {
+            clear_if_blank(&mut p.venue);
+            clear_if_blank(&mut p.date);
+            clear_if_blank(&mut p.url);
+            clear_if_blank(&mut p.summary);
+        }
+    }
+}
+
+/// Placeholders used by [`Resume::redact_contact`]
+pub const REDACTED_NAME: &str = "Your Name";
+pub const REDACTED_EMAIL: &str = "email@example.com";
+pub const REDACTED_PHONE: &str = "(555) 555-0100";
+pub const REDACTED_LOCATION: &str = "City, Country";
+
+fn trim_strings(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(text) => {
+            let trimmed = text.trim();
+            if trimmed.len() != text.len() {
+                *text = trimmed.to_string();
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(trim_strings),
+        serde_json::Value::Object(map) => map.values_mut().for_each(trim_strings),
+        _ => {}
+    }
+}
+
+fn clear_if_blank(field: &mut Option<String>) {
+    if field.as_deref().is_some_and(|v| v.trim().is_empty()) {
+        *field = None;
+    }
+}
+
+/// A free-form section with a title and a list of items
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[schemars(description = "A custom section with a title and bullet items")]
+pub struct CustomSection {
+    /// Section title (e.g., "Volunteering")
+    pub title: String,
+
+    /// Bullet items rendered under the title
+    #[serde(default)]
+    pub items: Vec<String>,
+}
+
 /// A project entry
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[schemars(description = "A project entry")]
@@ -108,11 +350,21 @@ pub struct Project {
     pub url: Option<String>,
 
     /// Start date
-    #[serde(rename = "startDate", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "startDate",
+        alias = "start_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(extend("x-aliases" = ["start_date"]))]
     pub start_date: Option<String>,
 
     /// End date
-    #[serde(rename = "endDate", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "endDate",
+        alias = "end_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(extend("x-aliases" = ["end_date"]))]
     pub end_date: Option<String>,
 
     /// Technologies or keywords used
@@ -121,7 +373,7 @@ pub struct Project {
 
     /// Key achievements or highlights
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub highlights: Vec<String>,
+    pub highlights: Vec<Highlight>,
 }
 
 /// Basic personal information
@@ -131,20 +383,26 @@ pub struct Basics {
     /// Full name
     pub name: String,
 
-    /// Email address
+    /// Professional email address, ideally based on your name (e.g., jane.doe@example.com)
     #[schemars(email)]
     pub email: String,
 
-    /// Phone number
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Phone number (also accepted as `phone_number` or `phoneNumber`)
+    #[serde(
+        alias = "phone_number",
+        alias = "phoneNumber",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(extend("x-aliases" = ["phone_number", "phoneNumber"]))]
     pub phone: Option<String>,
 
     /// Location (city, state/country)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<String>,
 
-    /// Professional summary or objective
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Professional summary or objective (also accepted as `objective`)
+    #[serde(alias = "objective", skip_serializing_if = "Option::is_none")]
+    #[schemars(extend("x-aliases" = ["objective"]))]
     pub summary: Option<String>,
 
     /// Online profiles and links
@@ -164,14 +422,42 @@ pub struct Profile {
     pub url: String,
 }
 
+impl Profile {
+    /// The canonical display name of this profile's network, if it is recognized
+    pub fn canonical_network(&self) -> Option<&'static str> {
+        let key: String = self
+            .network
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .flat_map(char::to_lowercase)
+            .collect();
+
+        KNOWN_NETWORKS
+            .iter()
+            .find(|(variant, _)| *variant == key)
+            .map(|(_, canonical)| *canonical)
+    }
+}
+
 /// A work experience entry
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[schemars(description = "A work experience entry")]
 pub struct WorkExperience {
-    /// Company or organization name
+    /// Company or organization name (also accepted as `name`, `employer`, or `organization`)
+    #[serde(alias = "name", alias = "employer", alias = "organization")]
+    #[schemars(extend("x-aliases" = ["name", "employer", "organization"]))]
     pub company: String,
 
-    /// Job title or position
+    /// Company logo: the name of a supplied asset, or an inline base64 image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Optional company logo shown next to the company name: the name of an image supplied in the generate tool's 'assets' (e.g. 'acme.png'), or an inline base64-encoded PNG, JPEG, GIF, WebP, or SVG (a data: URL also works). A logo that can't be used is omitted with a warning."
+    )]
+    pub logo: Option<String>,
+
+    /// Job title or position (also accepted as `jobTitle`, `job_title`, or `title`)
+    #[serde(alias = "jobTitle", alias = "job_title", alias = "title")]
+    #[schemars(extend("x-aliases" = ["jobTitle", "job_title", "title"]))]
     pub position: String,
 
     /// Location (city, state/country)
@@ -179,35 +465,90 @@ pub struct WorkExperience {
     pub location: Option<String>,
 
     /// Start date (YYYY-MM-DD or YYYY-MM format)
-    #[serde(rename = "startDate", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "startDate",
+        alias = "start_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(extend("x-aliases" = ["start_date"]))]
     #[schemars(description = "Start date in YYYY-MM-DD or YYYY-MM format")]
     pub start_date: Option<String>,
 
     /// End date (YYYY-MM-DD, YYYY-MM format, or "Present")
-    #[serde(rename = "endDate", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "endDate",
+        alias = "end_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(extend("x-aliases" = ["end_date"]))]
     #[schemars(
         description = "End date in YYYY-MM-DD or YYYY-MM format, or 'Present' for current positions"
     )]
     pub end_date: Option<String>,
 
+    /// Whether this is a current position (rendered as "Present"; omit `endDate`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "True for a position you currently hold. Renders the end date as 'Present'; do not also set endDate."
+    )]
+    pub current: Option<bool>,
+
     /// Key achievements and responsibilities
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub highlights: Vec<String>,
+    pub highlights: Vec<Highlight>,
+}
+
+/// A highlight bullet, either plain text or a bold lead-in followed by text
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+#[schemars(
+    description = "A highlight as a plain string, or as { lead, text } to render the lead-in phrase in bold"
+)]
+pub enum Highlight {
+    /// Plain highlight text
+    Plain(String),
+    /// Bold lead-in phrase followed by the rest of the highlight
+    Structured {
+        /// Phrase rendered in bold (e.g., "Led migration")
+        lead: String,
+        /// Remaining text (e.g., "that reduced costs by 30%")
+        text: String,
+    },
+}
+
+impl Highlight {
+    /// The highlight as plain text, with any lead-in joined to the rest
+    pub fn plain_text(&self) -> String {
+        match self {
+            Highlight::Plain(text) => text.clone(),
+            Highlight::Structured { lead, text } => format!("{} {}", lead.trim(), text.trim()),
+        }
+    }
 }
 
 /// An education entry
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[schemars(description = "An education entry")]
 pub struct Education {
-    /// Institution name
+    /// Institution name (also accepted as `school` or `university`)
+    #[serde(alias = "school", alias = "university")]
+    #[schemars(extend("x-aliases" = ["school", "university"]))]
     pub institution: String,
 
-    /// Degree or certificate type
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Degree or certificate type (also accepted as `studyType`)
+    #[serde(alias = "studyType", skip_serializing_if = "Option::is_none")]
+    #[schemars(extend("x-aliases" = ["studyType"]))]
     pub degree: Option<String>,
 
-    /// Field of study or major
-    #[serde(rename = "fieldOfStudy", skip_serializing_if = "Option::is_none")]
+    /// Field of study or major (also accepted as `area`, `major`, or `field_of_study`)
+    #[serde(
+        rename = "fieldOfStudy",
+        alias = "area",
+        alias = "major",
+        alias = "field_of_study",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(extend("x-aliases" = ["area", "major", "field_of_study"]))]
     pub field_of_study: Option<String>,
 
     /// Location (city, state/country)
@@ -215,12 +556,22 @@ pub struct Education {
     pub location: Option<String>,
 
     /// Start date (YYYY-MM-DD or YYYY-MM format)
-    #[serde(rename = "startDate", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "startDate",
+        alias = "start_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(extend("x-aliases" = ["start_date"]))]
     #[schemars(description = "Start date in YYYY-MM-DD or YYYY-MM format")]
     pub start_date: Option<String>,
 
     /// End date or expected graduation (YYYY-MM-DD, YYYY-MM format, or "Expected YYYY")
-    #[serde(rename = "endDate", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "endDate",
+        alias = "end_date",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schemars(extend("x-aliases" = ["end_date"]))]
     #[schemars(
         description = "End date in YYYY-MM-DD or YYYY-MM format, or 'Expected YYYY' for ongoing"
     )]
@@ -232,7 +583,7 @@ pub struct Education {
 
     /// Notable achievements, honors, or coursework
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
-    pub highlights: Vec<String>,
+    pub highlights: Vec<Highlight>,
 }
 
 /// A skill or competency
@@ -305,6 +656,82 @@ pub struct Language {
     pub fluency: Option<String>,
 }
 
+/// Canonical language proficiency scale, from lowest to highest
+///
+/// Free-text fluency values are mapped onto it by [`Language::proficiency`],
+/// accepting common wording and CEFR codes (A1 through C2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Proficiency {
+    Basic,
+    Intermediate,
+    Professional,
+    Fluent,
+    Native,
+}
+
+/// Normalized fluency wordings and the proficiency each stands for
+const PROFICIENCY_TERMS: &[(&str, Proficiency)] = &[
+    ("native", Proficiency::Native),
+    ("nativespeaker", Proficiency::Native),
+    ("bilingual", Proficiency::Native),
+    ("mothertongue", Proficiency::Native),
+    ("c2", Proficiency::Native),
+    ("fluent", Proficiency::Fluent),
+    ("advanced", Proficiency::Fluent),
+    ("fullprofessional", Proficiency::Fluent),
+    ("c1", Proficiency::Fluent),
+    ("professional", Proficiency::Professional),
+    ("professionalworking", Proficiency::Professional),
+    ("upperintermediate", Proficiency::Professional),
+    ("b2", Proficiency::Professional),
+    ("intermediate", Proficiency::Intermediate),
+    ("conversational", Proficiency::Intermediate),
+    ("limitedworking", Proficiency::Intermediate),
+    ("b1", Proficiency::Intermediate),
+    ("basic", Proficiency::Basic),
+    ("beginner", Proficiency::Basic),
+    ("elementary", Proficiency::Basic),
+    ("a1", Proficiency::Basic),
+    ("a2", Proficiency::Basic),
+];
+
+impl Proficiency {
+    /// Number of steps on the scale
+    pub const LEVELS: u8 = 5;
+
+    /// Position on the scale, from 1 (basic) to 5 (native)
+    pub fn level(self) -> u8 {
+        self as u8 + 1
+    }
+}
+
+impl Language {
+    /// The canonical proficiency for this language's fluency text, if recognized
+    ///
+    /// The whole value is tried first ("Upper Intermediate"), then each word
+    /// in turn ("Native speaker", "C1 - Advanced").
+    pub fn proficiency(&self) -> Option<Proficiency> {
+        let fluency = self.fluency.as_deref()?;
+        let lookup = |text: &str| {
+            let key: String = text
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect();
+            PROFICIENCY_TERMS
+                .iter()
+                .find(|(term, _)| *term == key)
+                .map(|(_, proficiency)| *proficiency)
+        };
+
+        lookup(fluency).or_else(|| {
+            fluency
+                .split(|c: char| !c.is_alphanumeric())
+                .find_map(lookup)
+        })
+    }
+}
+
 /// A publication entry
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 #[schemars(description = "A publication, paper, or patent")]
@@ -358,11 +785,13 @@ mod tests {
             },
             work: vec![WorkExperience {
                 company: "Tech Corp".to_string(),
+                logo: None,
                 position: "Senior Engineer".to_string(),
                 location: Some("San Francisco, CA".to_string()),
                 start_date: Some("2020-01".to_string()),
                 end_date: Some("Present".to_string()),
-                highlights: vec!["Led team of 5 engineers".to_string()],
+                current: None,
+                highlights: vec![Highlight::Plain("Led team of 5 engineers".to_string())],
             }],
             education: vec![Education {
                 institution: "MIT".to_string(),
@@ -403,6 +832,7 @@ mod tests {
                 url: Some("https://arxiv.org/abs/2312.00000".to_string()),
                 summary: None,
             }],
+            custom_sections: vec![],
             section_order: None,
             section_titles: None,
             show_header: None,
@@ -462,4 +892,84 @@ mod tests {
         assert_eq!(resume.education.len(), 1);
         assert_eq!(resume.skills.len(), 3);
     }
+
+    #[test]
+    fn test_mixed_highlight_forms() {
+        let work: WorkExperience = serde_json::from_value(serde_json::json!({
+            "company": "Tech Corp",
+            "position": "Engineer",
+            "highlights": [
+                "Plain bullet",
+                { "lead": "Led migration", "text": "that reduced costs by 30%" }
+            ]
+        }))
+        .unwrap();
+
+        assert!(matches!(&work.highlights[0], Highlight::Plain(text) if text == "Plain bullet"));
+        assert!(
+            matches!(&work.highlights[1], Highlight::Structured { lead, .. } if lead == "Led migration")
+        );
+        assert_eq!(
+            work.highlights[1].plain_text(),
+            "Led migration that reduced costs by 30%"
+        );
+    }
+
+    #[test]
+    fn test_alternate_field_names() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": {
+                "name": "Jane Smith",
+                "email": "jane@example.com",
+                "phone_number": "555-0100"
+            },
+            "work": [{
+                "name": "Tech Corp",
+                "jobTitle": "Staff Engineer",
+                "start_date": "2020-01"
+            }],
+            "education": [{ "school": "State University", "area": "Physics" }]
+        }))
+        .unwrap();
+
+        assert_eq!(resume.basics.phone.as_deref(), Some("555-0100"));
+        assert_eq!(resume.work[0].company, "Tech Corp");
+        assert_eq!(resume.work[0].position, "Staff Engineer");
+        assert_eq!(resume.work[0].start_date.as_deref(), Some("2020-01"));
+        assert_eq!(resume.education[0].institution, "State University");
+        assert_eq!(
+            resume.education[0].field_of_study.as_deref(),
+            Some("Physics")
+        );
+
+        // Canonical names are used when serializing
+        let json = serde_json::to_value(&resume).unwrap();
+        assert_eq!(json["work"][0]["position"], "Staff Engineer");
+        assert!(json["work"][0].get("jobTitle").is_none());
+    }
+
+    #[test]
+    fn test_language_proficiency_normalization() {
+        let proficiency = |fluency: &str| {
+            Language {
+                language: "Spanish".to_string(),
+                fluency: Some(fluency.to_string()),
+            }
+            .proficiency()
+        };
+
+        assert_eq!(proficiency("Native"), Some(Proficiency::Native));
+        assert_eq!(proficiency("native speaker"), Some(Proficiency::Native));
+        assert_eq!(proficiency("C1 - Advanced"), Some(Proficiency::Fluent));
+        assert_eq!(
+            proficiency("Upper-Intermediate"),
+            Some(Proficiency::Professional)
+        );
+        assert_eq!(proficiency("B1"), Some(Proficiency::Intermediate));
+        assert_eq!(proficiency("a2"), Some(Proficiency::Basic));
+        assert_eq!(proficiency("Reads menus"), None);
+
+        assert_eq!(Proficiency::Native.level(), Proficiency::LEVELS);
+        assert_eq!(Proficiency::Intermediate.level(), 2);
+    }
 }
@@ -0,0 +1,256 @@
+//! Fetching of URL-referenced JSON payloads
+//!
+//! Lets pipelines that keep resume JSON in object storage pass a `source_url`
+//! instead of the payload itself. Because the server makes the request on the
+//! caller's behalf, every fetch is guarded against SSRF:
+//! - only `http`/`https` URLs are accepted
+//! - hosts resolving to loopback, private, link-local, multicast, reserved or
+//!   otherwise non-public addresses are rejected (IPv4 addresses embedded in
+//!   IPv6 are unwrapped first), and the connection is pinned to the vetted
+//!   addresses
+//! - redirects are not followed
+//! - the response size and total request time are bounded
+//!
+//! The body is parsed like JSON `content`, so an object that repeats a key is
+//! rejected instead of silently keeping the last value.
+
+use crate::mcp::input_format::parse_json_strict;
+use serde_json::Value;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+/// Maximum size of a fetched payload (1 MiB)
+const MAX_SOURCE_BYTES: usize = 1024 * 1024;
+
+/// Maximum time allowed for the whole request
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Limits applied when fetching a remote payload
+#[derive(Debug, Clone)]
+pub struct FetchPolicy {
+    /// Allow hosts that resolve to non-public addresses (tests only)
+    pub allow_private_hosts: bool,
+    /// Maximum accepted response size in bytes
+    pub max_bytes: usize,
+    /// Timeout for the whole request
+    pub timeout: Duration,
+}
+
+impl Default for FetchPolicy {
+    fn default() -> Self {
+        Self {
+            allow_private_hosts: false,
+            max_bytes: MAX_SOURCE_BYTES,
+            timeout: FETCH_TIMEOUT,
+        }
+    }
+}
+
+/// Fetch a JSON document from `url`, enforcing the given policy
+pub async fn fetch_json(url: &str, policy: &FetchPolicy) -> Result<Value, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL '{}': {}", url, e))?;
+
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!(
+            "Unsupported URL scheme '{}'. Only http and https are allowed.",
+            parsed.scheme()
+        ));
+    }
+
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| format!("URL '{}' has no host", url))?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+    let port = parsed.port_or_known_default().unwrap_or(80);
+
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map_err(|e| format!("Failed to resolve host '{}': {}", host, e))?
+        .collect();
+
+    if addrs.is_empty() {
+        return Err(format!("Host '{}' did not resolve to any address", host));
+    }
+
+    if !policy.allow_private_hosts
+        && let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip()))
+    {
+        return Err(format!(
+            "Host '{}' resolves to a non-public address ({}); refusing to fetch",
+            host,
+            addr.ip()
+        ));
+    }
+
+    // Pin the connection to the addresses we just checked so a second DNS
+    // lookup cannot redirect the request to an internal host.
+    let client = reqwest::Client::builder()
+        .timeout(policy.timeout)
+        .redirect(reqwest::redirect::Policy::none())
+        .resolve_to_addrs(&host, &addrs)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut response = client
+        .get(parsed)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Server responded with status {}", response.status()));
+    }
+
+    if let Some(length) = response.content_length()
+        && length > policy.max_bytes as u64
+    {
+        return Err(format!(
+            "Payload is {} bytes, which exceeds the {} byte limit",
+            length, policy.max_bytes
+        ));
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Failed to read response body: {}", e))?
+    {
+        if body.len() + chunk.len() > policy.max_bytes {
+            return Err(format!(
+                "Payload exceeds the {} byte limit",
+                policy.max_bytes
+            ));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    std::str::from_utf8(&body)
+        .map_err(|e| e.to_string())
+        .and_then(|text| parse_json_strict(text).map_err(|e| e.to_string()))
+        .map_err(|e| format!("Response is not valid JSON: {}", e))
+}
+
+/// Returns true if the address is publicly routable
+///
+/// IPv6 addresses that embed an IPv4 address (mapped, compatible, NAT64 and
+/// 6to4) are judged by the embedded address.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            let this_network = a == 0; // 0.0.0.0/8
+            let shared = a == 100 && (64..128).contains(&b); // 100.64.0.0/10 (CGNAT)
+            let reserved = a >= 240; // 240.0.0.0/4, including broadcast
+            !(v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_multicast()
+                || v4.is_documentation()
+                || this_network
+                || shared
+                || reserved)
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = embedded_ipv4(v6) {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            let unique_local = (first & 0xfe00) == 0xfc00; // fc00::/7
+            let link_local = (first & 0xffc0) == 0xfe80; // fe80::/10
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                || unique_local
+                || link_local)
+        }
+    }
+}
+
+/// The IPv4 address an IPv6 address carries, if it is a translation form
+fn embedded_ipv4(v6: Ipv6Addr) -> Option<Ipv4Addr> {
+    let segments = v6.segments();
+    let octets = v6.octets();
+    let last_32 = Ipv4Addr::new(octets[12], octets[13], octets[14], octets[15]);
+
+    if let Some(v4) = v6.to_ipv4_mapped() {
+        // ::ffff:0:0/96
+        Some(v4)
+    } else if segments[..6] == [0; 6] && !v6.is_loopback() && !v6.is_unspecified() {
+        // ::/96, IPv4-compatible (deprecated)
+        Some(last_32)
+    } else if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+        // 64:ff9b::/96, NAT64
+        Some(last_32)
+    } else if segments[0] == 0x2002 {
+        // 2002::/16, 6to4
+        Some(Ipv4Addr::new(octets[2], octets[3], octets[4], octets[5]))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_public_ip() {
+        let cases = [
+            ("93.184.216.34", true),
+            ("127.0.0.1", false),
+            ("10.0.0.5", false),
+            ("192.168.1.1", false),
+            ("169.254.169.254", false),
+            ("100.64.0.1", false),
+            ("0.0.0.0", false),
+            ("0.1.2.3", false),
+            ("224.0.0.1", false),
+            ("239.255.255.250", false),
+            ("240.0.0.1", false),
+            ("255.255.255.255", false),
+            ("2606:2800:220:1:248:1893:25c8:1946", true),
+            ("::", false),
+            ("::1", false),
+            ("fd00::1", false),
+            ("fe80::1", false),
+            ("ff02::1", false),
+            ("::ffff:127.0.0.1", false),
+            ("::ffff:93.184.216.34", true),
+            ("::127.0.0.1", false),
+            ("::10.0.0.5", false),
+            ("::93.184.216.34", true),
+            ("64:ff9b::7f00:1", false),
+            ("64:ff9b::a9fe:a9fe", false),
+            ("64:ff9b::5db8:d822", true),
+            ("2002:7f00:1::", false),
+            ("2002:c0a8:101::1", false),
+            ("2002:5db8:d822::1", true),
+        ];
+
+        for (address, public) in cases {
+            assert_eq!(
+                is_public_ip(address.parse().unwrap()),
+                public,
+                "{} should be {}",
+                address,
+                if public { "public" } else { "non-public" }
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_loopback() {
+        let result = fetch_json("http://127.0.0.1:9/resume.json", &FetchPolicy::default()).await;
+        let error = result.unwrap_err();
+        assert!(error.contains("non-public"), "Unexpected error: {}", error);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_non_http_scheme() {
+        let result = fetch_json("file:///etc/passwd", &FetchPolicy::default()).await;
+        assert!(result.unwrap_err().contains("scheme"));
+    }
+}
@@ -1,16 +1,28 @@
 use rmcp::{ErrorData, ServerHandler, ServiceExt, model::*};
 use std::env;
-use tracing::{Level, info};
+use std::time::Instant;
+use tracing::{Level, error, info};
 use tracing_subscriber::FmtSubscriber;
 
+mod base_url;
 mod documents;
+mod fetch;
 mod mcp;
+mod mcp_headers;
+mod origins;
+mod rate_limit;
 mod storage;
 mod typst;
 
-use mcp::{prompts, resources, tools};
+use base_url::BaseUrl;
+use mcp::{completion, prompts, protocol, resources, tools};
+use origins::OriginAllowlist;
+use rate_limit::RateLimiter;
 use storage::FileStorage;
 
+/// Header carrying the MCP session id on streamable HTTP requests
+const SESSION_ID_HEADER: &str = "mcp-session-id";
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize tracing
@@ -22,6 +34,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     .expect("Failed to set tracing subscriber");
 
     info!("Starting docgen-mcp server");
+    info!(
+        "Compilation threads: {}",
+        crate::typst::pool::compile_pool().threads()
+    );
+    if let Some(path) = crate::typst::prelude::init_from_env()? {
+        info!("Typst prelude loaded from {}", path);
+    }
+    info!(
+        "Default page size: {}",
+        crate::typst::paper::init_from_env()?.as_str()
+    );
 
     // Check if HTTP mode is requested via --http flag or PORT environment variable
     let args: Vec<String> = env::args().collect();
@@ -42,29 +65,35 @@ async fn run_stdio_server() -> Result<(), Box<dyn std::error::Error>> {
 
     info!("Starting MCP server with stdio transport (Claude Desktop mode)");
 
+    // Parse bundled fonts up front so the first request isn't slow
+    prewarm();
+
     // Create the server handler (no file storage or base URL for stdio mode)
     let server = DocgenServer::new(None, None);
 
     // Create stdio transport
     let transport = AsyncRwTransport::new(stdin(), stdout());
 
-    // Run the server
-    server.serve(transport).await?;
+    // Run the server until the client closes stdin; returning earlier would
+    // drop the service and cancel every request after `initialize`
+    server.serve(transport).await?.waiting().await?;
 
     Ok(())
 }
 
 async fn run_http_server() -> Result<(), Box<dyn std::error::Error>> {
     use axum::{
-        Router,
+        Json, Router,
         extract::{Path, State},
-        http::{StatusCode, header},
+        http::{HeaderMap, StatusCode, header},
         response::{IntoResponse, Response},
     };
     use rmcp::transport::streamable_http_server::{
         StreamableHttpService, session::local::LocalSessionManager,
     };
     use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
     use uuid::Uuid;
 
     // Get port from environment or use default
@@ -76,19 +105,22 @@ async fn run_http_server() -> Result<(), Box<dyn std::error::Error>> {
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
 
     // Determine base URL for download links
-    // Use BASE_URL env var if set (for production), otherwise construct from port
-    let base_url = env::var("BASE_URL").unwrap_or_else(|_| {
-        format!("http://localhost:{}", port)
-    });
+    // Use DOCGEN_PUBLIC_BASE_URL if set (for production), otherwise derive it per request
+    let base_url = BaseUrl::from_env(format!("http://localhost:{}", port));
 
     info!(
         "Starting MCP server with Streamable HTTP transport on {}",
         addr
     );
-    info!("Download URL base: {}", base_url);
+    info!("Download URL base: {}", base_url.describe());
+
+    // Load fonts in the background; /readyz reports ready once compilation works
+    let ready = Arc::new(AtomicBool::new(false));
+    let ready_flag = ready.clone();
+    tokio::task::spawn_blocking(move || ready_flag.store(prewarm(), Ordering::Release));
 
     // Create file storage and start cleanup task
-    let file_storage = FileStorage::new();
+    let file_storage = FileStorage::from_env();
     file_storage.clone().start_cleanup_task();
 
     // Create the streamable HTTP service with storage
@@ -116,11 +148,11 @@ async fn run_http_server() -> Result<(), Box<dyn std::error::Error>> {
         // Retrieve file
         match storage.retrieve(&id).await {
             Some(file) => {
-                // Return the PDF with appropriate headers
+                // Return the file with the type recorded for its document type
                 (
                     StatusCode::OK,
                     [
-                        (header::CONTENT_TYPE, "application/pdf"),
+                        (header::CONTENT_TYPE, file.mime_type),
                         (
                             header::CONTENT_DISPOSITION,
                             &format!("inline; filename=\"{}\"", file.filename),
@@ -135,36 +167,178 @@ async fn run_http_server() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // Session file listing (only the caller's own session is visible)
+    async fn list_files(State(storage): State<FileStorage>, headers: HeaderMap) -> Response {
+        let session_id = match headers.get(SESSION_ID_HEADER).and_then(|v| v.to_str().ok()) {
+            Some(id) => id,
+            None => {
+                return (StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id header").into_response();
+            }
+        };
+
+        let files = storage.list_session(session_id).await;
+        Json(serde_json::json!({ "files": files })).into_response()
+    }
+
+    // Raw rendering: takes a generate tool's input and responds with the PDF itself
+    async fn render_document(
+        Path(document_type): Path<String>,
+        Json(input): Json<serde_json::Value>,
+    ) -> Response {
+        let context = tools::ToolContext::stdio();
+        match tools::render_pdf(&document_type, input, &context).await {
+            Ok((pdf, filename)) => (
+                StatusCode::OK,
+                [
+                    (header::CONTENT_TYPE, "application/pdf"),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        &format!("inline; filename=\"{}\"", filename),
+                    ),
+                ],
+                pdf,
+            )
+                .into_response(),
+            Err(error) => (StatusCode::UNPROCESSABLE_ENTITY, Json(error)).into_response(),
+        }
+    }
+
+    // Early deletion handler (lets users purge sensitive files before expiry)
+    async fn delete_file(
+        State(storage): State<FileStorage>,
+        Path(file_id): Path<String>,
+    ) -> Response {
+        let id = match Uuid::parse_str(&file_id) {
+            Ok(id) => id,
+            Err(_) => {
+                return (StatusCode::BAD_REQUEST, "Invalid file ID").into_response();
+            }
+        };
+
+        if storage.remove(&id).await {
+            StatusCode::NO_CONTENT.into_response()
+        } else {
+            (StatusCode::NOT_FOUND, "File not found or expired").into_response()
+        }
+    }
+
     // Create axum router with MCP endpoint and file downloads
-    let app = Router::new()
+    let mcp = Router::new()
         .nest_service("/mcp", service)
-        .route("/files/{id}", axum::routing::get(download_file))
+        .layer(axum::middleware::from_fn(mcp_headers::require_mcp_headers));
+    let mut app = Router::new()
+        .merge(mcp)
+        .route("/files", axum::routing::get(list_files))
+        .route(
+            "/render/{document_type}",
+            axum::routing::post(render_document),
+        )
+        .route(
+            "/files/{id}",
+            axum::routing::get(download_file).delete(delete_file),
+        )
         .with_state(file_storage);
 
+    // Rate limit the routes above; health endpoints are added after the layer
+    match RateLimiter::from_env() {
+        Some(limiter) => {
+            info!(
+                "Rate limit: {} requests/minute per IP",
+                limiter.per_minute()
+            );
+            app = app.layer(axum::middleware::from_fn_with_state(
+                limiter,
+                rate_limit::limit,
+            ));
+        }
+        None => info!("Rate limiting disabled"),
+    }
+
+    // Checked before rate limiting, so rejected origins and preflights cost no tokens
+    match OriginAllowlist::from_env()? {
+        Some(allowlist) => {
+            info!("Accepted browser origins: {}", allowlist.describe());
+            app = app.layer(axum::middleware::from_fn_with_state(
+                allowlist,
+                origins::check_origin,
+            ));
+        }
+        None => info!("Origin check disabled: browser requests from any origin are accepted"),
+    }
+
+    let app = app
+        // Liveness: the process is up and accepting connections
+        .route("/healthz", axum::routing::get(|| async { "ok" }))
+        // Readiness: fonts are loaded and a trial compile has succeeded
+        .route(
+            "/readyz",
+            axum::routing::get(move || async move {
+                if ready.load(Ordering::Acquire) {
+                    (StatusCode::OK, "ready")
+                } else {
+                    (StatusCode::SERVICE_UNAVAILABLE, "not ready")
+                }
+            }),
+        );
+
     info!("MCP server listening on {} (endpoint: /mcp)", addr);
-    info!("File download endpoint: /files/:id");
+    info!("File download endpoint: /files/:id (DELETE to purge early)");
+    info!("Session file listing: /files (requires Mcp-Session-Id header)");
+    info!("Raw rendering endpoint: POST /render/:document_type");
+    info!("Health endpoints: /healthz (liveness), /readyz (readiness)");
 
     // Start the server
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app)
-        .with_graceful_shutdown(async {
-            tokio::signal::ctrl_c().await.unwrap();
-        })
-        .await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async {
+        tokio::signal::ctrl_c().await.unwrap();
+    })
+    .await?;
 
     Ok(())
 }
 
+/// Load the font cache, run a trial compile, and compile every template's
+/// example, returning whether all of it succeeded
+fn prewarm() -> bool {
+    let started = Instant::now();
+    crate::typst::world::DocgenWorld::prewarm();
+
+    match crate::typst::compiler::smoke_test() {
+        Ok(()) => {
+            info!("Font cache prewarmed in {:?}", started.elapsed());
+        }
+        Err(diags) => {
+            error!("Trial compile failed after prewarming fonts: {:?}", diags);
+            return false;
+        }
+    }
+
+    match crate::typst::compiler::check_templates() {
+        Ok(()) => true,
+        Err(failures) => {
+            error!(
+                "FATAL: embedded templates do not compile; generating these documents will fail:\n{}",
+                failures
+            );
+            false
+        }
+    }
+}
+
 // The main server handler
 struct DocgenServer {
     /// Optional file storage for HTTP mode
     file_storage: Option<FileStorage>,
     /// Base URL for HTTP mode (for generating download links)
-    base_url: Option<String>,
+    base_url: Option<BaseUrl>,
 }
 
 impl DocgenServer {
-    fn new(file_storage: Option<FileStorage>, base_url: Option<String>) -> Self {
+    fn new(file_storage: Option<FileStorage>, base_url: Option<BaseUrl>) -> Self {
         Self {
             file_storage,
             base_url,
@@ -175,8 +349,9 @@ impl DocgenServer {
 impl ServerHandler for DocgenServer {
     fn get_info(&self) -> ServerInfo {
         ServerInfo {
-            protocol_version: ProtocolVersion::V_2025_03_26,
+            protocol_version: protocol::PREFERRED_PROTOCOL_VERSION,
             capabilities: ServerCapabilities::builder()
+                .enable_completions()
                 .enable_prompts()
                 .enable_resources()
                 .enable_tools()
@@ -188,23 +363,24 @@ impl ServerHandler for DocgenServer {
                 website_url: None,
                 icons: None,
             },
-            instructions: Some(
-                "A Model Context Protocol server for programmatic document generation, \
-                 powered by Typst. Use this server to generate professionally typeset \
-                 documents like resumes and CVs.\n\n\
-                 RECOMMENDED WORKFLOW FOR AI AGENTS:\n\
-                 1. Use 'get_resume_best_practices' tool to understand writing guidelines and best practices\n\
-                 2. Use 'get_resume_schema' tool to see the exact JSON structure required\n\
-                 3. Gather information from the user and construct the resume JSON\n\
-                 4. Use 'validate_resume' tool to check the JSON structure\n\
-                 5. Use 'generate_resume' tool to create the final PDF\n\n\
-                 Following this workflow ensures high-quality, properly structured documents.\n\n\
-                 ALTERNATIVE: Advanced agents can also use:\n\
-                 - PROMPT 'resume-best-practices' for comprehensive guidance\n\
-                 - RESOURCE 'docgen://schemas/resume' for schema definition"
-                    .to_string(),
-            ),
+            instructions: Some(tools::server_instructions()),
+        }
+    }
+
+    async fn initialize(
+        &self,
+        request: InitializeRequestParam,
+        context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<InitializeResult, ErrorData> {
+        let protocol_version = protocol::negotiate_protocol_version(&request.protocol_version);
+        if context.peer.peer_info().is_none() {
+            context.peer.set_peer_info(request);
         }
+
+        Ok(ServerInfo {
+            protocol_version,
+            ..self.get_info()
+        })
     }
 
     async fn list_resources(
@@ -252,7 +428,7 @@ impl ServerHandler for DocgenServer {
         request: GetPromptRequestParam,
         _context: rmcp::service::RequestContext<rmcp::RoleServer>,
     ) -> Result<GetPromptResult, ErrorData> {
-        match prompts::get_prompt(&request.name) {
+        match prompts::get_prompt_with_arguments(&request.name, request.arguments.as_ref()) {
             Some(result) => Ok(result),
             None => Err(ErrorData::resource_not_found(
                 format!("Prompt not found: {}", request.name),
@@ -261,6 +437,16 @@ impl ServerHandler for DocgenServer {
         }
     }
 
+    async fn complete(
+        &self,
+        request: CompleteRequestParam,
+        _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<CompleteResult, ErrorData> {
+        Ok(CompleteResult {
+            completion: completion::complete(&request.r#ref, &request.argument),
+        })
+    }
+
     async fn list_tools(
         &self,
         _request: Option<PaginatedRequestParam>,
@@ -276,23 +462,31 @@ impl ServerHandler for DocgenServer {
     async fn call_tool(
         &self,
         request: CallToolRequestParam,
-        _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::RoleServer>,
     ) -> Result<CallToolResult, ErrorData> {
         // Convert Map<String, Value> to Value::Object
         let arguments = serde_json::Value::Object(request.arguments.unwrap_or_default());
 
         // Create tool context based on transport mode
         let tool_context = if let (Some(storage), Some(base_url)) = (&self.file_storage, &self.base_url) {
-            tools::ToolContext::http(storage.clone(), base_url.clone())
+            // The streamable HTTP transport exposes the request head in the extensions
+            let headers = context
+                .extensions
+                .get::<axum::http::request::Parts>()
+                .map(|parts| &parts.headers);
+            let session_id = headers
+                .and_then(|h| h.get(SESSION_ID_HEADER))
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            tools::ToolContext {
+                session_id,
+                ..tools::ToolContext::http(storage.clone(), base_url.resolve(headers))
+            }
         } else {
             tools::ToolContext::stdio()
         };
 
-        match tools::call_tool(&request.name, arguments, &tool_context).await {
-            Ok(result) => Ok(CallToolResult::structured(result)),
-            Err(e) => Ok(CallToolResult::structured_error(serde_json::json!({
-                "error": e
-            }))),
-        }
+        let result = tools::call_tool(&request.name, arguments, &tool_context).await;
+        Ok(tools::into_call_tool_result(&request.name, result))
     }
 }
@@ -0,0 +1,273 @@
+//! ATS-readiness scoring for resumes
+//!
+//! Applicant tracking systems reward resumes that are easy to parse and rich
+//! in concrete signals. The score is a weighted sum of four categories, each
+//! computed from the deserialized `Resume`, with suggestions for every category
+//! that falls below its threshold.
+
+use serde::Serialize;
+
+use crate::documents::Resume;
+use crate::documents::resume::Highlight;
+
+/// Points available for quantified highlights
+pub const METRICS_WEIGHT: f64 = 30.0;
+
+/// Points available for highlights that start with an action verb
+pub const ACTION_VERBS_WEIGHT: f64 = 25.0;
+
+/// Points available for contact completeness
+pub const CONTACT_WEIGHT: f64 = 20.0;
+
+/// Points available for section coverage
+pub const SECTIONS_WEIGHT: f64 = 25.0;
+
+/// Share of highlights that should contain a number, percentage, or amount
+pub const METRICS_THRESHOLD: f64 = 0.5;
+
+/// Share of highlights that should start with an action verb
+pub const ACTION_VERBS_THRESHOLD: f64 = 0.7;
+
+/// Verbs recruiters and ATS keyword models expect at the start of a highlight
+pub const ACTION_VERBS: &[&str] = &[
+    "accelerated",
+    "achieved",
+    "analyzed",
+    "architected",
+    "automated",
+    "built",
+    "collaborated",
+    "created",
+    "cut",
+    "delivered",
+    "deployed",
+    "designed",
+    "developed",
+    "drove",
+    "engineered",
+    "established",
+    "grew",
+    "implemented",
+    "improved",
+    "increased",
+    "launched",
+    "led",
+    "managed",
+    "mentored",
+    "migrated",
+    "optimized",
+    "organized",
+    "owned",
+    "published",
+    "redesigned",
+    "reduced",
+    "refactored",
+    "resolved",
+    "scaled",
+    "shipped",
+    "spearheaded",
+    "streamlined",
+    "trained",
+    "transformed",
+    "wrote",
+];
+
+/// Points awarded per category
+#[derive(Debug, Serialize)]
+pub struct AtsBreakdown {
+    pub metrics: u8,
+    pub action_verbs: u8,
+    pub contact: u8,
+    pub sections: u8,
+}
+
+/// Result of the `ats_score` tool
+#[derive(Debug, Serialize)]
+pub struct AtsReport {
+    /// Overall score from 0 to 100
+    pub score: u8,
+    pub breakdown: AtsBreakdown,
+    /// Itemized, actionable suggestions (empty for a strong resume)
+    pub suggestions: Vec<String>,
+}
+
+/// Score a resume for ATS-friendliness
+pub fn score_resume(resume: &Resume) -> AtsReport {
+    let mut suggestions = Vec::new();
+
+    let highlight_texts: Vec<String> = resume
+        .work
+        .iter()
+        .flat_map(|w| &w.highlights)
+        .chain(resume.projects.iter().flat_map(|p| &p.highlights))
+        .map(Highlight::plain_text)
+        .collect();
+    let highlights: Vec<&str> = highlight_texts
+        .iter()
+        .map(|h| h.trim())
+        .filter(|h| !h.is_empty())
+        .collect();
+
+    // Quantified metrics
+    let quantified = ratio(&highlights, is_quantified);
+    if highlights.is_empty() {
+        suggestions.push(
+            "Add highlights to your work experience describing what you accomplished.".to_string(),
+        );
+    } else if quantified < METRICS_THRESHOLD {
+        suggestions.push(format!(
+            "Only {:.0}% of highlights include numbers. Quantify impact (e.g., \"reduced latency by 40%\").",
+            quantified * 100.0
+        ));
+    }
+
+    // Action verbs
+    let action_verbs = ratio(&highlights, starts_with_action_verb);
+    if !highlights.is_empty() && action_verbs < ACTION_VERBS_THRESHOLD {
+        suggestions.push(format!(
+            "Only {:.0}% of highlights start with an action verb. Lead with verbs like \"Led\", \"Built\", or \"Reduced\".",
+            action_verbs * 100.0
+        ));
+    }
+
+    // Contact completeness
+    let basics = &resume.basics;
+    let contact_items = [
+        ("an email address", !basics.email.trim().is_empty()),
+        ("a phone number", has_text(&basics.phone)),
+        ("a location", has_text(&basics.location)),
+        (
+            "a profile link (e.g., LinkedIn)",
+            !basics.profiles.is_empty(),
+        ),
+    ];
+    let contact = fraction(&contact_items);
+    for (label, present) in contact_items {
+        if !present {
+            suggestions.push(format!("Add {} to your contact information.", label));
+        }
+    }
+
+    // Section coverage
+    let section_items = [
+        ("a professional summary", has_text(&basics.summary)),
+        ("work experience", !resume.work.is_empty()),
+        ("education", !resume.education.is_empty()),
+        ("a skills section", !resume.skills.is_empty()),
+    ];
+    let sections = fraction(&section_items);
+    for (label, present) in section_items {
+        if !present {
+            suggestions.push(format!("Add {}; ATS filters commonly look for it.", label));
+        }
+    }
+
+    let breakdown = AtsBreakdown {
+        metrics: points(quantified, METRICS_WEIGHT),
+        action_verbs: points(action_verbs, ACTION_VERBS_WEIGHT),
+        contact: points(contact, CONTACT_WEIGHT),
+        sections: points(sections, SECTIONS_WEIGHT),
+    };
+    let score = breakdown.metrics + breakdown.action_verbs + breakdown.contact + breakdown.sections;
+
+    AtsReport {
+        score,
+        breakdown,
+        suggestions,
+    }
+}
+
+/// Whether a highlight contains a number, percentage, or currency amount
+fn is_quantified(highlight: &str) -> bool {
+    highlight
+        .chars()
+        .any(|c| c.is_ascii_digit() || matches!(c, '%' | '$' | '€' | '£'))
+}
+
+fn starts_with_action_verb(highlight: &str) -> bool {
+    highlight
+        .split_whitespace()
+        .next()
+        .map(|word| {
+            let word = word
+                .trim_matches(|c: char| !c.is_alphabetic())
+                .to_lowercase();
+            ACTION_VERBS.contains(&word.as_str())
+        })
+        .unwrap_or(false)
+}
+
+fn has_text(value: &Option<String>) -> bool {
+    value.as_deref().is_some_and(|v| !v.trim().is_empty())
+}
+
+/// Share of items matching a predicate (0 when there are no items)
+fn ratio(items: &[&str], predicate: impl Fn(&str) -> bool) -> f64 {
+    if items.is_empty() {
+        return 0.0;
+    }
+    items.iter().filter(|item| predicate(item)).count() as f64 / items.len() as f64
+}
+
+/// Share of checklist entries that are present
+fn fraction(items: &[(&str, bool)]) -> f64 {
+    items.iter().filter(|(_, present)| *present).count() as f64 / items.len() as f64
+}
+
+fn points(share: f64, weight: f64) -> u8 {
+    (share * weight).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strong_resume_scores_higher_than_weak() {
+        let strong: Resume =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+        let weak: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Jane Smith", "email": "jane@example.com" },
+            "work": [{
+                "company": "Tech Corp",
+                "position": "Engineer",
+                "highlights": ["Responsible for the backend", "Worked on various projects"]
+            }]
+        }))
+        .unwrap();
+
+        let strong_report = score_resume(&strong);
+        let weak_report = score_resume(&weak);
+
+        assert!(strong_report.score > weak_report.score);
+        assert!(weak_report.score <= 100 && strong_report.score <= 100);
+        assert!(
+            weak_report
+                .suggestions
+                .iter()
+                .any(|s| s.contains("numbers"))
+        );
+        assert!(
+            weak_report
+                .suggestions
+                .iter()
+                .any(|s| s.contains("action verb"))
+        );
+        assert!(weak_report.suggestions.iter().any(|s| s.contains("phone")));
+    }
+
+    #[test]
+    fn test_action_verb_detection() {
+        assert!(starts_with_action_verb("Led a team of 5"));
+        assert!(starts_with_action_verb("  reduced costs"));
+        assert!(!starts_with_action_verb("Responsible for deployments"));
+        assert!(!starts_with_action_verb(""));
+    }
+
+    #[test]
+    fn test_quantified_detection() {
+        assert!(is_quantified("Cut latency by 40%"));
+        assert!(is_quantified("Saved $2M annually"));
+        assert!(!is_quantified("Improved reliability"));
+    }
+}
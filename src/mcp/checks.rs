@@ -0,0 +1,565 @@
+//! Semantic checks for deserialized documents
+//!
+//! Serde only verifies that a payload has the right shape. The checks in this
+//! module run on a successfully deserialized document and report content
+//! problems: errors block generation, warnings are informational and are
+//! returned alongside a successful result.
+
+use crate::documents::Resume;
+use crate::documents::resume::SECTION_NAMES;
+use crate::mcp::tools::ValidationError;
+use serde_json::Value;
+
+/// Findings from the semantic checks
+#[derive(Debug, Default)]
+pub struct CheckReport {
+    /// Problems that make the document invalid
+    pub errors: Vec<ValidationError>,
+    /// Non-blocking suggestions
+    pub warnings: Vec<ValidationError>,
+}
+
+/// Run all semantic checks on a resume
+pub fn check_resume(resume: &Resume) -> CheckReport {
+    let mut report = CheckReport::default();
+
+    check_sparse(resume, &mut report);
+    check_custom_sections(resume, &mut report);
+    check_profile_networks(resume, &mut report);
+    check_work_dates(resume, &mut report);
+    check_section_order(resume, &mut report);
+    check_filler_phrases(resume, &mut report);
+    check_summary_length(resume, &mut report);
+
+    report
+}
+
+/// Remove control characters from every string in a raw payload
+///
+/// Pasted text can carry characters such as NUL or vertical tab that Typst
+/// renders as garbage. Tabs and line breaks are kept. This runs on the JSON
+/// before deserialization so it covers every text field; each cleaned field
+/// gets a warning with its path.
+pub fn strip_control_characters(value: &mut Value) -> Vec<ValidationError> {
+    let mut warnings = Vec::new();
+    strip_control_characters_at(value, "", &mut warnings);
+    warnings
+}
+
+fn strip_control_characters_at(value: &mut Value, path: &str, warnings: &mut Vec<ValidationError>) {
+    match value {
+        Value::String(text) => {
+            let mut removed: Vec<char> =
+                text.chars().filter(|&c| is_disallowed_control(c)).collect();
+            if removed.is_empty() {
+                return;
+            }
+            text.retain(|c| !is_disallowed_control(c));
+
+            let count = removed.len();
+            removed.sort_unstable();
+            removed.dedup();
+            let codes: Vec<String> = removed
+                .iter()
+                .map(|c| format!("U+{:04X}", *c as u32))
+                .collect();
+            warnings.push(ValidationError::new(
+                path,
+                format!(
+                    "Removed {} control character(s) ({}) that would not render",
+                    count,
+                    codes.join(", ")
+                ),
+            ));
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                strip_control_characters_at(item, &format!("{}[{}]", path, i), warnings);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                strip_control_characters_at(item, &child, warnings);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_disallowed_control(c: char) -> bool {
+    c.is_control() && !matches!(c, '\t' | '\n' | '\r')
+}
+
+/// Warn when the resume has nothing beyond basic contact information
+fn check_sparse(resume: &Resume, report: &mut CheckReport) {
+    let has_sections = !resume.work.is_empty()
+        || !resume.education.is_empty()
+        || !resume.skills.is_empty()
+        || !resume.projects.is_empty()
+        || !resume.certifications.is_empty()
+        || !resume.awards.is_empty()
+        || !resume.languages.is_empty()
+        || !resume.publications.is_empty()
+        || !resume.custom_sections.is_empty();
+
+    if !has_sections {
+        report.warnings.push(ValidationError::new(
+            "",
+            "Resume is sparse: only basic contact information is present. \
+             Consider adding work experience, education, or skills.",
+        ));
+    }
+}
+
+/// Custom section titles must be non-empty and unique
+fn check_custom_sections(resume: &Resume, report: &mut CheckReport) {
+    let mut seen = std::collections::HashSet::new();
+
+    for (i, section) in resume.custom_sections.iter().enumerate() {
+        let title = section.title.trim();
+        let path = format!("customSections[{}].title", i);
+
+        if title.is_empty() {
+            report.errors.push(ValidationError::new(
+                path,
+                "Custom section title must not be empty",
+            ));
+        } else if !seen.insert(title.to_lowercase()) {
+            report.errors.push(ValidationError::new(
+                path,
+                format!("Duplicate custom section title '{}'", title),
+            ));
+        }
+    }
+}
+
+/// Warn about profile networks that normalization does not recognize
+fn check_profile_networks(resume: &Resume, report: &mut CheckReport) {
+    for (i, profile) in resume.basics.profiles.iter().enumerate() {
+        if profile.canonical_network().is_none() {
+            report.warnings.push(ValidationError::new(
+                format!("basics.profiles[{}].network", i),
+                format!(
+                    "Unrecognized network '{}'; it will be shown exactly as written",
+                    profile.network
+                ),
+            ));
+        }
+    }
+}
+
+/// Flag work entries whose `current` flag and end date disagree
+fn check_work_dates(resume: &Resume, report: &mut CheckReport) {
+    for (i, w) in resume.work.iter().enumerate() {
+        let path = format!("work[{}]", i);
+        let current = w.current.unwrap_or(false);
+        let end_is_present = w
+            .end_date
+            .as_deref()
+            .is_some_and(|d| d.trim().eq_ignore_ascii_case("present"));
+
+        if current && w.end_date.is_some() && !end_is_present {
+            report.errors.push(ValidationError::new(
+                path,
+                format!(
+                    "Position at '{}' is marked current but also has endDate '{}'; remove one of them",
+                    w.company,
+                    w.end_date.as_deref().unwrap_or_default()
+                ),
+            ));
+        } else if !current && w.start_date.is_some() && w.end_date.is_none() {
+            report.warnings.push(ValidationError::new(
+                path,
+                format!(
+                    "Position at '{}' has a startDate but no endDate; set endDate, or current: true if it is ongoing",
+                    w.company
+                ),
+            ));
+        }
+    }
+}
+
+/// Warn about `sectionOrder` entries that will not produce a section
+///
+/// A listed section with no entries is skipped by the template, which is easy
+/// to mistake for a rendering bug, so each one is reported by position.
+fn check_section_order(resume: &Resume, report: &mut CheckReport) {
+    let Some(order) = &resume.section_order else {
+        return;
+    };
+
+    for (i, name) in order.iter().enumerate() {
+        let custom = resume.custom_sections.iter().find(|c| c.title == *name);
+        let message = match custom {
+            Some(section) if section.items.is_empty() => format!(
+                "Custom section '{}' is listed in sectionOrder but has no items, so it will not appear",
+                name
+            ),
+            Some(_) => continue,
+            None if !SECTION_NAMES.contains(&name.as_str()) => format!(
+                "'{}' is listed in sectionOrder but is neither a standard section nor a customSections title, so it will not appear",
+                name
+            ),
+            None if standard_section_is_empty(resume, name) => format!(
+                "Section '{}' is listed in sectionOrder but has no entries, so it will not appear",
+                name
+            ),
+            None => continue,
+        };
+        report.warnings.push(ValidationError::new(
+            format!("sectionOrder[{}]", i),
+            message,
+        ));
+    }
+}
+
+/// Longest summary, in sentences, before it is flagged as too long
+///
+/// The best-practices prompt asks for 2-3 sentences; one more is tolerated.
+const MAX_SUMMARY_SENTENCES: usize = 4;
+
+/// Longest summary, in words, before it is flagged as too long
+const MAX_SUMMARY_WORDS: usize = 80;
+
+/// Warn when the professional summary reads as a full paragraph
+fn check_summary_length(resume: &Resume, report: &mut CheckReport) {
+    let Some(summary) = resume.basics.summary.as_deref() else {
+        return;
+    };
+    let sentences = sentence_count(summary);
+    let words = summary.split_whitespace().count();
+
+    if sentences > MAX_SUMMARY_SENTENCES || words > MAX_SUMMARY_WORDS {
+        report.warnings.push(ValidationError::new(
+            "basics.summary",
+            format!(
+                "Summary is {} sentences ({} words); shorten it to 2-3 sentences so it can be read at a glance, \
+                 and move details into work highlights",
+                sentences, words
+            ),
+        ));
+    }
+}
+
+/// Number of sentences in `text`
+///
+/// A sentence ends at `.`, `!`, or `?` followed by whitespace or the end of
+/// the text, so decimals and URLs don't split sentences. Abbreviations such as
+/// "e.g." are counted as ends; the limit leaves room for that.
+fn sentence_count(text: &str) -> usize {
+    let mut chars = text.trim().chars().peekable();
+    let mut count = 0;
+    let mut in_sentence = false;
+    while let Some(c) = chars.next() {
+        if matches!(c, '.' | '!' | '?') {
+            if in_sentence && chars.peek().is_none_or(|next| next.is_whitespace()) {
+                count += 1;
+                in_sentence = false;
+            }
+        } else if !c.is_whitespace() {
+            in_sentence = true;
+        }
+    }
+    // Trailing text without final punctuation is a sentence too
+    count + usize::from(in_sentence)
+}
+
+/// Stock phrases the best-practices prompt advises against, with what to do instead
+///
+/// Phrases are lowercase with hyphens written as spaces, matching the
+/// normalization in [`filler_phrases`].
+const FILLER_PHRASES: &[(&str, &str)] = &[
+    (
+        "references available upon request",
+        "remove it; employers ask for references when they need them",
+    ),
+    (
+        "references available on request",
+        "remove it; employers ask for references when they need them",
+    ),
+    (
+        "references upon request",
+        "remove it; employers ask for references when they need them",
+    ),
+    (
+        "hard worker",
+        "show the effort through a concrete achievement instead",
+    ),
+    (
+        "team player",
+        "describe a result you reached with others instead",
+    ),
+    (
+        "detail oriented",
+        "give an example where your care prevented or caught a problem",
+    ),
+];
+
+/// Warn about filler phrases in the summary and in highlights
+///
+/// These are never errors: the resume still renders, but the space is better
+/// spent on specific accomplishments.
+fn check_filler_phrases(resume: &Resume, report: &mut CheckReport) {
+    let mut texts = Vec::new();
+    if let Some(summary) = &resume.basics.summary {
+        texts.push(("basics.summary".to_string(), summary.clone()));
+    }
+    let highlights = resume
+        .work
+        .iter()
+        .enumerate()
+        .map(|(i, w)| (format!("work[{}]", i), &w.highlights))
+        .chain(
+            resume
+                .education
+                .iter()
+                .enumerate()
+                .map(|(i, e)| (format!("education[{}]", i), &e.highlights)),
+        )
+        .chain(
+            resume
+                .projects
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (format!("projects[{}]", i), &p.highlights)),
+        );
+    for (entry, list) in highlights {
+        for (j, highlight) in list.iter().enumerate() {
+            texts.push((
+                format!("{}.highlights[{}]", entry, j),
+                highlight.plain_text(),
+            ));
+        }
+    }
+
+    for (path, text) in texts {
+        for (phrase, advice) in filler_phrases(&text) {
+            report.warnings.push(ValidationError::new(
+                path.clone(),
+                format!("Contains the filler phrase '{}'; {}", phrase, advice),
+            ));
+        }
+    }
+}
+
+/// The entries of [`FILLER_PHRASES`] found in `text`, ignoring case, hyphens, and extra spaces
+fn filler_phrases(text: &str) -> impl Iterator<Item = &'static (&'static str, &'static str)> {
+    let normalized = text
+        .to_lowercase()
+        .replace('-', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    FILLER_PHRASES
+        .iter()
+        .filter(move |(phrase, _)| normalized.contains(phrase))
+}
+
+fn standard_section_is_empty(resume: &Resume, section: &str) -> bool {
+    match section {
+        "education" => resume.education.is_empty(),
+        "experience" => resume.work.is_empty(),
+        "projects" => resume.projects.is_empty(),
+        "certifications" => resume.certifications.is_empty(),
+        "awards" => resume.awards.is_empty(),
+        "publications" => resume.publications.is_empty(),
+        "skills" => resume.skills.is_empty(),
+        "languages" => resume.languages.is_empty(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_resume_warns() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Jane Smith", "email": "jane@example.com" },
+            "work": []
+        }))
+        .unwrap();
+
+        let report = check_resume(&resume);
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].message.contains("sparse"));
+    }
+
+    #[test]
+    fn test_fixture_has_no_findings() {
+        let fixture = include_str!("../../tests/fixtures/sample_resume.json");
+        let resume: Resume = serde_json::from_str(fixture).unwrap();
+
+        let report = check_resume(&resume);
+        assert!(report.errors.is_empty());
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_filler_phrases_warn_without_blocking() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": {
+                "name": "Jane Smith",
+                "email": "jane@example.com",
+                "summary": "I am a hard-working  Hard Worker. References available upon request."
+            },
+            "work": [{
+                "company": "A",
+                "position": "Engineer",
+                "highlights": ["Shipped the billing rewrite", "Known as a team player"]
+            }]
+        }))
+        .unwrap();
+
+        let report = check_resume(&resume);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+
+        let found: Vec<(&str, &str)> = report
+            .warnings
+            .iter()
+            .map(|w| (w.path.as_str(), w.message.as_str()))
+            .collect();
+        assert_eq!(found.len(), 3, "{:?}", found);
+        assert_eq!(found[0].0, "basics.summary");
+        assert!(found[0].1.contains("'references available upon request'"));
+        assert_eq!(found[1].0, "basics.summary");
+        assert!(found[1].1.contains("'hard worker'"));
+        assert_eq!(found[2].0, "work[0].highlights[1]");
+        assert!(found[2].1.contains("'team player'"));
+    }
+
+    #[test]
+    fn test_long_summary_warns() {
+        let with_summary = |summary: &str| -> Resume {
+            serde_json::from_value(serde_json::json!({
+                "basics": { "name": "Jane Smith", "email": "jane@example.com", "summary": summary },
+                "work": [{ "company": "A", "position": "Engineer" }]
+            }))
+            .unwrap()
+        };
+
+        let short = with_summary(
+            "Backend engineer with 8 years of experience scaling payment systems. \
+             Cut p99 latency by 3.5x at Acme!",
+        );
+        assert!(check_resume(&short).warnings.is_empty());
+
+        let long = with_summary(
+            "I am an engineer. I build systems. I lead teams. I mentor juniors. \
+             I write documentation. I love learning new things.",
+        );
+        let report = check_resume(&long);
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 1, "{:?}", report.warnings);
+        assert_eq!(report.warnings[0].path, "basics.summary");
+        assert!(
+            report.warnings[0].message.contains("6 sentences"),
+            "{}",
+            report.warnings[0].message
+        );
+    }
+
+    #[test]
+    fn test_control_characters_stripped_with_path() {
+        let mut payload = serde_json::json!({
+            "basics": { "name": "Jane\u{0} Smith", "email": "jane@example.com" },
+            "work": [{ "company": "A", "position": "Engineer", "highlights": ["Line one\nLine two", "Shipped\u{b}\u{b} it"] }]
+        });
+
+        let warnings = strip_control_characters(&mut payload);
+        assert_eq!(payload["basics"]["name"], "Jane Smith");
+        assert_eq!(payload["work"][0]["highlights"][0], "Line one\nLine two");
+        assert_eq!(payload["work"][0]["highlights"][1], "Shipped it");
+
+        let paths: Vec<&str> = warnings.iter().map(|w| w.path.as_str()).collect();
+        assert_eq!(paths, vec!["basics.name", "work[0].highlights[1]"]);
+        assert!(
+            warnings[0].message.contains("U+0000"),
+            "{}",
+            warnings[0].message
+        );
+        assert!(
+            warnings[1].message.contains("2 control"),
+            "{}",
+            warnings[1].message
+        );
+    }
+
+    #[test]
+    fn test_custom_section_titles_checked() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Jane Smith", "email": "jane@example.com" },
+            "work": [],
+            "customSections": [
+                { "title": "Volunteering", "items": ["Food bank"] },
+                { "title": " ", "items": [] },
+                { "title": "volunteering", "items": [] }
+            ]
+        }))
+        .unwrap();
+
+        let report = check_resume(&resume);
+        let paths: Vec<&str> = report.errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["customSections[1].title", "customSections[2].title"]
+        );
+        assert!(report.errors[1].message.contains("Duplicate"));
+    }
+
+    #[test]
+    fn test_contradictory_work_dates_flagged() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Jane Smith", "email": "jane@example.com" },
+            "work": [
+                { "company": "A", "position": "Engineer", "startDate": "2020-01", "endDate": "2022-05", "current": true },
+                { "company": "B", "position": "Engineer", "startDate": "2018-01" },
+                { "company": "C", "position": "Engineer", "startDate": "2023-01", "current": true },
+                { "company": "D", "position": "Engineer", "startDate": "2023-01", "endDate": "Present", "current": true }
+            ]
+        }))
+        .unwrap();
+
+        let report = check_resume(&resume);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].path, "work[0]");
+        assert!(report.errors[0].message.contains("current"));
+
+        let warning_paths: Vec<&str> = report.warnings.iter().map(|w| w.path.as_str()).collect();
+        assert_eq!(warning_paths, vec!["work[1]"]);
+    }
+
+    #[test]
+    fn test_empty_ordered_section_warns() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Jane Smith", "email": "jane@example.com" },
+            "work": [{ "company": "A", "position": "Engineer" }],
+            "awards": [],
+            "customSections": [{ "title": "Talks", "items": [] }],
+            "sectionOrder": ["experience", "awards", "Talks", "hobbies"]
+        }))
+        .unwrap();
+
+        let report = check_resume(&resume);
+        assert!(report.errors.is_empty());
+        let paths: Vec<&str> = report.warnings.iter().map(|w| w.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["sectionOrder[1]", "sectionOrder[2]", "sectionOrder[3]"]
+        );
+        assert!(
+            report.warnings[0]
+                .message
+                .contains("Section 'awards' is listed in sectionOrder but has no entries"),
+            "{}",
+            report.warnings[0].message
+        );
+    }
+}
@@ -0,0 +1,139 @@
+//! Resume completeness metric
+//!
+//! A progress indicator rather than a quality judgement: each field or section
+//! that is populated earns its weight, and the total is a percentage. Items
+//! most resumes should have (summary, contact details, experience, education,
+//! skills) carry nearly all of the weight; optional sections add the rest.
+
+use serde::Serialize;
+
+use crate::documents::Resume;
+
+/// Weight of each checklist item; the weights add up to 100
+const RECOMMENDED: &[(&str, u8)] = &[
+    ("summary", 15),
+    ("phone", 5),
+    ("location", 5),
+    ("profiles", 5),
+    ("work", 20),
+    ("work highlights", 10),
+    ("education", 15),
+    ("skills", 15),
+];
+
+/// Optional sections and their weights
+const OPTIONAL: &[(&str, u8)] = &[
+    ("projects", 4),
+    ("certifications", 2),
+    ("languages", 2),
+    ("awards", 1),
+    ("publications", 1),
+];
+
+/// Result of the `resume_completeness` tool
+#[derive(Debug, Serialize)]
+pub struct CompletenessReport {
+    /// Share of the weighted checklist that is populated, from 0 to 100
+    pub percentage: u8,
+    /// Missing items most resumes should have, most valuable first
+    pub recommended: Vec<String>,
+    /// Optional sections that are empty
+    pub empty_sections: Vec<String>,
+}
+
+/// Measure how much of the resume is filled in
+pub fn resume_completeness(resume: &Resume) -> CompletenessReport {
+    let mut percentage = 0;
+    let mut recommended = Vec::new();
+    let mut empty_sections = Vec::new();
+
+    for &(item, weight) in RECOMMENDED {
+        if is_populated(resume, item) {
+            percentage += weight;
+        } else {
+            recommended.push((item, weight));
+        }
+    }
+    for &(item, weight) in OPTIONAL {
+        if is_populated(resume, item) {
+            percentage += weight;
+        } else {
+            empty_sections.push(item.to_string());
+        }
+    }
+
+    recommended.sort_by_key(|r| std::cmp::Reverse(r.1));
+
+    CompletenessReport {
+        percentage,
+        recommended: recommended
+            .into_iter()
+            .map(|(item, _)| item.to_string())
+            .collect(),
+        empty_sections,
+    }
+}
+
+fn is_populated(resume: &Resume, item: &str) -> bool {
+    let basics = &resume.basics;
+    match item {
+        "summary" => has_text(&basics.summary),
+        "phone" => has_text(&basics.phone),
+        "location" => has_text(&basics.location),
+        "profiles" => !basics.profiles.is_empty(),
+        "work" => !resume.work.is_empty(),
+        "work highlights" => resume.work.iter().any(|w| !w.highlights.is_empty()),
+        "education" => !resume.education.is_empty(),
+        "skills" => !resume.skills.is_empty(),
+        "projects" => !resume.projects.is_empty(),
+        "certifications" => !resume.certifications.is_empty(),
+        "languages" => !resume.languages.is_empty(),
+        "awards" => !resume.awards.is_empty(),
+        "publications" => !resume.publications.is_empty(),
+        _ => false,
+    }
+}
+
+fn has_text(value: &Option<String>) -> bool {
+    value.as_deref().is_some_and(|v| !v.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weights_add_up_to_100() {
+        let total: u32 = RECOMMENDED
+            .iter()
+            .chain(OPTIONAL)
+            .map(|&(_, weight)| u32::from(weight))
+            .sum();
+        assert_eq!(total, 100);
+    }
+
+    #[test]
+    fn test_minimal_resume_scores_low_and_fixture_high() {
+        let minimal: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Jane Smith", "email": "jane@example.com" },
+            "work": []
+        }))
+        .unwrap();
+        let full: Resume =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+
+        let minimal_report = resume_completeness(&minimal);
+        assert!(minimal_report.percentage < 20, "{:?}", minimal_report);
+        assert_eq!(minimal_report.recommended[0], "work");
+        assert!(minimal_report.recommended.contains(&"summary".to_string()));
+        assert!(
+            minimal_report
+                .empty_sections
+                .contains(&"projects".to_string())
+        );
+
+        let full_report = resume_completeness(&full);
+        assert!(full_report.percentage >= 80, "{:?}", full_report);
+        assert!(full_report.recommended.is_empty(), "{:?}", full_report);
+    }
+}
@@ -0,0 +1,147 @@
+//! Argument completion (`completion/complete`)
+//!
+//! Clients call this while the user fills in a prompt argument, to offer
+//! suggestions. Only the arguments a prompt declares are completed: the
+//! resume best practices prompt's `role` gets common job titles, and a
+//! `document_type` gets the implemented document types. Anything else,
+//! including resource references, gets no suggestions.
+
+use rmcp::model::{ArgumentInfo, CompletionInfo, Reference};
+
+use crate::documents::DocumentType;
+use crate::mcp::prompts::{self, DOCUMENT_TYPE_ARGUMENT, ROLE_ARGUMENT};
+
+/// Roles suggested for the `role` argument
+const COMMON_ROLES: &[&str] = &[
+    "Software Engineer",
+    "Senior Software Engineer",
+    "Frontend Engineer",
+    "Backend Engineer",
+    "Data Scientist",
+    "Data Analyst",
+    "Machine Learning Engineer",
+    "DevOps Engineer",
+    "Site Reliability Engineer",
+    "Engineering Manager",
+    "Product Manager",
+    "Product Designer",
+    "UX Researcher",
+    "Project Manager",
+    "Business Analyst",
+    "Marketing Manager",
+    "Sales Representative",
+    "Account Manager",
+    "Customer Success Manager",
+    "Financial Analyst",
+    "Accountant",
+    "Registered Nurse",
+    "Teacher",
+    "Research Scientist",
+];
+
+/// Suggestions for an argument of the referenced prompt
+///
+/// A suggestion matches when it, or one of its words, starts with the typed
+/// value, ignoring case; an empty value matches every suggestion.
+pub fn complete(reference: &Reference, argument: &ArgumentInfo) -> CompletionInfo {
+    let declared = match reference {
+        Reference::Prompt(prompt) => prompts::list_prompts()
+            .into_iter()
+            .find(|p| p.name == prompt.name)
+            .and_then(|p| p.arguments)
+            .is_some_and(|args| args.iter().any(|a| a.name == argument.name)),
+        Reference::Resource(_) => false,
+    };
+
+    let candidates: Vec<&str> = match argument.name.as_str() {
+        _ if !declared => Vec::new(),
+        ROLE_ARGUMENT => COMMON_ROLES.to_vec(),
+        DOCUMENT_TYPE_ARGUMENT => DocumentType::ALL.iter().map(|t| t.name).collect(),
+        _ => Vec::new(),
+    };
+
+    let typed = argument.value.trim().to_lowercase();
+    let values: Vec<String> = candidates
+        .into_iter()
+        .filter(|candidate| {
+            let candidate = candidate.to_lowercase();
+            candidate.starts_with(&typed)
+                || candidate
+                    .split(|c: char| c.is_whitespace() || c == '_')
+                    .any(|word| word.starts_with(&typed))
+        })
+        .map(str::to_string)
+        .collect();
+
+    CompletionInfo {
+        total: Some(values.len() as u32),
+        has_more: Some(false),
+        values,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::PromptReference;
+
+    fn prompt(name: &str) -> Reference {
+        Reference::Prompt(PromptReference {
+            name: name.to_string(),
+            title: None,
+        })
+    }
+
+    fn argument(name: &str, value: &str) -> ArgumentInfo {
+        ArgumentInfo {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_role_completions() {
+        let reference = prompt(prompts::RESUME_BEST_PRACTICES_PROMPT);
+
+        let all = complete(&reference, &argument(ROLE_ARGUMENT, ""));
+        assert_eq!(all.values.len(), COMMON_ROLES.len());
+        assert_eq!(all.has_more, Some(false));
+
+        let engineers = complete(&reference, &argument(ROLE_ARGUMENT, "eng"));
+        assert!(engineers.values.contains(&"Software Engineer".to_string()));
+        assert!(
+            engineers
+                .values
+                .contains(&"Engineering Manager".to_string())
+        );
+        assert!(!engineers.values.contains(&"Data Scientist".to_string()));
+        assert_eq!(engineers.total, Some(engineers.values.len() as u32));
+    }
+
+    #[test]
+    fn test_document_type_completions() {
+        let reference = prompt(prompts::TOOL_USAGE_GUIDE_PROMPT);
+
+        let all = complete(&reference, &argument(DOCUMENT_TYPE_ARGUMENT, ""));
+        assert_eq!(all.values, vec!["resume", "cover_letter"]);
+
+        let letter = complete(&reference, &argument(DOCUMENT_TYPE_ARGUMENT, "let"));
+        assert_eq!(letter.values, vec!["cover_letter"]);
+    }
+
+    #[test]
+    fn test_undeclared_arguments_get_no_completions() {
+        // The cover letter prompt declares no role argument
+        let reference = prompt(prompts::COVER_LETTER_BEST_PRACTICES_PROMPT);
+        assert!(
+            complete(&reference, &argument(ROLE_ARGUMENT, ""))
+                .values
+                .is_empty()
+        );
+        assert!(
+            complete(&prompt("unknown-prompt"), &argument(ROLE_ARGUMENT, ""))
+                .values
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Parsing of document payloads supplied as YAML or TOML text
+//!
+//! Tools normally take the document as a JSON object. Users who keep their
+//! data in YAML or TOML can instead pass `content` as a string together with
+//! `format`; it is parsed into a JSON `Value` here and then validated exactly
+//! like an inline payload.
+//!
+//! Duplicate keys are rejected in every format. The TOML parser does this
+//! itself; JSON and YAML text are built through a strict visitor because
+//! `serde_json` and `serde_yaml` would otherwise keep the last value and
+//! silently drop the others. Other JSON text a tool receives (a resume sent
+//! as a JSON-encoded string, a `source_url` body) goes through the same
+//! strict JSON parser.
+
+use serde::Deserialize;
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Text format of a `content` payload
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputFormat {
+    #[default]
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl InputFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InputFormat::Json => "JSON",
+            InputFormat::Yaml => "YAML",
+            InputFormat::Toml => "TOML",
+        }
+    }
+}
+
+/// Parse `content` in the given format into a JSON value
+pub fn parse_content(format: InputFormat, content: &str) -> Result<Value, String> {
+    let parsed = match format {
+        InputFormat::Json => parse_json_strict(content).map_err(|e| e.to_string()),
+        InputFormat::Yaml => parse_yaml_strict(content).map_err(|e| e.to_string()),
+        InputFormat::Toml => toml::from_str::<toml::Value>(content)
+            .map(toml_to_json)
+            .map_err(|e| e.to_string()),
+    };
+
+    parsed.map_err(|e| format!("Invalid {} content: {}", format.as_str(), e.trim()))
+}
+
+/// Parse JSON text, rejecting objects that repeat a key
+pub fn parse_json_strict(content: &str) -> Result<Value, serde_json::Error> {
+    let mut deserializer = serde_json::Deserializer::from_str(content);
+    let value = StrictValue {
+        path: String::new(),
+    }
+    .deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Parse YAML text, rejecting mappings that repeat a key
+fn parse_yaml_strict(content: &str) -> Result<Value, serde_yaml::Error> {
+    StrictValue {
+        path: String::new(),
+    }
+    .deserialize(serde_yaml::Deserializer::from_str(content))
+}
+
+/// Builds a `Value` like `serde_json` does, but fails on duplicate keys and
+/// reports where the duplicate was found (e.g. `basics.email`)
+struct StrictValue {
+    path: String,
+}
+
+impl StrictValue {
+    fn child(&self, segment: &str) -> Self {
+        let path = if self.path.is_empty() {
+            segment.to_string()
+        } else {
+            format!("{}.{}", self.path, segment)
+        };
+        Self { path }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for StrictValue {
+    type Value = Value;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for StrictValue {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any JSON-compatible value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element_seed(StrictValue {
+            path: format!("{}[{}]", self.path, items.len()),
+        })? {
+            items.push(item);
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Value, A::Error> {
+        let mut map = Map::new();
+        while let Some(key) = access.next_key::<String>()? {
+            let child = self.child(&key);
+            if map.contains_key(&key) {
+                return Err(de::Error::custom(format!(
+                    "duplicate key `{}` at {}",
+                    key, child.path
+                )));
+            }
+            let value = access.next_value_seed(child)?;
+            map.insert(key, value);
+        }
+        Ok(Value::Object(map))
+    }
+}
+
+/// Convert a TOML value to JSON, rendering dates as the strings they were written as
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_yaml_and_toml() {
+        let yaml = "basics:\n  name: Jane Smith\n  email: jane@example.com\nwork: []\n";
+        let value = parse_content(InputFormat::Yaml, yaml).unwrap();
+        assert_eq!(value["basics"]["name"], "Jane Smith");
+
+        let toml = "[basics]\nname = \"Jane Smith\"\nemail = \"jane@example.com\"\n\n[[work]]\ncompany = \"Tech Corp\"\nposition = \"Engineer\"\nstartDate = 2020-01-15\n";
+        let value = parse_content(InputFormat::Toml, toml).unwrap();
+        assert_eq!(value["work"][0]["startDate"], "2020-01-15");
+    }
+
+    #[test]
+    fn test_duplicate_keys_are_rejected() {
+        let json =
+            r#"{"basics": {"name": "Jane", "email": "a@example.com", "email": "b@example.com"}}"#;
+        let err = parse_content(InputFormat::Json, json).unwrap_err();
+        assert!(
+            err.contains("duplicate key `email` at basics.email"),
+            "{}",
+            err
+        );
+
+        let json = r#"{"work": [{"company": "A"}, {"company": "B", "company": "C"}]}"#;
+        let err = parse_content(InputFormat::Json, json).unwrap_err();
+        assert!(err.contains("at work[1].company"), "{}", err);
+
+        let yaml = "basics:\n  email: a@example.com\n  email: b@example.com\n";
+        let err = parse_content(InputFormat::Yaml, yaml).unwrap_err();
+        assert!(
+            err.contains("duplicate key `email` at basics.email"),
+            "{}",
+            err
+        );
+
+        let toml = "[basics]\nemail = \"a@example.com\"\nemail = \"b@example.com\"\n";
+        assert!(parse_content(InputFormat::Toml, toml).is_err());
+
+        let value = parse_content(InputFormat::Json, r#"{"a": [1, 2.5, null, true]}"#).unwrap();
+        assert_eq!(value, serde_json::json!({ "a": [1, 2.5, null, true] }));
+    }
+
+    #[test]
+    fn test_malformed_content_is_reported() {
+        let err = parse_content(InputFormat::Yaml, "basics: [unclosed").unwrap_err();
+        assert!(err.starts_with("Invalid YAML content"), "{}", err);
+
+        let err = parse_content(InputFormat::Toml, "basics = ").unwrap_err();
+        assert!(err.starts_with("Invalid TOML content"), "{}", err);
+    }
+}
@@ -0,0 +1,396 @@
+//! LaTeX source export of resumes
+//!
+//! Academic users often maintain their CV in LaTeX. This is the LaTeX
+//! counterpart of the Typst transform: the resume is rendered into the
+//! moderncv-based template in `templates/resume.tex`, with sections in the
+//! same order and under the same titles as the PDF. Every value is escaped,
+//! so the output compiles regardless of the characters in the resume.
+
+use crate::documents::Resume;
+use crate::documents::resume::{Highlight, SECTION_NAMES};
+use crate::mcp::text_export::default_title;
+
+/// The raw LaTeX template content for resumes
+const RESUME_LATEX_TEMPLATE: &str = include_str!("../../templates/resume.tex");
+
+/// Placeholder replaced with the personal data commands
+const PERSONAL_PLACEHOLDER: &str = "%% docgen:personal";
+
+/// Placeholder replaced with the rendered sections
+const SECTIONS_PLACEHOLDER: &str = "%% docgen:sections";
+
+/// Render a resume as a standalone LaTeX document
+pub fn resume_to_latex(resume: &Resume) -> String {
+    let mut sections = Vec::new();
+
+    if let Some(summary) = resume
+        .basics
+        .summary
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+    {
+        sections.push(format!(
+            "\\section{{Summary}}\n\\cvitem{{}}{{{}}}",
+            escape(summary.trim())
+        ));
+    }
+
+    let order: Vec<&str> = match &resume.section_order {
+        Some(order) => order.iter().map(String::as_str).collect(),
+        None => SECTION_NAMES.to_vec(),
+    };
+
+    for section in &order {
+        if let Some(block) = section_block(resume, section) {
+            sections.push(block);
+        }
+    }
+
+    // Custom sections not positioned via sectionOrder follow the standard ones
+    for custom in &resume.custom_sections {
+        if !order.contains(&custom.title.as_str())
+            && let Some(block) = section_block(resume, &custom.title)
+        {
+            sections.push(block);
+        }
+    }
+
+    RESUME_LATEX_TEMPLATE
+        .replace(PERSONAL_PLACEHOLDER, &personal(resume))
+        .replace(SECTIONS_PLACEHOLDER, &sections.join("\n\n"))
+}
+
+/// moderncv personal data commands: name, contact details, and links
+fn personal(resume: &Resume) -> String {
+    let basics = &resume.basics;
+
+    // moderncv takes the name as first and last parts
+    let name = basics.name.trim();
+    let (first, last) = name.rsplit_once(' ').unwrap_or((name, ""));
+    let mut lines = vec![format!(
+        "\\name{{{}}}{{{}}}",
+        escape(first.trim()),
+        escape(last)
+    )];
+
+    if let Some(location) = &basics.location {
+        lines.push(format!("\\address{{{}}}{{}}{{}}", escape(location)));
+    }
+    if let Some(phone) = &basics.phone {
+        lines.push(format!("\\phone[mobile]{{{}}}", escape(phone)));
+    }
+    lines.push(format!("\\email{{{}}}", escape(&basics.email)));
+
+    if !basics.profiles.is_empty() {
+        let links: Vec<String> = basics
+            .profiles
+            .iter()
+            .map(|profile| {
+                let network = profile.canonical_network().unwrap_or(&profile.network);
+                format!(
+                    "\\href{{{}}}{{{}}}",
+                    escape_url(&profile.url),
+                    escape(network)
+                )
+            })
+            .collect();
+        lines.push(format!("\\extrainfo{{{}}}", links.join(" \\textbullet{} ")));
+    }
+
+    lines.join("\n")
+}
+
+/// One section with its heading, or `None` if it has no entries
+fn section_block(resume: &Resume, section: &str) -> Option<String> {
+    let entries: Vec<String> = match section {
+        "education" => resume
+            .education
+            .iter()
+            .map(|edu| {
+                let degree = join_present(
+                    [edu.degree.as_deref(), edu.field_of_study.as_deref()],
+                    " in ",
+                );
+                let gpa = edu.gpa.as_ref().map(|gpa| format!("GPA: {}", gpa));
+                cventry(
+                    &date_range(edu.start_date.as_deref(), edu.end_date.as_deref()),
+                    &degree,
+                    &edu.institution,
+                    edu.location.as_deref().unwrap_or_default(),
+                    gpa.as_deref().unwrap_or_default(),
+                    &itemize(&edu.highlights),
+                )
+            })
+            .collect(),
+        "experience" => resume
+            .work
+            .iter()
+            .map(|job| {
+                let present = (job.current == Some(true)).then_some("Present");
+                let end = job.end_date.as_deref().or(present);
+                cventry(
+                    &date_range(job.start_date.as_deref(), end),
+                    &job.position,
+                    &job.company,
+                    job.location.as_deref().unwrap_or_default(),
+                    "",
+                    &itemize(&job.highlights),
+                )
+            })
+            .collect(),
+        "projects" => resume
+            .projects
+            .iter()
+            .map(|project| {
+                let mut description = Vec::new();
+                if let Some(text) = &project.description {
+                    description.push(escape(text));
+                }
+                if let Some(url) = &project.url {
+                    description.push(format!("\\url{{{}}}", escape_url(url)));
+                }
+                let highlights = itemize(&project.highlights);
+                if !highlights.is_empty() {
+                    description.push(highlights);
+                }
+                cventry(
+                    &date_range(project.start_date.as_deref(), project.end_date.as_deref()),
+                    &project.name,
+                    &project.keywords.join(", "),
+                    "",
+                    "",
+                    &description.join("\n"),
+                )
+            })
+            .collect(),
+        "certifications" => resume
+            .certifications
+            .iter()
+            .map(|cert| {
+                cvitem(
+                    cert.date.as_deref().unwrap_or_default(),
+                    &join_present([Some(cert.name.as_str()), cert.issuer.as_deref()], ", "),
+                )
+            })
+            .collect(),
+        "awards" => resume
+            .awards
+            .iter()
+            .map(|award| {
+                cvitem(
+                    award.date.as_deref().unwrap_or_default(),
+                    &join_present(
+                        [
+                            Some(award.title.as_str()),
+                            award.awarder.as_deref(),
+                            award.summary.as_deref(),
+                        ],
+                        ", ",
+                    ),
+                )
+            })
+            .collect(),
+        "publications" => resume
+            .publications
+            .iter()
+            .map(|publication| {
+                let authors = publication.authors.join(", ");
+                cvitem(
+                    publication.date.as_deref().unwrap_or_default(),
+                    &join_present(
+                        [
+                            Some(publication.title.as_str()),
+                            Some(authors.as_str()),
+                            publication.venue.as_deref(),
+                        ],
+                        ". ",
+                    ),
+                )
+            })
+            .collect(),
+        "skills" => resume
+            .skills
+            .iter()
+            .map(|skill| cvitem(&skill.name, &skill.keywords.join(", ")))
+            .collect(),
+        "languages" => resume
+            .languages
+            .iter()
+            .map(|language| {
+                cvitem(
+                    &language.language,
+                    language.fluency.as_deref().unwrap_or_default(),
+                )
+            })
+            .collect(),
+        custom => resume
+            .custom_sections
+            .iter()
+            .filter(|c| c.title == custom)
+            .flat_map(|c| {
+                c.items
+                    .iter()
+                    .map(|item| format!("\\cvlistitem{{{}}}", escape(item)))
+            })
+            .collect(),
+    };
+
+    if entries.is_empty() {
+        return None;
+    }
+
+    let title = resume
+        .section_titles
+        .as_ref()
+        .and_then(|titles| titles.get(section))
+        .map(String::as_str)
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or_else(|| match default_title(section) {
+            "" => section,
+            title => title,
+        });
+
+    Some(format!(
+        "\\section{{{}}}\n{}",
+        escape(title),
+        entries.join("\n")
+    ))
+}
+
+/// `\cventry{dates}{title}{employer}{city}{grade}{description}`
+///
+/// `description` is already LaTeX; every other argument is escaped here.
+fn cventry(
+    dates: &str,
+    title: &str,
+    employer: &str,
+    city: &str,
+    grade: &str,
+    description: &str,
+) -> String {
+    format!(
+        "\\cventry{{{}}}{{{}}}{{{}}}{{{}}}{{{}}}{{{}}}",
+        escape(dates),
+        escape(title),
+        escape(employer),
+        escape(city),
+        escape(grade),
+        description
+    )
+}
+
+/// `\cvitem{label}{text}`
+fn cvitem(label: &str, text: &str) -> String {
+    format!("\\cvitem{{{}}}{{{}}}", escape(label), escape(text))
+}
+
+/// Highlights as an `itemize` list, with structured lead-ins in bold
+fn itemize(highlights: &[Highlight]) -> String {
+    if highlights.is_empty() {
+        return String::new();
+    }
+
+    let items: Vec<String> = highlights
+        .iter()
+        .map(|highlight| match highlight {
+            Highlight::Plain(text) => format!("\\item {}", escape(text.trim())),
+            Highlight::Structured { lead, text } => format!(
+                "\\item \\textbf{{{}}} {}",
+                escape(lead.trim()),
+                escape(text.trim())
+            ),
+        })
+        .collect();
+    format!("\\begin{{itemize}}\n{}\n\\end{{itemize}}", items.join("\n"))
+}
+
+/// "start -- end" for whichever ends are present
+fn date_range(start: Option<&str>, end: Option<&str>) -> String {
+    join_present([start, end], " -- ")
+}
+
+/// Join the present, non-blank parts with a separator
+fn join_present<const N: usize>(parts: [Option<&str>; N], separator: &str) -> String {
+    parts
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Escape LaTeX special characters in text
+///
+/// Dashes are left alone so `--` in date ranges still renders as an en dash.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '{' | '}' | '&' | '%' | '$' | '#' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '<' => escaped.push_str("\\textless{}"),
+            '>' => escaped.push_str("\\textgreater{}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape a URL for `\url` and `\href`, where only a few characters are special
+fn escape_url(url: &str) -> String {
+    url.replace('\\', "/")
+        .replace('%', "\\%")
+        .replace('#', "\\#")
+        .replace('{', "%7B")
+        .replace('}', "%7D")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_resume() -> Resume {
+        serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap()
+    }
+
+    #[test]
+    fn test_latex_export_is_a_moderncv_document() {
+        let latex = resume_to_latex(&sample_resume());
+
+        assert!(latex.contains("\\documentclass[11pt,letterpaper,sans]{moderncv}"));
+        assert!(latex.contains("\\name{Jane}{Smith}"));
+        assert!(latex.contains("\\email{jane.smith@example.com}"));
+        assert!(latex.contains("\\section{Experience}"));
+        assert!(latex.contains("{Senior Software Engineer}{Tech Innovations Inc.}"));
+        assert!(latex.contains("\\item Mentored team of 4 junior developers"));
+        assert!(!latex.contains("docgen:"), "Placeholders must be replaced");
+
+        let position = |heading: &str| latex.find(&format!("\\section{{{}}}", heading)).unwrap();
+        assert!(position("Education") < position("Experience"));
+        assert!(position("Experience") < position("Technical Skills"));
+    }
+
+    #[test]
+    fn test_latex_special_characters_are_escaped() {
+        assert_eq!(escape("R&D 100% #1 $5_k"), "R\\&D 100\\% \\#1 \\$5\\_k");
+        assert_eq!(
+            escape("{a}\\b~c^"),
+            "\\{a\\}\\textbackslash{}b\\textasciitilde{}c\\textasciicircum{}"
+        );
+        assert_eq!(
+            escape_url("https://example.com/a%20b#top"),
+            "https://example.com/a\\%20b\\#top"
+        );
+
+        let mut resume = sample_resume();
+        resume.basics.name = "Ana María O'Neil".to_string();
+        let latex = resume_to_latex(&resume);
+        assert!(latex.contains("\\name{Ana María}{O'Neil}"));
+    }
+}
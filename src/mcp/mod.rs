@@ -1,4 +1,14 @@
 // MCP handler modules
+pub mod ats;
+pub mod checks;
+pub mod completeness;
+pub mod completion;
+pub mod input_format;
+pub mod latex_export;
 pub mod prompts;
+pub mod protocol;
 pub mod resources;
+pub mod schema_validation;
+pub mod text_export;
 pub mod tools;
+pub mod typescript;
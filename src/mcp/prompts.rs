@@ -3,9 +3,14 @@
 //! This module provides MCP prompts that help LLMs create effective document content.
 //! The prompts include best practices, writing guidelines, and schema references.
 
-use crate::documents::{CoverLetter, Resume};
+use crate::documents::{CoverLetter, DocumentType, Resume};
 use crate::mcp::resources::{COVER_LETTER_SCHEMA_URI, RESUME_SCHEMA_URI};
-use rmcp::model::{GetPromptResult, Prompt, PromptMessage, PromptMessageRole};
+use crate::mcp::tools;
+use rmcp::model::{
+    GetPromptResult, JsonObject, Meta, Prompt, PromptArgument, PromptMessage, PromptMessageContent,
+    PromptMessageRole, Tool,
+};
+use serde_json::Value;
 
 /// Prompt name for resume best practices
 pub const RESUME_BEST_PRACTICES_PROMPT: &str = "resume-best-practices";
@@ -22,9 +27,24 @@ const RESUME_BEST_PRACTICES_TEMPLATE: &str = include_str!("../../prompts/resume-
 /// Raw markdown template for cover letter best practices (embedded at compile time)
 const COVER_LETTER_BEST_PRACTICES_TEMPLATE: &str = include_str!("../../prompts/cover-letter-best-practices.md");
 
+/// Prompt name for the tool usage guide
+pub const TOOL_USAGE_GUIDE_PROMPT: &str = "tool-usage-guide";
+
 /// Raw markdown for document type guide (embedded at compile time)
 const DOCUMENT_TYPE_GUIDE_TEMPLATE: &str = include_str!("../../prompts/document-type-guide.md");
 
+/// Raw markdown template for the tool usage guide (embedded at compile time)
+const TOOL_USAGE_GUIDE_TEMPLATE: &str = include_str!("../../prompts/tool-usage-guide.md");
+
+/// Optional argument of the resume best practices prompt naming the target role
+pub const ROLE_ARGUMENT: &str = "role";
+
+/// Optional argument of the tool usage guide limiting it to one document type
+pub const DOCUMENT_TYPE_ARGUMENT: &str = "document_type";
+
+/// Rough characters-per-token ratio used for size estimates (English prose and JSON)
+const CHARS_PER_TOKEN: usize = 4;
+
 /// Returns a list of all available prompts
 pub fn list_prompts() -> Vec<Prompt> {
     vec![
@@ -36,9 +56,18 @@ pub fn list_prompts() -> Vec<Prompt> {
                  Includes writing tips, formatting guidance, and the schema reference."
                     .to_string(),
             ),
-            arguments: None,
+            arguments: Some(vec![PromptArgument {
+                name: ROLE_ARGUMENT.to_string(),
+                title: Some("Target role".to_string()),
+                description: Some(
+                    "Optional. The role the resume targets (e.g. 'Software Engineer'); \
+                     the guidance then asks for content tailored to it."
+                        .to_string(),
+                ),
+                required: Some(false),
+            }]),
             icons: None,
-            meta: None,
+            meta: prompt_size_meta(RESUME_BEST_PRACTICES_PROMPT),
         },
         Prompt {
             name: COVER_LETTER_BEST_PRACTICES_PROMPT.to_string(),
@@ -50,7 +79,7 @@ pub fn list_prompts() -> Vec<Prompt> {
             ),
             arguments: None,
             icons: None,
-            meta: None,
+            meta: prompt_size_meta(COVER_LETTER_BEST_PRACTICES_PROMPT),
         },
         Prompt {
             name: DOCUMENT_TYPE_GUIDE_PROMPT.to_string(),
@@ -62,7 +91,28 @@ pub fn list_prompts() -> Vec<Prompt> {
             ),
             arguments: None,
             icons: None,
-            meta: None,
+            meta: prompt_size_meta(DOCUMENT_TYPE_GUIDE_PROMPT),
+        },
+        Prompt {
+            name: TOOL_USAGE_GUIDE_PROMPT.to_string(),
+            title: Some("Tool Usage Guide".to_string()),
+            description: Some(
+                "How to call each tool: exact argument shapes, the resume and cover letter \
+                 wrappers, and the validate-then-generate workflow."
+                    .to_string(),
+            ),
+            arguments: Some(vec![PromptArgument {
+                name: DOCUMENT_TYPE_ARGUMENT.to_string(),
+                title: Some("Document type".to_string()),
+                description: Some(
+                    "Optional. 'resume' or 'cover_letter' to leave out the tools of \
+                     the other document type."
+                        .to_string(),
+                ),
+                required: Some(false),
+            }]),
+            icons: None,
+            meta: prompt_size_meta(TOOL_USAGE_GUIDE_PROMPT),
         },
     ]
 }
@@ -73,10 +123,81 @@ pub fn get_prompt(name: &str) -> Option<GetPromptResult> {
         RESUME_BEST_PRACTICES_PROMPT => Some(build_resume_best_practices_prompt()),
         COVER_LETTER_BEST_PRACTICES_PROMPT => Some(build_cover_letter_best_practices_prompt()),
         DOCUMENT_TYPE_GUIDE_PROMPT => Some(build_document_type_guide_prompt()),
+        TOOL_USAGE_GUIDE_PROMPT => Some(build_tool_usage_guide_prompt(None)),
         _ => None,
     }
 }
 
+/// Gets a prompt by name, applying the optional arguments it declares
+///
+/// Blank or unrecognized argument values are ignored, giving the same prompt
+/// as [`get_prompt`].
+pub fn get_prompt_with_arguments(
+    name: &str,
+    arguments: Option<&JsonObject>,
+) -> Option<GetPromptResult> {
+    let argument = |key: &str| {
+        arguments
+            .and_then(|args| args.get(key))
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+
+    match name {
+        RESUME_BEST_PRACTICES_PROMPT => {
+            let mut prompt = build_resume_best_practices_prompt();
+            if let Some(role) = argument(ROLE_ARGUMENT) {
+                prompt.messages.push(PromptMessage::new_text(
+                    PromptMessageRole::User,
+                    format!(
+                        "The resume targets a {} role. Lead the summary with what makes the \
+                         candidate a fit for it, prefer highlights and skills relevant to it, \
+                         and use the vocabulary of its job postings.",
+                        role
+                    ),
+                ));
+            }
+            Some(prompt)
+        }
+        TOOL_USAGE_GUIDE_PROMPT => {
+            let document_type = argument(DOCUMENT_TYPE_ARGUMENT).and_then(|value| {
+                let value = value.replace('-', "_");
+                DocumentType::ALL.iter().find(|t| t.name == value)
+            });
+            Some(build_tool_usage_guide_prompt(document_type))
+        }
+        _ => get_prompt(name),
+    }
+}
+
+/// Number of characters of text content in a built prompt
+fn prompt_text_len(prompt: &GetPromptResult) -> usize {
+    prompt
+        .messages
+        .iter()
+        .map(|message| match &message.content {
+            PromptMessageContent::Text { text } => text.chars().count(),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Size metadata for a prompt so clients can budget context before fetching it
+///
+/// Reported under `_meta` as `characters` and `estimatedTokens`.
+fn prompt_size_meta(name: &str) -> Option<Meta> {
+    let characters = prompt_text_len(&get_prompt(name)?);
+
+    let mut meta = Meta::new();
+    meta.0.insert("characters".to_string(), characters.into());
+    meta.0.insert(
+        "estimatedTokens".to_string(),
+        characters.div_ceil(CHARS_PER_TOKEN).into(),
+    );
+    Some(meta)
+}
+
 /// Builds the resume best practices prompt with guidelines and schema reference
 fn build_resume_best_practices_prompt() -> GetPromptResult {
     // Generate the schema for reference
@@ -128,6 +249,77 @@ fn build_document_type_guide_prompt() -> GetPromptResult {
     }
 }
 
+/// Builds the tool usage guide from the live tool registry
+///
+/// With a `document_type`, tools named after another document type are left out.
+fn build_tool_usage_guide_prompt(document_type: Option<&DocumentType>) -> GetPromptResult {
+    let excluded: Vec<&str> = match document_type {
+        Some(selected) => DocumentType::ALL
+            .iter()
+            .filter(|t| t.name != selected.name)
+            .map(|t| t.name)
+            .collect(),
+        None => Vec::new(),
+    };
+    let reference = tools::list_tools()
+        .iter()
+        .filter(|tool| !excluded.iter().any(|name| tool.name.contains(name)))
+        .map(tool_reference)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    GetPromptResult {
+        description: Some(
+            "How to call each tool, with argument shapes and the recommended workflow".to_string(),
+        ),
+        messages: vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            TOOL_USAGE_GUIDE_TEMPLATE.replace("{{TOOL_REFERENCE}}", reference.trim_end()),
+        )],
+    }
+}
+
+/// Markdown section describing one tool and its top-level arguments
+fn tool_reference(tool: &Tool) -> String {
+    let mut section = format!("### `{}`\n\n", tool.name);
+    if let Some(description) = &tool.description {
+        section.push_str(&format!("{}\n\n", description));
+    }
+
+    let required: Vec<&str> = tool
+        .input_schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    let properties = tool
+        .input_schema
+        .get("properties")
+        .and_then(Value::as_object);
+    match properties {
+        Some(properties) if !properties.is_empty() => {
+            section.push_str("Arguments:\n");
+            for (name, schema) in properties {
+                let kind = schema.get("type").and_then(Value::as_str).unwrap_or("any");
+                let presence = if required.contains(&name.as_str()) {
+                    "**required**"
+                } else {
+                    "optional"
+                };
+                section.push_str(&format!("- `{}` ({}, {})", name, kind, presence));
+                if let Some(description) = schema.get("description").and_then(Value::as_str) {
+                    section.push_str(&format!(": {}", description));
+                }
+                section.push('\n');
+            }
+        }
+        _ => section.push_str("Arguments: none\n"),
+    }
+
+    section
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,13 +327,74 @@ mod tests {
     #[test]
     fn test_list_prompts() {
         let prompts = list_prompts();
-        assert_eq!(prompts.len(), 3);
+        assert_eq!(prompts.len(), 4);
         assert_eq!(prompts[0].name, RESUME_BEST_PRACTICES_PROMPT);
         assert_eq!(prompts[1].name, COVER_LETTER_BEST_PRACTICES_PROMPT);
         assert_eq!(prompts[2].name, DOCUMENT_TYPE_GUIDE_PROMPT);
-        assert!(prompts[0].description.is_some());
-        assert!(prompts[1].description.is_some());
-        assert!(prompts[2].description.is_some());
+        assert_eq!(prompts[3].name, TOOL_USAGE_GUIDE_PROMPT);
+        assert!(prompts.iter().all(|p| p.description.is_some()));
+    }
+
+    #[test]
+    fn test_tool_usage_guide_covers_registry() {
+        let result = get_prompt(TOOL_USAGE_GUIDE_PROMPT).unwrap();
+
+        if let rmcp::model::PromptMessageContent::Text { text } = &result.messages[0].content {
+            assert!(text.contains("`validate_resume`"));
+            assert!(text.contains("`generate_resume`"));
+            assert!(text.contains("`cover_letter`"));
+            assert!(text.contains("- `resume` (object, **required**)"));
+            assert!(!text.contains("{{"), "Unreplaced placeholder found");
+
+            // Every registered tool gets a section
+            for tool in tools::list_tools() {
+                assert!(text.contains(&format!("### `{}`", tool.name)));
+            }
+        } else {
+            panic!("Expected text content");
+        }
+    }
+
+    #[test]
+    fn test_prompt_arguments_are_applied() {
+        let args = |key: &str, value: &str| {
+            let mut map = JsonObject::new();
+            map.insert(key.to_string(), value.into());
+            map
+        };
+        let text = |prompt: &GetPromptResult| -> String {
+            prompt
+                .messages
+                .iter()
+                .filter_map(|message| match &message.content {
+                    PromptMessageContent::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let tailored = get_prompt_with_arguments(
+            RESUME_BEST_PRACTICES_PROMPT,
+            Some(&args(ROLE_ARGUMENT, "Data Scientist")),
+        )
+        .unwrap();
+        assert_eq!(tailored.messages.len(), 2);
+        assert!(text(&tailored).contains("targets a Data Scientist role"));
+
+        let blank = get_prompt_with_arguments(
+            RESUME_BEST_PRACTICES_PROMPT,
+            Some(&args(ROLE_ARGUMENT, " ")),
+        )
+        .unwrap();
+        assert_eq!(blank.messages.len(), 1);
+
+        let guide = get_prompt_with_arguments(
+            TOOL_USAGE_GUIDE_PROMPT,
+            Some(&args(DOCUMENT_TYPE_ARGUMENT, "cover-letter")),
+        )
+        .unwrap();
+        assert!(text(&guide).contains("### `generate_cover_letter`"));
+        assert!(!text(&guide).contains("### `generate_resume`"));
     }
 
     #[test]
@@ -213,4 +466,21 @@ mod tests {
             panic!("Expected text content");
         }
     }
+
+    #[test]
+    fn test_list_prompts_reports_size() {
+        for prompt in list_prompts() {
+            let meta = prompt.meta.expect("Prompt should report its size");
+            let characters = meta.0["characters"].as_u64().unwrap() as usize;
+            let tokens = meta.0["estimatedTokens"].as_u64().unwrap() as usize;
+
+            let built = get_prompt(&prompt.name).unwrap();
+            let actual = prompt_text_len(&built);
+            assert_eq!(characters, actual);
+            assert!(actual > 0);
+
+            // Token estimates should stay within a plausible band of the text length
+            assert!(tokens >= actual / 6 && tokens <= actual / 2);
+        }
+    }
 }
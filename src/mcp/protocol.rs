@@ -0,0 +1,48 @@
+//! MCP protocol version negotiation
+//!
+//! Clients send the protocol version they speak in `initialize`. If we support
+//! it we answer with the same version; otherwise we answer with our preferred
+//! version and leave it to the client to decide whether it can continue.
+
+use rmcp::model::ProtocolVersion;
+
+/// Protocol versions this server can speak, newest first
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[ProtocolVersion] = &[
+    ProtocolVersion::V_2025_06_18,
+    ProtocolVersion::V_2025_03_26,
+    ProtocolVersion::V_2024_11_05,
+];
+
+/// Version offered when the client requests one we do not support
+pub const PREFERRED_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V_2025_03_26;
+
+/// Pick the protocol version to answer an `initialize` request with
+pub fn negotiate_protocol_version(requested: &ProtocolVersion) -> ProtocolVersion {
+    if SUPPORTED_PROTOCOL_VERSIONS.contains(requested) {
+        requested.clone()
+    } else {
+        PREFERRED_PROTOCOL_VERSION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_versions_are_echoed() {
+        for version in SUPPORTED_PROTOCOL_VERSIONS {
+            assert_eq!(negotiate_protocol_version(version), *version);
+        }
+    }
+
+    #[test]
+    fn test_unknown_version_gets_preferred() {
+        let requested: ProtocolVersion = serde_json::from_str("\"2099-01-01\"").unwrap();
+        assert_eq!(
+            negotiate_protocol_version(&requested),
+            PREFERRED_PROTOCOL_VERSION
+        );
+        assert!(SUPPORTED_PROTOCOL_VERSIONS.contains(&PREFERRED_PROTOCOL_VERSION));
+    }
+}
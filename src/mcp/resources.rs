@@ -1,10 +1,19 @@
 //! Resource handlers for exposing JSON schemas
 //!
 //! This module provides functions for MCP resource discovery and retrieval.
-//! Resources expose JSON schemas generated from Rust types.
+//! Resources expose JSON schemas generated from Rust types, a changelog that
+//! clients can read after a list-changed notification to see what's new, and
+//! the server's resolved configuration for operators debugging a deployment.
 
+use crate::base_url::BaseUrl;
 use crate::documents::{CoverLetter, Resume};
+use crate::mcp::tools::{self, ToolContext};
+use crate::origins::OriginAllowlist;
+use crate::rate_limit::RateLimiter;
+use crate::storage::FileStorage;
+use crate::typst::{paper, pool, prelude};
 use rmcp::model::{AnnotateAble, RawResource, Resource, ResourceContents};
+use serde::Serialize;
 
 /// URI for the resume schema resource
 pub const RESUME_SCHEMA_URI: &str = "docgen://schemas/resume";
@@ -12,6 +21,269 @@ pub const RESUME_SCHEMA_URI: &str = "docgen://schemas/resume";
 /// URI for the cover letter schema resource
 pub const COVER_LETTER_SCHEMA_URI: &str = "docgen://schemas/cover-letter";
 
+/// URI for the capability changelog resource
+pub const CHANGELOG_URI: &str = "docgen://changelog";
+
+/// URI for the resolved configuration resource
+pub const CONFIG_URI: &str = "docgen://config";
+
+/// One capability change, as listed in the changelog resource
+#[derive(Debug, Serialize)]
+pub struct ChangeEntry {
+    /// What kind of capability changed: "tool", "resource", "option", or "field"
+    pub kind: &'static str,
+    /// Name of the tool, resource URI, input option, or document field
+    pub name: &'static str,
+    /// "added", "changed", or "removed"
+    pub change: &'static str,
+    /// Short human-readable description
+    pub description: &'static str,
+}
+
+/// Recent capability changes, newest first
+pub const CHANGELOG: &[ChangeEntry] = &[
+    ChangeEntry {
+        kind: "option",
+        name: "density",
+        change: "added",
+        description: "Comfortable or compact spacing between resume entries",
+    },
+    ChangeEntry {
+        kind: "resource",
+        name: "docgen://config",
+        change: "added",
+        description: "The server's resolved configuration, with secrets redacted",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "page_numbers",
+        change: "added",
+        description: "Show or hide the page number footer, overriding showPageNumbers",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "deterministic_id",
+        change: "added",
+        description: "Store generated files under an ID derived from their content",
+    },
+    ChangeEntry {
+        kind: "tool",
+        name: "compare_resumes",
+        change: "added",
+        description: "Render two resumes side by side in one PDF",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "normalize",
+        change: "added",
+        description: "validate_resume can return the resume in canonical form as 'normalized'",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "icon_style",
+        change: "added",
+        description: "Draw contact icons from bundled SVGs instead of the icon font",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "combine_recognition",
+        change: "added",
+        description: "Awards and certifications under one Honors & Certifications heading",
+    },
+    ChangeEntry {
+        kind: "tool",
+        name: "verify_resume_text",
+        change: "added",
+        description: "Generate a resume and return the text extracted from its PDF",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "section_colors",
+        change: "added",
+        description: "Per-section heading colors as hex",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "role",
+        change: "added",
+        description: "Target role argument of the resume-best-practices prompt, with completions",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "divider_style",
+        change: "added",
+        description: "Full, short, or no rule under section headings",
+    },
+    ChangeEntry {
+        kind: "tool",
+        name: "generate_cover_letter",
+        change: "changed",
+        description: "Default filename is now First_Last_Cover_Letter.pdf, matching the resume pattern",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "dedupe_skills",
+        change: "added",
+        description: "Merge same-named skill categories and drop repeated keywords",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "publication_math",
+        change: "added",
+        description: "Inline $...$ math in publication titles, venues, and summaries",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "debug",
+        change: "added",
+        description: "Compile errors from the generate tools can include a source excerpt",
+    },
+    ChangeEntry {
+        kind: "field",
+        name: "work[].logo",
+        change: "added",
+        description: "Company logo next to the company name, from an asset or inline base64",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "failures_only",
+        change: "added",
+        description: "validate_resume_batch can return only the invalid payloads",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "summary_style",
+        change: "added",
+        description: "Render the resume summary as plain text or a shaded callout box",
+    },
+    ChangeEntry {
+        kind: "tool",
+        name: "list_style_options",
+        change: "added",
+        description: "Accepted values and defaults of the resume style options",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "estimate_pages",
+        change: "added",
+        description: "validate_resume can report the rendered page count",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "ttl_seconds",
+        change: "added",
+        description: "Per-request lifetime for stored download links",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "treat_warnings_as_errors",
+        change: "added",
+        description: "Fail generation when the Typst compiler reports warnings",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "thumbnail",
+        change: "added",
+        description: "Return a small first-page PNG alongside the generated PDF",
+    },
+    ChangeEntry {
+        kind: "tool",
+        name: "resume_completeness",
+        change: "added",
+        description: "Completeness percentage with missing recommended sections",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "highlight_keywords",
+        change: "added",
+        description: "Bold job-posting keywords in highlights and the summary",
+    },
+    ChangeEntry {
+        kind: "tool",
+        name: "export_resume_latex",
+        change: "added",
+        description: "Export a resume as moderncv LaTeX source",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "language_style",
+        change: "added",
+        description: "Proficiency dots for recognized language fluency levels",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "emphasis",
+        change: "added",
+        description: "Section ordering presets for experienced candidates and students",
+    },
+    ChangeEntry {
+        kind: "tool",
+        name: "get_typescript_types",
+        change: "added",
+        description: "TypeScript declarations generated from a document schema",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "strict",
+        change: "added",
+        description: "Validate against the published JSON Schema, including formats",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "max_highlights_per_entry",
+        change: "added",
+        description: "Cap the highlights rendered per resume entry",
+    },
+    ChangeEntry {
+        kind: "tool",
+        name: "export_resume_text",
+        change: "added",
+        description: "Export a resume as structured plain text for ATS systems",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "signature_space",
+        change: "added",
+        description: "Reserve a ruled space above the typed name on cover letters",
+    },
+    ChangeEntry {
+        kind: "resource",
+        name: CHANGELOG_URI,
+        change: "added",
+        description: "Structured list of recent capability changes",
+    },
+    ChangeEntry {
+        kind: "field",
+        name: "work[].current",
+        change: "added",
+        description: "Mark a position as current instead of setting endDate",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "redact_contact",
+        change: "added",
+        description: "Render resumes with placeholder contact details",
+    },
+    ChangeEntry {
+        kind: "tool",
+        name: "preview_section",
+        change: "added",
+        description: "Render a single resume section as PNG or PDF",
+    },
+    ChangeEntry {
+        kind: "option",
+        name: "formats",
+        change: "added",
+        description: "Return several output formats from one generate_resume call",
+    },
+    ChangeEntry {
+        kind: "tool",
+        name: "validate_resume_batch",
+        change: "added",
+        description: "Validate several resumes in one call",
+    },
+];
+
 /// Returns a list of all available resources
 pub fn list_resources() -> Vec<Resource> {
     let mut resume_resource = RawResource::new(RESUME_SCHEMA_URI, "Resume Schema");
@@ -22,12 +294,64 @@ pub fn list_resources() -> Vec<Resource> {
     cover_letter_resource.description = Some("JSON Schema for cover letter documents".to_string());
     cover_letter_resource.mime_type = Some("application/schema+json".to_string());
 
+    let mut changelog_resource = RawResource::new(CHANGELOG_URI, "Changelog");
+    changelog_resource.description = Some("Recent capability changes".to_string());
+    changelog_resource.mime_type = Some("application/json".to_string());
+
+    let mut config_resource = RawResource::new(CONFIG_URI, "Configuration");
+    config_resource.description =
+        Some("Resolved server configuration, with secrets redacted".to_string());
+    config_resource.mime_type = Some("application/json".to_string());
+
     vec![
         resume_resource.no_annotation(),
         cover_letter_resource.no_annotation(),
+        changelog_resource.no_annotation(),
+        config_resource.no_annotation(),
     ]
 }
 
+/// The configuration in effect, resolved the way the server resolves it
+///
+/// Values come from the environment with defaults applied. The prelude is
+/// reported by path only and credentials in the public base URL are masked.
+/// Rate limiting, allowed origins and the base URL only apply in HTTP mode.
+pub fn resolved_config() -> serde_json::Value {
+    let context = ToolContext::stdio();
+    let storage = FileStorage::from_env();
+    let tools: Vec<String> = tools::list_tools()
+        .into_iter()
+        .map(|tool| tool.name.to_string())
+        .collect();
+
+    serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "default_page_size": paper::default_page_size().as_str(),
+        "max_pages": context.max_pages,
+        "compile_threads": pool::compile_pool().threads(),
+        "assets": {
+            "max_asset_bytes": context.asset_limits.max_asset_bytes,
+            "max_total_bytes": context.asset_limits.max_total_bytes,
+        },
+        "files": {
+            "default_ttl_seconds": storage.ttl_for(None).as_secs(),
+            "max_ttl_seconds": storage.max_ttl().as_secs(),
+        },
+        "default_signature": context.default_signature,
+        "prelude_path": prelude::prelude()
+            .and_then(|_| std::env::var(prelude::PRELUDE_PATH_ENV).ok()),
+        "http": {
+            "public_base_url": BaseUrl::from_env(String::new()).redacted(),
+            "rate_limit_per_minute": RateLimiter::from_env().map(|l| l.per_minute()),
+            "allowed_origins": OriginAllowlist::from_env()
+                .ok()
+                .flatten()
+                .map(|allowlist| allowlist.describe()),
+        },
+        "tools": tools,
+    })
+}
+
 /// Reads a resource by URI and returns its contents
 pub fn read_resource(uri: &str) -> Option<ResourceContents> {
     match uri {
@@ -55,6 +379,32 @@ pub fn read_resource(uri: &str) -> Option<ResourceContents> {
                 meta: None,
             })
         }
+        CHANGELOG_URI => {
+            let changelog = serde_json::json!({
+                "version": env!("CARGO_PKG_VERSION"),
+                "changes": CHANGELOG,
+            });
+            let changelog_json =
+                serde_json::to_string_pretty(&changelog).expect("Failed to serialize changelog");
+
+            Some(ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some("application/json".to_string()),
+                text: changelog_json,
+                meta: None,
+            })
+        }
+        CONFIG_URI => {
+            let config_json = serde_json::to_string_pretty(&resolved_config())
+                .expect("Failed to serialize configuration");
+
+            Some(ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some("application/json".to_string()),
+                text: config_json,
+                meta: None,
+            })
+        }
         _ => None,
     }
 }
@@ -66,11 +416,13 @@ mod tests {
     #[test]
     fn test_list_resources() {
         let resources = list_resources();
-        assert_eq!(resources.len(), 2);
+        assert_eq!(resources.len(), 4);
         assert_eq!(resources[0].raw.uri, RESUME_SCHEMA_URI);
         assert_eq!(resources[0].raw.name, "Resume Schema");
         assert_eq!(resources[1].raw.uri, COVER_LETTER_SCHEMA_URI);
         assert_eq!(resources[1].raw.name, "Cover Letter Schema");
+        assert_eq!(resources[2].raw.uri, CHANGELOG_URI);
+        assert_eq!(resources[3].raw.uri, CONFIG_URI);
     }
 
     #[test]
@@ -90,6 +442,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_read_changelog() {
+        let contents = read_resource(CHANGELOG_URI);
+        let Some(ResourceContents::TextResourceContents { text, .. }) = contents else {
+            panic!("Expected TextResourceContents");
+        };
+
+        let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(parsed["version"], env!("CARGO_PKG_VERSION"));
+
+        let changes = parsed["changes"].as_array().unwrap();
+        assert!(!changes.is_empty());
+        for change in changes {
+            for key in ["kind", "name", "change", "description"] {
+                assert!(change[key].is_string(), "missing {} in {}", key, change);
+            }
+        }
+        assert!(changes.iter().any(|c| c["name"] == "preview_section"));
+    }
+
+    #[test]
+    fn test_read_config() {
+        let contents = read_resource(CONFIG_URI);
+        let Some(ResourceContents::TextResourceContents { text, .. }) = contents else {
+            panic!("Expected TextResourceContents");
+        };
+
+        let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(parsed["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            parsed["default_page_size"],
+            paper::default_page_size().as_str()
+        );
+        assert!(parsed["max_pages"].as_u64().unwrap() > 0);
+        assert!(parsed["assets"]["max_asset_bytes"].is_u64());
+        assert!(
+            parsed["files"]["default_ttl_seconds"].as_u64().unwrap()
+                <= parsed["files"]["max_ttl_seconds"].as_u64().unwrap()
+        );
+
+        let names = parsed["tools"].as_array().unwrap();
+        assert_eq!(names.len(), tools::list_tools().len());
+        assert!(names.iter().any(|t| t == tools::GENERATE_RESUME_TOOL));
+    }
+
     #[test]
     fn test_read_unknown_resource() {
         let contents = read_resource("docgen://unknown");
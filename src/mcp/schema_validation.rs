@@ -0,0 +1,119 @@
+//! Validation against the published JSON Schemas
+//!
+//! Regular validation deserializes with serde, which ignores constraints that
+//! only exist in the generated schema (such as the `email` and `uri` formats).
+//! Strict validation runs the payload through a JSON Schema validator built
+//! from the same schemas served as resources, and reports every violation.
+
+use std::sync::LazyLock;
+
+use jsonschema::Validator;
+use serde_json::Value;
+
+use crate::documents::{CoverLetter, Resume};
+use crate::mcp::tools::ValidationError;
+
+static RESUME_VALIDATOR: LazyLock<Validator> =
+    LazyLock::new(|| build_validator(schemars::schema_for!(Resume)));
+
+static COVER_LETTER_VALIDATOR: LazyLock<Validator> =
+    LazyLock::new(|| build_validator(schemars::schema_for!(CoverLetter)));
+
+fn build_validator(schema: schemars::Schema) -> Validator {
+    let schema = serde_json::to_value(schema).expect("Failed to serialize schema");
+    jsonschema::options()
+        .should_validate_formats(true)
+        .build(&schema)
+        .expect("Generated schema should be a valid JSON Schema")
+}
+
+/// All violations of the resume schema
+pub fn resume_schema_errors(resume: &Value) -> Vec<ValidationError> {
+    schema_errors(&RESUME_VALIDATOR, resume)
+}
+
+/// All violations of the cover letter schema
+pub fn cover_letter_schema_errors(cover_letter: &Value) -> Vec<ValidationError> {
+    schema_errors(&COVER_LETTER_VALIDATOR, cover_letter)
+}
+
+fn schema_errors(validator: &Validator, instance: &Value) -> Vec<ValidationError> {
+    validator
+        .iter_errors(instance)
+        .map(|error| {
+            let pointer = error.instance_path.to_string();
+            ValidationError::new(pointer_to_path(&pointer), error.to_string()).with_pointer(pointer)
+        })
+        .collect()
+}
+
+/// Convert a JSON pointer (`/work/0/startDate`) to the dotted path style used
+/// by other validation errors (`work[0].startDate`)
+fn pointer_to_path(pointer: &str) -> String {
+    let mut path = String::new();
+    for segment in pointer.split('/').skip(1) {
+        let segment = segment.replace("~1", "/").replace("~0", "~");
+        if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+            path.push_str(&format!("[{}]", segment));
+        } else {
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(&segment);
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pointer_to_path() {
+        assert_eq!(pointer_to_path(""), "");
+        assert_eq!(pointer_to_path("/basics/email"), "basics.email");
+        assert_eq!(
+            pointer_to_path("/work/0/highlights/2"),
+            "work[0].highlights[2]"
+        );
+        assert_eq!(pointer_to_path("/sectionTitles/a~1b"), "sectionTitles.a/b");
+    }
+
+    #[test]
+    fn test_schema_reports_format_violations() {
+        let resume = serde_json::json!({
+            "basics": {
+                "name": "Test User",
+                "email": "not-an-email",
+                "profiles": [{ "network": "GitHub", "url": "not a url" }]
+            },
+            "work": []
+        });
+
+        // Serde alone accepts the payload
+        assert!(serde_json::from_value::<Resume>(resume.clone()).is_ok());
+
+        let errors = resume_schema_errors(&resume);
+        assert!(errors.iter().any(|e| e.path == "basics.email"));
+        assert!(errors.iter().any(|e| e.path == "basics.profiles[0].url"));
+        let email = errors.iter().find(|e| e.path == "basics.email").unwrap();
+        assert_eq!(email.pointer.as_deref(), Some("/basics/email"));
+    }
+
+    #[test]
+    fn test_valid_documents_pass_schema() {
+        let resume: Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+        assert!(resume_schema_errors(&resume).is_empty());
+
+        let cover_letter = serde_json::json!({
+            "sender": { "name": "Jane Doe", "email": "jane@example.com" },
+            "recipient": { "company": "Tech Corp" },
+            "opening": "Opening.",
+            "body": ["Body."],
+            "closing": "Closing."
+        });
+        assert!(cover_letter_schema_errors(&cover_letter).is_empty());
+    }
+}
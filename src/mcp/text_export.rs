@@ -0,0 +1,316 @@
+//! Plain-text rendering of resumes
+//!
+//! Some applicant tracking systems prefer pasted plain text over a PDF. This
+//! walks the deserialized `Resume` directly (no Typst involved) and emits a
+//! structured text version: the header, an optional summary, then each
+//! section under an upper-case heading in the same order the PDF uses.
+
+use crate::documents::Resume;
+use crate::documents::resume::{Highlight, SECTION_NAMES};
+
+/// Default heading for a standard section (matches the PDF template)
+pub fn default_title(section: &str) -> &'static str {
+    match section {
+        "education" => "Education",
+        "experience" => "Experience",
+        "projects" => "Projects",
+        "certifications" => "Certifications",
+        "awards" => "Awards",
+        "publications" => "Publications",
+        "skills" => "Technical Skills",
+        "languages" => "Languages",
+        _ => "",
+    }
+}
+
+/// Render a resume as structured plain text
+pub fn resume_to_text(resume: &Resume) -> String {
+    let mut blocks = vec![header(resume)];
+
+    if let Some(summary) = resume
+        .basics
+        .summary
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+    {
+        blocks.push(format!("SUMMARY\n{}", summary.trim()));
+    }
+
+    let order: Vec<&str> = match &resume.section_order {
+        Some(order) => order.iter().map(String::as_str).collect(),
+        None => SECTION_NAMES.to_vec(),
+    };
+
+    for section in &order {
+        if let Some(block) = section_block(resume, section) {
+            blocks.push(block);
+        }
+    }
+
+    // Custom sections not positioned via sectionOrder follow the standard ones
+    for custom in &resume.custom_sections {
+        if !order.contains(&custom.title.as_str())
+            && let Some(block) = section_block(resume, &custom.title)
+        {
+            blocks.push(block);
+        }
+    }
+
+    let mut text = blocks.join("\n\n");
+    text.push('\n');
+    text
+}
+
+/// Name, contact line, and profile links
+fn header(resume: &Resume) -> String {
+    let basics = &resume.basics;
+    let mut lines = vec![basics.name.clone()];
+
+    let contact = join_present(
+        [
+            basics.location.as_deref(),
+            basics.phone.as_deref(),
+            Some(basics.email.as_str()),
+        ],
+        " | ",
+    );
+    lines.push(contact);
+
+    for profile in &basics.profiles {
+        let network = profile.canonical_network().unwrap_or(&profile.network);
+        lines.push(format!("{}: {}", network, profile.url));
+    }
+
+    lines.join("\n")
+}
+
+/// One section with its heading, or `None` if it has no entries
+fn section_block(resume: &Resume, section: &str) -> Option<String> {
+    let entries: Vec<String> = match section {
+        "education" => resume
+            .education
+            .iter()
+            .map(|edu| {
+                let degree = join_present(
+                    [edu.degree.as_deref(), edu.field_of_study.as_deref()],
+                    " in ",
+                );
+                let mut lines = vec![join_present(
+                    [Some(edu.institution.as_str()), edu.location.as_deref()],
+                    ", ",
+                )];
+                lines.extend(non_empty(degree));
+                lines.extend(date_range(
+                    edu.start_date.as_deref(),
+                    edu.end_date.as_deref(),
+                ));
+                lines.extend(edu.gpa.as_ref().map(|gpa| format!("GPA: {}", gpa)));
+                lines.extend(bullets(&edu.highlights));
+                lines.join("\n")
+            })
+            .collect(),
+        "experience" => resume
+            .work
+            .iter()
+            .map(|job| {
+                let mut lines = vec![join_present(
+                    [
+                        Some(job.position.as_str()),
+                        Some(job.company.as_str()),
+                        job.location.as_deref(),
+                    ],
+                    " | ",
+                )];
+                let present = (job.current == Some(true)).then_some("Present");
+                let end = job.end_date.as_deref().or(present);
+                lines.extend(date_range(job.start_date.as_deref(), end));
+                lines.extend(bullets(&job.highlights));
+                lines.join("\n")
+            })
+            .collect(),
+        "projects" => resume
+            .projects
+            .iter()
+            .map(|project| {
+                let mut lines = vec![project.name.clone()];
+                lines.extend(date_range(
+                    project.start_date.as_deref(),
+                    project.end_date.as_deref(),
+                ));
+                lines.extend(project.url.clone());
+                lines.extend(project.description.clone());
+                if !project.keywords.is_empty() {
+                    lines.push(format!("Technologies: {}", project.keywords.join(", ")));
+                }
+                lines.extend(bullets(&project.highlights));
+                lines.join("\n")
+            })
+            .collect(),
+        "certifications" => resume
+            .certifications
+            .iter()
+            .map(|cert| {
+                join_present(
+                    [
+                        Some(cert.name.as_str()),
+                        cert.issuer.as_deref(),
+                        cert.date.as_deref(),
+                        cert.url.as_deref(),
+                    ],
+                    " | ",
+                )
+            })
+            .collect(),
+        "awards" => resume
+            .awards
+            .iter()
+            .map(|award| {
+                let mut lines = vec![join_present(
+                    [
+                        Some(award.title.as_str()),
+                        award.awarder.as_deref(),
+                        award.date.as_deref(),
+                    ],
+                    " | ",
+                )];
+                lines.extend(award.summary.clone());
+                lines.join("\n")
+            })
+            .collect(),
+        "publications" => resume
+            .publications
+            .iter()
+            .map(|publication| {
+                let mut lines = vec![join_present(
+                    [
+                        Some(publication.title.as_str()),
+                        publication.venue.as_deref(),
+                        publication.date.as_deref(),
+                    ],
+                    " | ",
+                )];
+                if !publication.authors.is_empty() {
+                    lines.push(publication.authors.join(", "));
+                }
+                lines.extend(publication.url.clone());
+                lines.extend(publication.summary.clone());
+                lines.join("\n")
+            })
+            .collect(),
+        "skills" => resume
+            .skills
+            .iter()
+            .map(|skill| format!("{}: {}", skill.name, skill.keywords.join(", ")))
+            .collect(),
+        "languages" => resume
+            .languages
+            .iter()
+            .map(|language| match &language.fluency {
+                Some(fluency) => format!("{} ({})", language.language, fluency),
+                None => language.language.clone(),
+            })
+            .collect(),
+        custom => resume
+            .custom_sections
+            .iter()
+            .filter(|c| c.title == custom)
+            .flat_map(|c| c.items.iter().map(|item| format!("- {}", item)))
+            .collect(),
+    };
+
+    if entries.is_empty() {
+        return None;
+    }
+
+    let title = resume
+        .section_titles
+        .as_ref()
+        .and_then(|titles| titles.get(section))
+        .map(String::as_str)
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or_else(|| match default_title(section) {
+            "" => section,
+            title => title,
+        });
+
+    // Multi-line entries are separated by a blank line; one-liners stay compact
+    let separator = if entries.iter().any(|e| e.contains('\n')) {
+        "\n\n"
+    } else {
+        "\n"
+    };
+    Some(format!(
+        "{}\n{}",
+        title.to_uppercase(),
+        entries.join(separator)
+    ))
+}
+
+/// "start - end" for whichever ends are present
+fn date_range(start: Option<&str>, end: Option<&str>) -> Option<String> {
+    non_empty(join_present([start, end], " - "))
+}
+
+/// Highlights as "- " bullet lines
+fn bullets(highlights: &[Highlight]) -> impl Iterator<Item = String> + '_ {
+    highlights
+        .iter()
+        .map(|highlight| format!("- {}", highlight.plain_text()))
+}
+
+/// Join the present, non-blank parts with a separator
+fn join_present<const N: usize>(parts: [Option<&str>; N], separator: &str) -> String {
+    parts
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn non_empty(text: String) -> Option<String> {
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_resume() -> Resume {
+        serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap()
+    }
+
+    #[test]
+    fn test_text_export_contains_content_in_order() {
+        let text = resume_to_text(&sample_resume());
+
+        assert!(text.starts_with("Jane Smith\n"));
+        assert!(text.contains("San Francisco, CA | +1-555-123-4567 | jane.smith@example.com"));
+        assert!(text.contains("Senior Software Engineer | Tech Innovations Inc."));
+        assert!(text.contains("StartupXYZ"));
+        assert!(text.contains("- Mentored team of 4 junior developers"));
+
+        let position = |heading: &str| text.find(&format!("\n{}\n", heading)).unwrap();
+        assert!(position("SUMMARY") < position("EDUCATION"));
+        assert!(position("EDUCATION") < position("EXPERIENCE"));
+        assert!(position("EXPERIENCE") < position("TECHNICAL SKILLS"));
+    }
+
+    #[test]
+    fn test_text_export_respects_section_order_and_titles() {
+        let mut resume = sample_resume();
+        resume.section_order = Some(vec!["skills".to_string(), "experience".to_string()]);
+        resume.section_titles = Some(
+            [("skills".to_string(), "Core Competencies".to_string())]
+                .into_iter()
+                .collect(),
+        );
+
+        let text = resume_to_text(&resume);
+        let skills = text.find("CORE COMPETENCIES").unwrap();
+        let experience = text.find("EXPERIENCE").unwrap();
+        assert!(skills < experience);
+        assert!(!text.contains("EDUCATION"), "Omitted sections are hidden");
+    }
+}
@@ -5,18 +5,51 @@
 //! - Document type discovery
 //! - Resume tools (schema, best practices, validate, generate)
 //! - Cover letter tools (schema, best practices, validate, generate)
+//! - Schema compatibility diagnostics
 
-use rmcp::model::Tool;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use rmcp::model::{CallToolResult, Content, ResourceContents, Tool};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
 use std::sync::Arc;
-
-use crate::documents::{CoverLetter, Resume};
+use std::time::Duration;
+use typst::diag::SourceDiagnostic;
+use typst::foundations::Bytes;
+
+use crate::documents::cover_letter::DEFAULT_SIGNATURE;
+use crate::documents::resume::SECTION_NAMES;
+use crate::documents::{CoverLetter, DocumentType, Resume};
+use crate::fetch::{FetchPolicy, fetch_json};
+use crate::mcp::ats::score_resume;
+use crate::mcp::checks::{check_resume, strip_control_characters};
+use crate::mcp::completeness::resume_completeness;
+use crate::mcp::input_format::{InputFormat, parse_content, parse_json_strict};
+use crate::mcp::latex_export::resume_to_latex;
+use crate::mcp::schema_validation::{cover_letter_schema_errors, resume_schema_errors};
+use crate::mcp::text_export::resume_to_text;
+use crate::mcp::typescript::schema_to_typescript;
 use crate::mcp::{prompts, resources};
 use crate::storage::FileStorage;
-use crate::typst::compiler::compile;
-use crate::typst::transform::{transform_cover_letter, transform_resume};
+use crate::typst::assets::{
+    AssetLimits, AssetMap, check_asset_names, compress_images, decode_assets, decode_image,
+};
+use crate::typst::cache::{CompiledOutputs, OutputKey, output_cache};
+use crate::typst::compiler::{
+    ExportOptions, OutputFormat, compile_document_with_page_limit, compile_world_to_formats,
+    compile_world_with_page_count, compile_world_with_thumbnail, max_pages_from_env,
+    source_excerpt,
+};
+use crate::typst::pool::compile_pool;
+use crate::typst::transform::{
+    COMPARISON_ASSETS, ContactStyle, CoverLetterOptions, Density, DividerStyle, Emphasis,
+    HeadingWeight, IconStyle, LanguageStyle, MAX_RULE_THICKNESS, RenderOptions, SkillsStyle,
+    SummaryPlacement, SummaryStyle, transform_comparison, transform_cover_letter, transform_resume,
+    transform_resume_section,
+};
+use crate::typst::world::DocgenWorld;
 
 /// Tool name for discovering available document types
 pub const GET_DOCUMENT_TYPES_TOOL: &str = "get_document_types";
@@ -33,9 +66,24 @@ pub const GET_RESUME_BEST_PRACTICES_TOOL: &str = "get_resume_best_practices";
 /// Tool name for resume validation
 pub const VALIDATE_RESUME_TOOL: &str = "validate_resume";
 
+/// Tool name for validating several resumes in one call
+pub const VALIDATE_RESUME_BATCH_TOOL: &str = "validate_resume_batch";
+
 /// Tool name for resume generation
 pub const GENERATE_RESUME_TOOL: &str = "generate_resume";
 
+/// Tool name for rendering a single resume section as a preview
+pub const PREVIEW_SECTION_TOOL: &str = "preview_section";
+
+/// Tool name for exporting a resume as plain text
+pub const EXPORT_RESUME_TEXT_TOOL: &str = "export_resume_text";
+
+/// Tool name for exporting a resume as LaTeX source
+pub const EXPORT_RESUME_LATEX_TOOL: &str = "export_resume_latex";
+
+/// Tool name for rendering two resumes side by side in one PDF
+pub const COMPARE_RESUMES_TOOL: &str = "compare_resumes";
+
 /// Tool name for getting cover letter schema
 pub const GET_COVER_LETTER_SCHEMA_TOOL: &str = "get_cover_letter_schema";
 
@@ -48,12 +96,43 @@ pub const VALIDATE_COVER_LETTER_TOOL: &str = "validate_cover_letter";
 /// Tool name for cover letter generation
 pub const GENERATE_COVER_LETTER_TOOL: &str = "generate_cover_letter";
 
+/// Tool name for the preflight schema compatibility check
+pub const CHECK_COMPATIBILITY_TOOL: &str = "check_compatibility";
+
+/// Tool name for ATS-readiness scoring
+pub const ATS_SCORE_TOOL: &str = "ats_score";
+
+/// Tool name for the resume completeness metric
+pub const RESUME_COMPLETENESS_TOOL: &str = "resume_completeness";
+
+/// Tool name for extracting the text of a generated resume
+pub const VERIFY_RESUME_TEXT_TOOL: &str = "verify_resume_text";
+
+/// Tool name for TypeScript declarations of a document schema
+pub const GET_TYPESCRIPT_TYPES_TOOL: &str = "get_typescript_types";
+
+/// Tool name for listing the accepted values of style options
+pub const LIST_STYLE_OPTIONS_TOOL: &str = "list_style_options";
+
+/// Environment variable that sets the server-wide default cover letter sign-off
+pub const DEFAULT_SIGNATURE_ENV: &str = "DOCGEN_DEFAULT_SIGNATURE";
+
 /// Context for tool execution (passed from server)
 pub struct ToolContext {
     /// File storage for remote PDF delivery (HTTP mode only)
     pub file_storage: Option<FileStorage>,
     /// Base URL for generating download links (HTTP mode only)
     pub base_url: Option<String>,
+    /// Limits applied when fetching a `source_url` payload
+    pub fetch_policy: FetchPolicy,
+    /// Size limits for embedded `assets`
+    pub asset_limits: AssetLimits,
+    /// Most pages a generated document may have
+    pub max_pages: usize,
+    /// Sign-off used for cover letters that don't specify a `signature`
+    pub default_signature: String,
+    /// MCP session id, used to tag stored files (HTTP mode only)
+    pub session_id: Option<String>,
 }
 
 impl ToolContext {
@@ -62,6 +141,11 @@ impl ToolContext {
         Self {
             file_storage: None,
             base_url: None,
+            fetch_policy: FetchPolicy::default(),
+            asset_limits: AssetLimits::from_env(),
+            max_pages: max_pages_from_env(),
+            default_signature: default_signature_from_env(),
+            session_id: None,
         }
     }
 
@@ -70,10 +154,24 @@ impl ToolContext {
         Self {
             file_storage: Some(file_storage),
             base_url: Some(base_url),
+            fetch_policy: FetchPolicy::default(),
+            asset_limits: AssetLimits::from_env(),
+            max_pages: max_pages_from_env(),
+            default_signature: default_signature_from_env(),
+            session_id: None,
         }
     }
 }
 
+/// The cover letter sign-off configured via `DEFAULT_SIGNATURE_ENV`, or "Sincerely"
+fn default_signature_from_env() -> String {
+    std::env::var(DEFAULT_SIGNATURE_ENV)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| DEFAULT_SIGNATURE.to_string())
+}
+
 /// Result of a validation operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "status")]
@@ -83,6 +181,17 @@ pub enum ValidationResult {
     Valid {
         /// The validated resume (echoed back for confirmation)
         resume: Box<Resume>,
+        /// Non-blocking suggestions about the content
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<ValidationError>,
+        /// Page count of the resume rendered with default options, when
+        /// `estimate_pages` was requested
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        estimated_pages: Option<usize>,
+        /// Canonical form of the resume (see `Resume::normalize`), when
+        /// `normalize` was requested
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        normalized: Option<Box<Resume>>,
     },
     /// Validation failed with errors
     #[serde(rename = "invalid")]
@@ -92,6 +201,23 @@ pub enum ValidationResult {
     },
 }
 
+/// Validation result for one entry of a batch, tagged with its position
+#[derive(Debug, Serialize)]
+pub struct BatchValidationItem {
+    /// Index of the payload in the submitted `resumes` array
+    pub index: usize,
+    #[serde(flatten)]
+    pub result: ValidationResult,
+}
+
+/// Result of the validate_resume_batch tool
+#[derive(Debug, Serialize)]
+pub struct BatchValidationResult {
+    /// One result per submitted payload, in submission order (only the
+    /// invalid ones when `failures_only` is set)
+    pub results: Vec<BatchValidationItem>,
+}
+
 /// Result of a generation operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "status")]
@@ -107,6 +233,15 @@ pub enum GenerationResult {
         download_url: Option<String>,
         /// Human-readable success message
         message: String,
+        /// Non-blocking warnings about the document content
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        warnings: Vec<ValidationError>,
+        /// Base64-encoded outputs keyed by format, when `formats` was requested
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        outputs: BTreeMap<String, String>,
+        /// Base64-encoded PNG of the first page, when `thumbnail` was requested
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        thumbnail_base64: Option<String>,
     },
     /// Generation failed (validation or compilation error)
     #[serde(rename = "error")]
@@ -116,6 +251,10 @@ pub enum GenerationResult {
         /// Validation errors if applicable
         #[serde(skip_serializing_if = "Option::is_none")]
         validation_errors: Option<Vec<ValidationError>>,
+        /// Generated Typst source around the failing line, for compile
+        /// failures when `debug` was requested
+        #[serde(skip_serializing_if = "Option::is_none")]
+        source_excerpt: Option<String>,
     },
 }
 
@@ -126,6 +265,13 @@ pub struct ValidationError {
     pub path: String,
     /// Human-readable error message
     pub message: String,
+    /// JSON Pointer to the error location (e.g., "/basics/email"), set by
+    /// strict schema validation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pointer: Option<String>,
+    /// What the field expects, taken from its schema description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
 }
 
 impl ValidationError {
@@ -134,8 +280,22 @@ impl ValidationError {
         Self {
             path: path.into(),
             message: message.into(),
+            pointer: None,
+            hint: None,
         }
     }
+
+    /// Attach a hint about the expected value, if there is one
+    pub fn with_hint(mut self, hint: Option<String>) -> Self {
+        self.hint = hint;
+        self
+    }
+
+    /// Attach the JSON Pointer of the error location
+    pub fn with_pointer(mut self, pointer: impl Into<String>) -> Self {
+        self.pointer = Some(pointer.into());
+        self
+    }
 }
 
 /// Returns a list of all available tools
@@ -162,49 +322,405 @@ pub fn list_tools() -> Vec<Tool> {
 
     // ========== RESUME TOOLS ==========
 
-    // Schema for validate_resume
-    let mut resume_prop = serde_json::Map::new();
-    resume_prop.insert("type".to_string(), Value::String("object".to_string()));
-    resume_prop.insert(
-        "description".to_string(),
-        Value::String("The resume JSON payload. Use 'get_resume_schema' tool to see the full schema structure.".to_string()),
+    // Schema for validate_resume (field-level structure generated from the Resume type)
+    let (resume_prop, resume_defs) = document_schema_prop(
+        schemars::schema_for!(Resume),
+        "The resume JSON payload. Use 'get_resume_schema' tool to see the full schema structure.",
     );
 
-    let mut validate_resume_properties = serde_json::Map::new();
-    validate_resume_properties.insert("resume".to_string(), Value::Object(resume_prop.clone()));
+    let strict_prop = serde_json::json!({
+        "type": "boolean",
+        "description": "Optional. Also validate against the published JSON Schema, enforcing constraints serde does not check (such as 'email' and 'uri' formats). Every violation is reported with a JSON 'pointer'. Default: false."
+    });
+
+    let estimate_pages_prop = serde_json::json!({
+        "type": "boolean",
+        "description": "Optional. Compile a valid resume once with default render options and report its page count as 'estimated_pages'. Slower than plain validation. Default: false."
+    });
+
+    let normalize_prop = serde_json::json!({
+        "type": "boolean",
+        "description": "Optional. Also return a valid resume in canonical form as 'normalized', for storing: whitespace trimmed from every text value, blank optional fields removed, alternate field names replaced by the standard ones, and profile networks given their canonical names. Default: false."
+    });
+
+    let content_prop = serde_json::json!({
+        "type": "string",
+        "description": "Optional. The resume as text in the format given by 'format', instead of passing 'resume' as an object."
+    });
+
+    let content_format_prop = serde_json::json!({
+        "type": "string",
+        "enum": ["json", "yaml", "toml"],
+        "description": "Optional. Format of 'content'. Default: json."
+    });
 
+    let mut validate_resume_properties = serde_json::Map::new();
+    validate_resume_properties.insert("resume".to_string(), resume_prop.clone());
+    validate_resume_properties.insert("content".to_string(), content_prop.clone());
+    validate_resume_properties.insert("format".to_string(), content_format_prop.clone());
+    validate_resume_properties.insert("strict".to_string(), strict_prop.clone());
+    validate_resume_properties.insert("estimate_pages".to_string(), estimate_pages_prop);
+    validate_resume_properties.insert("normalize".to_string(), normalize_prop);
+
+    // Either 'resume' or 'content' must be provided, so neither is listed as required
     let mut validate_resume_schema = serde_json::Map::new();
     validate_resume_schema.insert("type".to_string(), Value::String("object".to_string()));
+    validate_resume_schema.insert("$defs".to_string(), resume_defs.clone());
     validate_resume_schema.insert("properties".to_string(), Value::Object(validate_resume_properties));
-    validate_resume_schema.insert(
+
+    let validate_resume_schema_arc = Arc::new(validate_resume_schema);
+
+    // Schema for validate_resume_batch
+    let resumes_prop = serde_json::json!({
+        "type": "array",
+        "items": resume_prop,
+        "description": "Resume JSON payloads to validate. Each one is validated independently."
+    });
+
+    let failures_only_prop = serde_json::json!({
+        "type": "boolean",
+        "description": "Optional. Return only the invalid payloads, each still tagged with its index. Useful for bulk QA where most payloads pass. Default: false."
+    });
+
+    let mut validate_resume_batch_properties = serde_json::Map::new();
+    validate_resume_batch_properties.insert("resumes".to_string(), resumes_prop);
+    validate_resume_batch_properties.insert("failures_only".to_string(), failures_only_prop);
+
+    let mut validate_resume_batch_schema = serde_json::Map::new();
+    validate_resume_batch_schema.insert("type".to_string(), Value::String("object".to_string()));
+    validate_resume_batch_schema.insert("$defs".to_string(), resume_defs.clone());
+    validate_resume_batch_schema.insert("properties".to_string(), Value::Object(validate_resume_batch_properties));
+    validate_resume_batch_schema.insert(
         "required".to_string(),
-        Value::Array(vec![Value::String("resume".to_string())]),
+        Value::Array(vec![Value::String("resumes".to_string())]),
     );
 
-    let validate_resume_schema_arc = Arc::new(validate_resume_schema);
+    let validate_resume_batch_schema_arc = Arc::new(validate_resume_batch_schema);
 
     // Schema for generate_resume
     let mut filename_prop = serde_json::Map::new();
     filename_prop.insert("type".to_string(), Value::String("string".to_string()));
     filename_prop.insert(
         "description".to_string(),
-        Value::String("Optional filename for the generated PDF (e.g., 'john-doe-resume.pdf'). If not provided, a default name will be generated.".to_string()),
+        Value::String("Optional filename for the generated PDF (e.g., 'John_Doe_Resume.pdf'). Path separators and illegal characters are replaced. If not provided, a default name will be generated from the person's name.".to_string()),
+    );
+
+    let mut icons_prop = serde_json::Map::new();
+    icons_prop.insert("type".to_string(), Value::String("boolean".to_string()));
+    icons_prop.insert(
+        "description".to_string(),
+        Value::String("Optional. Render small icons before contact items (email, phone, links). Default: false.".to_string()),
+    );
+
+    let icon_style_prop = serde_json::json!({
+        "type": "string",
+        "enum": IconStyle::ALL,
+        "description": "Optional. How icons are drawn when 'icons' is set: 'font' uses glyphs from the bundled icon font, 'svg' uses bundled SVG images (email, phone, GitHub, LinkedIn, and a generic link). Default: font."
+    });
+
+    let mut page_breaks_prop = serde_json::Map::new();
+    page_breaks_prop.insert("type".to_string(), Value::String("array".to_string()));
+    page_breaks_prop.insert(
+        "items".to_string(),
+        Value::Object(serde_json::Map::from_iter([(
+            "type".to_string(),
+            Value::String("string".to_string()),
+        )])),
+    );
+    page_breaks_prop.insert(
+        "description".to_string(),
+        Value::String(format!(
+            "Optional. Section names that should start on a new page (e.g., ['publications']). Valid sections: {}.",
+            SECTION_NAMES.join(", ")
+        )),
+    );
+
+    let mut footer_timestamp_prop = serde_json::Map::new();
+    footer_timestamp_prop.insert("type".to_string(), Value::String("boolean".to_string()));
+    footer_timestamp_prop.insert(
+        "description".to_string(),
+        Value::String("Optional. Render a small 'Generated on <date>' line in the page footer. Default: false.".to_string()),
+    );
+
+    let style_overrides_prop = serde_json::json!({
+        "type": "object",
+        "description": "Optional. Allowlisted style tweaks; arbitrary Typst code is not accepted.",
+        "properties": {
+            "justify": { "type": "boolean", "description": "Justify paragraphs. Default: true." },
+            "heading_weight": {
+                "type": "string",
+                "enum": HeadingWeight::ALL,
+                "description": "Font weight of section headings. Default: bold."
+            },
+            "rule_thickness": {
+                "type": "number",
+                "minimum": 0,
+                "maximum": MAX_RULE_THICKNESS,
+                "description": "Thickness of the rule under section headings, in points. Default: 0.5."
+            }
+        },
+        "additionalProperties": false
+    });
+
+    let summary_placement_prop = serde_json::json!({
+        "type": "string",
+        "enum": SummaryPlacement::ALL,
+        "description": "Optional. 'header' renders the summary under the name; 'section' renders it as its own Summary section. Default: header."
+    });
+
+    let summary_style_prop = serde_json::json!({
+        "type": "string",
+        "enum": SummaryStyle::ALL,
+        "description": "Optional. 'plain' renders the summary as regular text; 'box' sets it in a shaded, bordered callout. Default: plain."
+    });
+
+    let skills_style_prop = serde_json::json!({
+        "type": "string",
+        "enum": SkillsStyle::ALL,
+        "description": "Optional. 'inline' renders each skill group on one line ('Languages: Rust, Python'); 'grouped' renders the group name followed by a bulleted list. Default: inline."
+    });
+
+    let contact_style_prop = serde_json::json!({
+        "type": "string",
+        "enum": ContactStyle::ALL,
+        "description": "Optional. 'stacked' puts the location on its own line above the contact line; 'inline' puts the location and all contact items on one bullet-separated line. Default: stacked."
+    });
+
+    let language_style_prop = serde_json::json!({
+        "type": "string",
+        "enum": LanguageStyle::ALL,
+        "description": "Optional. 'text' shows fluency in parentheses ('Spanish (Fluent)'); 'dots' shows a five-dot proficiency indicator for recognized values (Native, Fluent, Professional, Intermediate, Basic, or CEFR A1-C2) and falls back to the text otherwise. Default: text."
+    });
+
+    let highlight_keywords_prop = serde_json::json!({
+        "type": "array",
+        "items": { "type": "string" },
+        "description": "Optional. Keywords (e.g., from a job posting) to set in bold wherever they appear in highlights and the summary. Matching is case-insensitive and whole-word: 'Rust' marks 'rust' but not 'Rustic'."
+    });
+
+    let section_colors_prop = serde_json::json!({
+        "type": "object",
+        "additionalProperties": { "type": "string", "pattern": "^#([0-9a-fA-F]{3}|[0-9a-fA-F]{6})$" },
+        "description": format!(
+            "Optional. Heading and rule color per section as #RGB or #RRGGBB hex (e.g., {{\"skills\": \"#1a73e8\"}}); other sections stay black. Valid sections: {}.",
+            SECTION_NAMES.join(", ")
+        )
+    });
+
+    let divider_style_prop = serde_json::json!({
+        "type": "string",
+        "enum": DividerStyle::ALL,
+        "description": "Optional. Rule under each section heading: 'full' spans the text width, 'short' is a short rule under the start of the heading, 'none' draws no rule. Default: full."
+    });
+
+    let density_prop = serde_json::json!({
+        "type": "string",
+        "enum": Density::ALL,
+        "description": "Optional. Vertical spacing: 'comfortable' keeps the regular gaps between entries, header lines, and highlights; 'compact' tightens them to fit more on a page. Default: comfortable."
+    });
+
+    let emphasis_prop = serde_json::json!({
+        "type": "string",
+        "enum": Emphasis::ALL,
+        "description": "Optional. Section ordering preset used when the resume has no sectionOrder: 'experienced' leads with experience; 'student' leads with education, then projects and experience. An explicit sectionOrder takes precedence."
+    });
+
+    let max_highlights_prop = serde_json::json!({
+        "type": "integer",
+        "minimum": 0,
+        "description": "Optional. Render only the first N highlights of each work, education, and project entry; truncated entries are listed in 'warnings'. 0 or absent means no limit."
+    });
+
+    let mut redact_contact_prop = serde_json::Map::new();
+    redact_contact_prop.insert("type".to_string(), Value::String("boolean".to_string()));
+    redact_contact_prop.insert(
+        "description".to_string(),
+        Value::String("Optional. Replace name, email, phone, and location with placeholders and omit profile links, e.g. to share a layout without personal details. Default: false.".to_string()),
+    );
+
+    let dedupe_skills_prop = serde_json::json!({
+        "type": "boolean",
+        "description": "Optional. Merge skill categories with the same name and drop keywords already listed in an earlier category (case-insensitive; the first occurrence is kept). Categories left empty are omitted. Default: false."
+    });
+
+    let page_numbers_prop = serde_json::json!({
+        "type": "boolean",
+        "description": "Optional. true adds a 'Name | Page X of Y' footer to every page; false removes it. Overrides the resume's 'showPageNumbers'; if omitted, that setting applies (default: shown)."
+    });
+
+    let combine_recognition_prop = serde_json::json!({
+        "type": "boolean",
+        "description": "Optional. Render awards and certifications together under a single 'Honors & Certifications' heading (awards first), in the position of whichever of the two sections comes first in the section order. Default: false."
+    });
+
+    let publication_math_prop = serde_json::json!({
+        "type": "boolean",
+        "description": "Optional. Render $...$ segments of publication titles, venues, and summaries as inline math (e.g. 'Sorting in $n^2$ time'). Only plain math (symbols like alpha or arrow.r, attachments, fractions, roots) is rendered; segments with code, function calls, or unknown names are shown literally. Default: false, so dollar signs are always literal."
+    });
+
+    let assets_prop = serde_json::json!({
+        "type": "object",
+        "additionalProperties": { "type": "string" },
+        "description": "Optional. Images keyed by name as base64 (or data: URLs) in PNG, JPEG, GIF, WebP, or SVG format. Images that fail to decode are skipped with a warning instead of failing generation."
+    });
+
+    let formats_prop = serde_json::json!({
+        "type": "array",
+        "items": { "type": "string", "enum": ["pdf", "png"] },
+        "description": "Optional. Return these formats inline as base64 in 'outputs' (e.g., ['png', 'pdf'] for a preview plus a download) instead of saving or storing a PDF. The document is compiled once for all formats; 'png' renders the first page."
+    });
+
+    let treat_warnings_as_errors_prop = serde_json::json!({
+        "type": "boolean",
+        "description": "Optional. Fail generation if the Typst compiler reports any warning (e.g. an unknown font), returning the warnings as an error. Default: false."
+    });
+
+    let debug_prop = serde_json::json!({
+        "type": "boolean",
+        "description": "Optional. If compilation fails, include the generated Typst source around the failing line in 'source_excerpt', for bug reports. The excerpt contains document content, so it is off by default. Default: false."
+    });
+
+    let ttl_seconds_prop = serde_json::json!({
+        "type": "integer",
+        "minimum": 1,
+        "description": "Optional. How long the download URL stays valid, in seconds (HTTP mode only). Defaults to one hour; longer requests are capped at the server's maximum (24 hours unless configured)."
+    });
+
+    let deterministic_id_prop = serde_json::json!({
+        "type": "boolean",
+        "description": "Optional. Store the PDF under an ID derived from its content instead of a random one (HTTP mode only), so generating the same document again returns the same download URL. Default: false."
+    });
+
+    let thumbnail_prop = serde_json::json!({
+        "type": "boolean",
+        "description": "Optional. Also return a small PNG of the first page (200 px wide) as base64 in 'thumbnail_base64', rendered from the same compilation as the PDF. Default: false."
+    });
+
+    let mut compress_prop = serde_json::Map::new();
+    compress_prop.insert("type".to_string(), Value::String("boolean".to_string()));
+    compress_prop.insert(
+        "description".to_string(),
+        Value::String("Optional. Produce a smaller PDF by scaling PNG and JPEG images (assets and work logos) down to at most 800 px on their longest side and re-encoding them. Has no effect on documents without images. Default: false.".to_string()),
+    );
+
+    let mut source_url_prop = serde_json::Map::new();
+    source_url_prop.insert("type".to_string(), Value::String("string".to_string()));
+    source_url_prop.insert(
+        "description".to_string(),
+        Value::String("Optional. Public http(s) URL of a resume JSON document to fetch instead of passing 'resume' inline. Private/internal hosts are rejected; size and time limits apply.".to_string()),
     );
 
     let mut generate_resume_properties = serde_json::Map::new();
-    generate_resume_properties.insert("resume".to_string(), Value::Object(resume_prop));
+    generate_resume_properties.insert("resume".to_string(), resume_prop.clone());
+    generate_resume_properties.insert("source_url".to_string(), Value::Object(source_url_prop));
+    generate_resume_properties.insert("content".to_string(), content_prop);
+    generate_resume_properties.insert("format".to_string(), content_format_prop);
     generate_resume_properties.insert("filename".to_string(), Value::Object(filename_prop.clone()));
+    generate_resume_properties.insert("icons".to_string(), Value::Object(icons_prop));
+    generate_resume_properties.insert("icon_style".to_string(), icon_style_prop);
+    generate_resume_properties.insert("page_breaks".to_string(), Value::Object(page_breaks_prop));
+    generate_resume_properties.insert("footer_timestamp".to_string(), Value::Object(footer_timestamp_prop));
+    generate_resume_properties.insert("page_numbers".to_string(), page_numbers_prop);
+    generate_resume_properties.insert("style_overrides".to_string(), style_overrides_prop);
+    generate_resume_properties.insert("summary_placement".to_string(), summary_placement_prop);
+    generate_resume_properties.insert("summary_style".to_string(), summary_style_prop);
+    generate_resume_properties.insert("skills_style".to_string(), skills_style_prop);
+    generate_resume_properties.insert("contact_style".to_string(), contact_style_prop);
+    generate_resume_properties.insert("language_style".to_string(), language_style_prop);
+    generate_resume_properties.insert("divider_style".to_string(), divider_style_prop);
+    generate_resume_properties.insert("density".to_string(), density_prop);
+    generate_resume_properties.insert("section_colors".to_string(), section_colors_prop);
+    generate_resume_properties.insert("emphasis".to_string(), emphasis_prop);
+    generate_resume_properties.insert("highlight_keywords".to_string(), highlight_keywords_prop);
+    generate_resume_properties.insert("redact_contact".to_string(), Value::Object(redact_contact_prop));
+    generate_resume_properties.insert("max_highlights_per_entry".to_string(), max_highlights_prop);
+    generate_resume_properties.insert("publication_math".to_string(), publication_math_prop);
+    generate_resume_properties.insert("dedupe_skills".to_string(), dedupe_skills_prop);
+    generate_resume_properties.insert("combine_recognition".to_string(), combine_recognition_prop);
+    generate_resume_properties.insert("compress".to_string(), Value::Object(compress_prop));
+    generate_resume_properties.insert("treat_warnings_as_errors".to_string(), treat_warnings_as_errors_prop.clone());
+    generate_resume_properties.insert("assets".to_string(), assets_prop);
+    generate_resume_properties.insert("formats".to_string(), formats_prop.clone());
+    generate_resume_properties.insert("thumbnail".to_string(), thumbnail_prop.clone());
+    generate_resume_properties.insert("ttl_seconds".to_string(), ttl_seconds_prop.clone());
+    generate_resume_properties.insert("deterministic_id".to_string(), deterministic_id_prop.clone());
+    generate_resume_properties.insert("debug".to_string(), debug_prop.clone());
+
+    // Schema for compare_resumes: the generate_resume options, applied to both resumes
+    let mut compare_resumes_properties = generate_resume_properties.clone();
+    for key in ["resume", "source_url", "content", "format"] {
+        compare_resumes_properties.remove(key);
+    }
+    for (key, position) in [("left", "left-hand"), ("right", "right-hand")] {
+        let mut side_prop = resume_prop.clone();
+        side_prop["description"] = Value::String(format!(
+            "The resume shown in the {} column, in the same structure as generate_resume's 'resume'.",
+            position
+        ));
+        compare_resumes_properties.insert(key.to_string(), side_prop);
+    }
+    compare_resumes_properties.insert(
+        "filename".to_string(),
+        serde_json::json!({
+            "type": "string",
+            "description": "Optional filename for the comparison PDF. Path separators and illegal characters are replaced. Default: 'Resume_Comparison.pdf'."
+        }),
+    );
+
+    let mut compare_resumes_schema = serde_json::Map::new();
+    compare_resumes_schema.insert("type".to_string(), Value::String("object".to_string()));
+    compare_resumes_schema.insert("$defs".to_string(), resume_defs.clone());
+    compare_resumes_schema.insert("properties".to_string(), Value::Object(compare_resumes_properties));
+    compare_resumes_schema.insert(
+        "required".to_string(),
+        Value::Array(vec![
+            Value::String("left".to_string()),
+            Value::String("right".to_string()),
+        ]),
+    );
 
+    // One of 'resume', 'source_url', or 'content' must be provided, so none is listed as required
     let mut generate_resume_schema = serde_json::Map::new();
     generate_resume_schema.insert("type".to_string(), Value::String("object".to_string()));
+    generate_resume_schema.insert("$defs".to_string(), resume_defs.clone());
     generate_resume_schema.insert("properties".to_string(), Value::Object(generate_resume_properties));
-    generate_resume_schema.insert(
-        "required".to_string(),
-        Value::Array(vec![Value::String("resume".to_string())]),
-    );
 
     let generate_resume_schema_arc = Arc::new(generate_resume_schema);
 
+    // Schema for preview_section
+    let mut preview_section_properties = serde_json::Map::new();
+    preview_section_properties.insert("resume".to_string(), resume_prop);
+    preview_section_properties.insert(
+        "section".to_string(),
+        serde_json::json!({
+            "type": "string",
+            "description": format!(
+                "Section to render: one of {}, or the title of a custom section.",
+                SECTION_NAMES.join(", ")
+            )
+        }),
+    );
+    preview_section_properties.insert(
+        "format".to_string(),
+        serde_json::json!({
+            "type": "string",
+            "enum": ["png", "pdf"],
+            "description": "Optional. Output format. Default: png."
+        }),
+    );
+
+    let mut preview_section_schema = serde_json::Map::new();
+    preview_section_schema.insert("type".to_string(), Value::String("object".to_string()));
+    preview_section_schema.insert("$defs".to_string(), resume_defs);
+    preview_section_schema.insert("properties".to_string(), Value::Object(preview_section_properties));
+    preview_section_schema.insert(
+        "required".to_string(),
+        Value::Array(vec![
+            Value::String("resume".to_string()),
+            Value::String("section".to_string()),
+        ]),
+    );
+
     let get_resume_schema_tool = Tool::new(
         GET_RESUME_SCHEMA_TOOL,
         "Returns the complete JSON Schema for resume documents. Use this to understand the exact structure, required fields, and data types expected by validate_resume and generate_resume.",
@@ -220,30 +736,60 @@ pub fn list_tools() -> Vec<Tool> {
     let validate_resume_tool = Tool::new(
         VALIDATE_RESUME_TOOL,
         "Validates a resume JSON payload against the schema without generating a document. Returns validation errors with paths if invalid.",
-        validate_resume_schema_arc,
+        validate_resume_schema_arc.clone(),
+    );
+
+    let validate_resume_batch_tool = Tool::new(
+        VALIDATE_RESUME_BATCH_TOOL,
+        "Validates several resume JSON payloads in one call. Returns one result per payload, tagged with its index; an invalid payload does not affect the others. Set 'failures_only' to return just the invalid payloads. Useful for cheaply pre-checking many resumes before generating.",
+        validate_resume_batch_schema_arc,
     );
 
     let generate_resume_tool = Tool::new(
         GENERATE_RESUME_TOOL,
         "Generates a professionally formatted PDF resume from a JSON payload. Returns file path or download URL depending on the environment. RECOMMENDED: Use 'validate_resume' before generating.",
-        generate_resume_schema_arc,
+        generate_resume_schema_arc.clone(),
+    );
+
+    let preview_section_tool = Tool::new(
+        PREVIEW_SECTION_TOOL,
+        "Renders a single resume section (no header or footer) to PNG or PDF for fast live previews while editing. Returns the base64-encoded file in 'outputs'. Much cheaper than generating the full document.",
+        Arc::new(preview_section_schema),
+    );
+
+    let export_resume_text_tool = Tool::new(
+        EXPORT_RESUME_TEXT_TOOL,
+        "Exports a resume as structured plain text (no PDF), with sections in the same order as the generated document. Useful for ATS systems or application forms that ask for pasted text. Invalid payloads return validation errors instead.",
+        validate_resume_schema_arc.clone(),
+    );
+
+    let export_resume_latex_tool = Tool::new(
+        EXPORT_RESUME_LATEX_TOOL,
+        "Exports a resume as a standalone LaTeX document (moderncv class) instead of a PDF, with sections in the same order as the generated document. Useful in academic settings where CVs are maintained in LaTeX. Invalid payloads return validation errors instead.",
+        validate_resume_schema_arc.clone(),
+    );
+
+    let compare_resumes_tool = Tool::new(
+        COMPARE_RESUMES_TOOL,
+        "Renders two resumes side by side in a single landscape PDF, page 1 of each on the first page, page 2 of each on the second, and so on. Useful for comparing candidates or two versions of the same resume. Takes the same render and delivery options as generate_resume, applied to both. Validation errors are reported under 'left.' or 'right.'.",
+        Arc::new(compare_resumes_schema),
     );
 
     // ========== COVER LETTER TOOLS ==========
 
-    // Schema for validate_cover_letter
-    let mut cover_letter_prop = serde_json::Map::new();
-    cover_letter_prop.insert("type".to_string(), Value::String("object".to_string()));
-    cover_letter_prop.insert(
-        "description".to_string(),
-        Value::String("The cover letter JSON payload. Use 'get_cover_letter_schema' tool to see the full schema structure.".to_string()),
+    // Schema for validate_cover_letter (field-level structure generated from the CoverLetter type)
+    let (cover_letter_prop, cover_letter_defs) = document_schema_prop(
+        schemars::schema_for!(CoverLetter),
+        "The cover letter JSON payload. Use 'get_cover_letter_schema' tool to see the full schema structure.",
     );
 
     let mut validate_cover_letter_properties = serde_json::Map::new();
-    validate_cover_letter_properties.insert("cover_letter".to_string(), Value::Object(cover_letter_prop.clone()));
+    validate_cover_letter_properties.insert("cover_letter".to_string(), cover_letter_prop.clone());
+    validate_cover_letter_properties.insert("strict".to_string(), strict_prop);
 
     let mut validate_cover_letter_schema = serde_json::Map::new();
     validate_cover_letter_schema.insert("type".to_string(), Value::String("object".to_string()));
+    validate_cover_letter_schema.insert("$defs".to_string(), cover_letter_defs.clone());
     validate_cover_letter_schema.insert("properties".to_string(), Value::Object(validate_cover_letter_properties));
     validate_cover_letter_schema.insert(
         "required".to_string(),
@@ -254,11 +800,25 @@ pub fn list_tools() -> Vec<Tool> {
 
     // Schema for generate_cover_letter
     let mut generate_cover_letter_properties = serde_json::Map::new();
-    generate_cover_letter_properties.insert("cover_letter".to_string(), Value::Object(cover_letter_prop));
+    generate_cover_letter_properties.insert("cover_letter".to_string(), cover_letter_prop);
     generate_cover_letter_properties.insert("filename".to_string(), Value::Object(filename_prop));
+    generate_cover_letter_properties.insert("treat_warnings_as_errors".to_string(), treat_warnings_as_errors_prop);
+    generate_cover_letter_properties.insert("formats".to_string(), formats_prop);
+    generate_cover_letter_properties.insert("thumbnail".to_string(), thumbnail_prop);
+    generate_cover_letter_properties.insert("ttl_seconds".to_string(), ttl_seconds_prop);
+    generate_cover_letter_properties.insert("deterministic_id".to_string(), deterministic_id_prop);
+    generate_cover_letter_properties.insert("debug".to_string(), debug_prop);
+    generate_cover_letter_properties.insert(
+        "signature_space".to_string(),
+        serde_json::json!({
+            "type": "boolean",
+            "description": "Optional. Leave a blank, ruled space above the typed name for a signature applied later (e.g. an e-signature). Default: false."
+        }),
+    );
 
     let mut generate_cover_letter_schema = serde_json::Map::new();
     generate_cover_letter_schema.insert("type".to_string(), Value::String("object".to_string()));
+    generate_cover_letter_schema.insert("$defs".to_string(), cover_letter_defs);
     generate_cover_letter_schema.insert("properties".to_string(), Value::Object(generate_cover_letter_properties));
     generate_cover_letter_schema.insert(
         "required".to_string(),
@@ -276,7 +836,7 @@ pub fn list_tools() -> Vec<Tool> {
     let get_cover_letter_best_practices_tool = Tool::new(
         GET_COVER_LETTER_BEST_PRACTICES_TOOL,
         "Returns comprehensive best practices and guidelines for writing compelling cover letters. Call this BEFORE gathering user information to understand what makes a great cover letter.",
-        empty_schema,
+        empty_schema.clone(),
     );
 
     let validate_cover_letter_tool = Tool::new(
@@ -291,6 +851,95 @@ pub fn list_tools() -> Vec<Tool> {
         generate_cover_letter_schema_arc,
     );
 
+    // ========== DIAGNOSTIC TOOLS ==========
+
+    // Schema for check_compatibility
+    let mut document_type_prop = serde_json::Map::new();
+    document_type_prop.insert("type".to_string(), Value::String("string".to_string()));
+    document_type_prop.insert(
+        "enum".to_string(),
+        Value::Array(vec![
+            Value::String("resume".to_string()),
+            Value::String("cover_letter".to_string()),
+        ]),
+    );
+    document_type_prop.insert(
+        "description".to_string(),
+        Value::String("The document type to check the payload against.".to_string()),
+    );
+
+    let mut document_prop = serde_json::Map::new();
+    document_prop.insert("type".to_string(), Value::String("object".to_string()));
+    document_prop.insert(
+        "description".to_string(),
+        Value::String("An arbitrary JSON object to compare against the document schema.".to_string()),
+    );
+
+    let mut check_compatibility_properties = serde_json::Map::new();
+    check_compatibility_properties.insert("document_type".to_string(), Value::Object(document_type_prop));
+    check_compatibility_properties.insert("document".to_string(), Value::Object(document_prop));
+
+    let mut check_compatibility_schema = serde_json::Map::new();
+    check_compatibility_schema.insert("type".to_string(), Value::String("object".to_string()));
+    check_compatibility_schema.insert("properties".to_string(), Value::Object(check_compatibility_properties));
+    check_compatibility_schema.insert(
+        "required".to_string(),
+        Value::Array(vec![
+            Value::String("document_type".to_string()),
+            Value::String("document".to_string()),
+        ]),
+    );
+
+    let mut typescript_properties = serde_json::Map::new();
+    typescript_properties.insert(
+        "document_type".to_string(),
+        serde_json::json!({
+            "type": "string",
+            "enum": ["resume", "cover_letter"],
+            "description": "Optional. The document type to generate declarations for. Default: resume."
+        }),
+    );
+
+    let mut typescript_schema = serde_json::Map::new();
+    typescript_schema.insert("type".to_string(), Value::String("object".to_string()));
+    typescript_schema.insert("properties".to_string(), Value::Object(typescript_properties));
+
+    let get_typescript_types_tool = Tool::new(
+        GET_TYPESCRIPT_TYPES_TOOL,
+        "Returns TypeScript declarations (interfaces and type aliases) generated from a document schema, for frontends that build payloads in TypeScript. Optional fields are marked with '?', and field descriptions are included as doc comments.",
+        Arc::new(typescript_schema),
+    );
+
+    let list_style_options_tool = Tool::new(
+        LIST_STYLE_OPTIONS_TOOL,
+        "Lists the accepted values and defaults of every generate_resume style option (icon_style, summary_placement, summary_style, skills_style, contact_style, language_style, divider_style, density, emphasis, and the style_overrides fields), for building selection UIs without guessing.",
+        empty_schema,
+    );
+
+    let check_compatibility_tool = Tool::new(
+        CHECK_COMPATIBILITY_TOOL,
+        "Compares an arbitrary JSON object with the schema of a document type and reports which fields matched, which are unknown (and would be ignored), and which required fields are missing. Never fails on partial input, so it is useful for migrating data from other resume formats.",
+        Arc::new(check_compatibility_schema),
+    );
+
+    let ats_score_tool = Tool::new(
+        ATS_SCORE_TOOL,
+        "Scores a resume from 0 to 100 for ATS-friendliness: quantified highlights, action-verb starts, contact completeness, and section coverage. Returns a per-category breakdown and itemized suggestions. Invalid payloads return validation errors instead.",
+        validate_resume_schema_arc.clone(),
+    );
+
+    let resume_completeness_tool = Tool::new(
+        RESUME_COMPLETENESS_TOOL,
+        "Measures how complete a resume is as a percentage, weighting recommended content (summary, contact details, experience, education, skills) above optional sections. Returns the percentage, missing recommended items (most valuable first), and empty optional sections. Invalid payloads return validation errors instead.",
+        validate_resume_schema_arc,
+    );
+
+    let verify_resume_text_tool = Tool::new(
+        VERIFY_RESUME_TEXT_TOOL,
+        "Generates the resume exactly as 'generate_resume' would with the same input, then extracts the text from the PDF and returns it in 'text' instead of the file. Use it to confirm the document contains the expected content (names, dates, keywords). Nothing is stored or written.",
+        generate_resume_schema_arc,
+    );
+
     vec![
         // Document type discovery (call these first!)
         get_document_types_tool,
@@ -299,15 +948,171 @@ pub fn list_tools() -> Vec<Tool> {
         get_resume_schema_tool,
         get_resume_best_practices_tool,
         validate_resume_tool,
+        validate_resume_batch_tool,
         generate_resume_tool,
+        preview_section_tool,
+        export_resume_text_tool,
+        export_resume_latex_tool,
+        compare_resumes_tool,
         // Cover letter tools
         get_cover_letter_schema_tool,
         get_cover_letter_best_practices_tool,
         validate_cover_letter_tool,
         generate_cover_letter_tool,
+        // Diagnostics
+        check_compatibility_tool,
+        ats_score_tool,
+        resume_completeness_tool,
+        verify_resume_text_tool,
+        // Integration
+        get_typescript_types_tool,
+        list_style_options_tool,
     ]
 }
 
+/// Input for the preview_section tool
+#[derive(Debug, Deserialize)]
+pub struct PreviewSectionInput {
+    pub resume: Value,
+    /// Standard section name or custom section title
+    pub section: String,
+    #[serde(default)]
+    pub format: Option<OutputFormat>,
+    #[serde(flatten)]
+    pub options: RenderOptions,
+}
+
+/// Renders one section of a resume to a single-format preview
+///
+/// The preview is held to the same page limit as generated documents.
+pub async fn preview_section(input: Value, context: &ToolContext) -> GenerationResult {
+    let parsed_input: PreviewSectionInput = match serde_json::from_value(input) {
+        Ok(v) => v,
+        Err(e) => {
+            return GenerationResult::Error {
+                message: format!(
+                    "Invalid tool input: expected object with 'resume' and 'section' fields. {}",
+                    e
+                ),
+                validation_errors: None,
+                source_excerpt: None,
+            };
+        }
+    };
+
+    let option_errors = validate_render_options(&parsed_input.options);
+    if !option_errors.is_empty() {
+        return GenerationResult::Error {
+            message: "Invalid render options".to_string(),
+            validation_errors: Some(option_errors),
+            source_excerpt: None,
+        };
+    }
+
+    let validation_input = serde_json::json!({ "resume": parsed_input.resume });
+    let (mut resume, warnings) = match validate_resume(validation_input) {
+        ValidationResult::Valid {
+            resume, warnings, ..
+        } => (resume, warnings),
+        ValidationResult::Invalid { errors } => {
+            return GenerationResult::Error {
+                message: "Validation failed".to_string(),
+                validation_errors: Some(errors),
+                source_excerpt: None,
+            };
+        }
+    };
+
+    let section = parsed_input.section.as_str();
+    let known = SECTION_NAMES.contains(&section)
+        || resume.custom_sections.iter().any(|c| c.title == section);
+    if !known {
+        return GenerationResult::Error {
+            message: "Unknown section".to_string(),
+            validation_errors: Some(vec![ValidationError::new(
+                "section",
+                format!(
+                    "Unknown section '{}'. Valid sections: {}, or a custom section title.",
+                    section,
+                    SECTION_NAMES.join(", ")
+                ),
+            )]),
+            source_excerpt: None,
+        };
+    }
+
+    // Previews take no assets, so logos are left out
+    drop_logos(&mut resume);
+    let source = match transform_resume_section(&resume, &parsed_input.options, section) {
+        Ok(s) => s,
+        Err(e) => {
+            return GenerationResult::Error {
+                message: format!("Failed to transform resume to Typst: {}", e),
+                validation_errors: None,
+                source_excerpt: None,
+            };
+        }
+    };
+
+    let format = parsed_input.format.unwrap_or(OutputFormat::Png);
+    let world = DocgenWorld::new(source);
+    let export = ExportOptions {
+        max_pages: Some(context.max_pages),
+        ..Default::default()
+    };
+    let compiled = compile_pool()
+        .run(move || compile_world_to_formats(&world, &[format], &export))
+        .await;
+    match compiled {
+        Ok(bytes_by_format) => GenerationResult::Success {
+            file_path: None,
+            download_url: None,
+            message: format!(
+                "Preview of the '{}' section rendered as {}. The base64-encoded file is in 'outputs'.",
+                section,
+                format.as_str()
+            ),
+            warnings,
+            outputs: bytes_by_format
+                .into_iter()
+                .map(|(format, bytes)| (format.as_str().to_string(), BASE64.encode(bytes)))
+                .collect(),
+            thumbnail_base64: None,
+        },
+        Err(diags) => {
+            let msg = diags
+                .iter()
+                .map(|d| format!("{:?}: {}", d.severity, d.message))
+                .collect::<Vec<_>>()
+                .join("\n");
+            GenerationResult::Error {
+                message: format!("Typst compilation failed:\n{}", msg),
+                validation_errors: None,
+                source_excerpt: None,
+            }
+        }
+    }
+}
+
+/// Splits a generated document schema into a tool input property and its definitions
+///
+/// The definitions must be hoisted to the root of the tool's input schema so
+/// that the `#/$defs/...` references inside the property still resolve.
+fn document_schema_prop(schema: schemars::Schema, description: &str) -> (Value, Value) {
+    let mut prop = match serde_json::to_value(schema).expect("Failed to serialize schema") {
+        Value::Object(map) => map,
+        _ => serde_json::Map::new(),
+    };
+    prop.remove("$schema");
+    prop.remove("title");
+    let defs = prop
+        .remove("$defs")
+        .unwrap_or_else(|| Value::Object(serde_json::Map::new()));
+    prop.insert("description".to_string(), Value::String(description.to_string()));
+
+    (Value::Object(prop), defs)
+}
+
 /// Returns the JSON schema for resume documents
 ///
 /// This is a convenience tool that wraps the 'docgen://schemas/resume' resource,
@@ -357,20 +1162,73 @@ pub fn get_resume_best_practices() -> Value {
 /// Input for the validate_resume tool
 #[derive(Debug, Deserialize)]
 pub struct ValidateResumeInput {
-    pub resume: Value,
+    #[serde(default)]
+    pub resume: Option<Value>,
+    /// The resume as YAML, TOML, or JSON text instead of an inline object
+    #[serde(default)]
+    pub content: Option<String>,
+    /// Format of `content`
+    #[serde(default)]
+    pub format: Option<InputFormat>,
+    /// Also check the payload against the published JSON Schema
+    #[serde(default)]
+    pub strict: bool,
+    /// Compile a valid resume once to report `estimated_pages`
+    #[serde(default)]
+    pub estimate_pages: bool,
+    /// Also return the resume in canonical form as `normalized`
+    #[serde(default)]
+    pub normalize: bool,
 }
 
 /// Input for the generate_resume tool
 #[derive(Debug, Deserialize)]
 pub struct GenerateResumeInput {
-    pub resume: Value,
+    #[serde(default)]
+    pub resume: Option<Value>,
+    /// URL of a resume JSON document to fetch instead of an inline payload
+    #[serde(default)]
+    pub source_url: Option<String>,
+    /// The resume as YAML, TOML, or JSON text instead of an inline object
+    #[serde(default)]
+    pub content: Option<String>,
+    /// Format of `content`
+    #[serde(default)]
+    pub format: Option<InputFormat>,
     pub filename: Option<String>,
+    /// Produce a smaller PDF by downscaling and re-encoding raster images
+    #[serde(default)]
+    pub compress: bool,
+    /// Fail generation if the Typst compiler reports any warning
+    #[serde(default)]
+    pub treat_warnings_as_errors: bool,
+    /// Base64-encoded images keyed by name, readable by the template as `assets/<name>`
+    #[serde(default)]
+    pub assets: BTreeMap<String, String>,
+    /// Return these formats inline (base64) instead of saving or storing a PDF
+    #[serde(default)]
+    pub formats: Vec<OutputFormat>,
+    /// Also return a small PNG of the first page as `thumbnail_base64`
+    #[serde(default)]
+    pub thumbnail: bool,
+    /// How long the download link stays valid, in seconds (HTTP mode only)
+    #[serde(default)]
+    pub ttl_seconds: Option<u64>,
+    /// Store the file under an ID derived from its content (HTTP mode only)
+    #[serde(default)]
+    pub deterministic_id: bool,
+    /// Include the generated source around the error when compilation fails
+    #[serde(default)]
+    pub debug: bool,
+    #[serde(flatten)]
+    pub options: RenderOptions,
 }
 
 /// Validates a resume JSON payload
 ///
-/// Uses serde deserialization to validate the payload against the Resume type.
-/// Returns structured validation errors if the payload is invalid.
+/// Uses serde deserialization to validate the payload against the Resume type,
+/// followed by semantic checks. Returns structured validation errors if the
+/// payload is invalid, and non-blocking warnings alongside a valid result.
 pub fn validate_resume(input: Value) -> ValidationResult {
     // First, parse the tool input wrapper
     let parsed_input: ValidateResumeInput = match serde_json::from_value(input.clone()) {
@@ -388,106 +1246,557 @@ pub fn validate_resume(input: Value) -> ValidationResult {
         }
     };
 
-    // Then validate the resume payload itself
-    match serde_json::from_value::<Resume>(parsed_input.resume) {
-        Ok(resume) => ValidationResult::Valid {
-            resume: Box::new(resume),
-        },
-        Err(e) => ValidationResult::Invalid {
-            errors: parse_serde_error(&e),
+    // Resolve the payload (inline object or text content)
+    let mut resume_value = match (parsed_input.resume, &parsed_input.content) {
+        // Some clients send the resume as JSON-encoded text instead of an object
+        (Some(Value::String(text)), None) => match parse_json_strict(&text) {
+            Ok(value) => value,
+            Err(e) => {
+                return ValidationResult::Invalid {
+                    errors: parse_serde_error(&e, &Value::Null),
+                };
+            }
         },
-    }
-}
-
-/// Generates a PDF resume from a JSON payload
-///
-/// In stdio mode: saves the PDF to a local file
-/// In HTTP mode: stores the PDF in temporary storage and returns a download URL
-pub async fn generate_resume(input: Value, context: &ToolContext) -> GenerationResult {
-    // 0. Parse input to get resume and optional filename
-    let parsed_input: GenerateResumeInput = match serde_json::from_value(input.clone()) {
+        (Some(resume), None) => resume,
+        (None, Some(content)) => {
+            match parse_content(parsed_input.format.unwrap_or_default(), content) {
+                Ok(value) => value,
+                Err(e) => {
+                    return ValidationResult::Invalid {
+                        errors: vec![ValidationError::new("content", e)],
+                    };
+                }
+            }
+        }
+        (Some(_), Some(_)) => {
+            return ValidationResult::Invalid {
+                errors: vec![ValidationError::new(
+                    "",
+                    "Invalid tool input: provide either 'resume' or 'content', not both",
+                )],
+            };
+        }
+        (None, None) => {
+            return ValidationResult::Invalid {
+                errors: vec![ValidationError::new(
+                    "",
+                    "Invalid tool input: expected object with 'resume' field (or 'content')",
+                )],
+            };
+        }
+    };
+
+    // Pasted control characters are removed up front and reported as warnings
+    let sanitized = strip_control_characters(&mut resume_value);
+
+    // Strict mode enforces the published schema (formats included) and reports every violation
+    if parsed_input.strict {
+        // The schema lists only canonical field names, so alternate names
+        // serde accepts (e.g. `jobTitle`) are renamed first
+        let schema = schemars::schema_for!(Resume).to_value();
+        canonicalize_aliases(&schema, &schema, &mut resume_value);
+        let errors = resume_schema_errors(&resume_value);
+        if !errors.is_empty() {
+            return ValidationResult::Invalid { errors };
+        }
+    }
+
+    // Then validate the resume payload itself
+    match Resume::deserialize(&resume_value) {
+        Ok(mut resume) => {
+            // Normalize free-text values so the output renders consistently
+            resume.normalize_profiles();
+
+            // Finally run semantic checks that serde cannot express
+            let report = check_resume(&resume);
+            if report.errors.is_empty() {
+                let mut warnings = sanitized;
+                warnings.extend(report.warnings);
+                let normalized = parsed_input.normalize.then(|| {
+                    let mut normalized = resume.clone();
+                    normalized.normalize();
+                    Box::new(normalized)
+                });
+                ValidationResult::Valid {
+                    resume: Box::new(resume),
+                    warnings,
+                    estimated_pages: None,
+                    normalized,
+                }
+            } else {
+                ValidationResult::Invalid {
+                    errors: report.errors,
+                }
+            }
+        }
+        Err(e) => ValidationResult::Invalid {
+            errors: parse_serde_error(&e, &resume_value),
+        },
+    }
+}
+
+/// Validates a resume and, with `estimate_pages`, compiles it to report its length
+///
+/// The compile uses default render options and runs on the compile pool, so
+/// plain validation stays as fast as `validate_resume`. A resume over the page
+/// limit is not laid out in full, so it gets no estimate.
+pub async fn validate_resume_with_estimate(
+    input: Value,
+    context: &ToolContext,
+) -> ValidationResult {
+    let estimate = serde_json::from_value::<ValidateResumeInput>(input.clone())
+        .is_ok_and(|parsed| parsed.estimate_pages);
+
+    let mut result = validate_resume(input);
+    if estimate
+        && let ValidationResult::Valid {
+            resume,
+            estimated_pages,
+            ..
+        } = &mut result
+    {
+        *estimated_pages = estimate_page_count(resume, context.max_pages).await;
+    }
+    result
+}
+
+/// Number of pages the resume renders to with default options
+async fn estimate_page_count(resume: &Resume, max_pages: usize) -> Option<usize> {
+    // Logos may name assets that validation doesn't receive; they sit inline
+    // with the company name, so leaving them out doesn't change the page count
+    let mut resume = resume.clone();
+    drop_logos(&mut resume);
+    let source = transform_resume(&resume, &RenderOptions::default()).ok()?;
+    compile_pool()
+        .run(move || compile_document_with_page_limit(source, max_pages))
+        .await
+        .ok()
+        .map(|document| document.pages.len())
+}
+
+/// Input for the validate_resume_batch tool
+#[derive(Debug, Deserialize)]
+pub struct ValidateResumeBatchInput {
+    pub resumes: Vec<Value>,
+    /// Report only the payloads that failed validation
+    #[serde(default)]
+    pub failures_only: bool,
+}
+
+/// Validates a batch of resume payloads
+///
+/// Each payload goes through the same checks as `validate_resume`, and its
+/// result is reported at its index regardless of how the others fared. With
+/// `failures_only`, valid payloads are left out of the results.
+pub fn validate_resume_batch(input: Value) -> Result<BatchValidationResult, String> {
+    let parsed_input: ValidateResumeBatchInput = serde_json::from_value(input).map_err(|e| {
+        format!(
+            "Invalid tool input: expected object with 'resumes' array. {}",
+            e
+        )
+    })?;
+
+    let results = parsed_input
+        .resumes
+        .into_iter()
+        .enumerate()
+        .map(|(index, resume)| BatchValidationItem {
+            index,
+            result: validate_resume(serde_json::json!({ "resume": resume })),
+        })
+        .filter(|item| {
+            !parsed_input.failures_only || matches!(item.result, ValidationResult::Invalid { .. })
+        })
+        .collect();
+
+    Ok(BatchValidationResult { results })
+}
+
+/// Prefix of the asset names inline logos are stored under; supplied asset
+/// names can't contain a `/`, so these never collide with them
+const INLINE_LOGO_PREFIX: &str = "logos/work-";
+
+/// Resolve each work entry's `logo` to an asset the template can load
+///
+/// A logo naming a supplied asset refers to it; anything else is decoded as
+/// an inline base64 image and added to `assets`. A logo that can't be used is
+/// dropped with a warning, so the entry renders without it.
+fn resolve_work_logos(
+    resume: &mut Resume,
+    supplied: &BTreeMap<String, String>,
+    assets: &mut AssetMap,
+    limits: &AssetLimits,
+) -> Vec<ValidationError> {
+    let mut warnings = Vec::new();
+
+    for (index, job) in resume.work.iter_mut().enumerate() {
+        let Some(logo) = job.logo.take() else {
+            continue;
+        };
+        let path = format!("work[{}].logo", index);
+
+        if assets.contains_key(&logo) {
+            job.logo = Some(logo);
+        } else if supplied.contains_key(&logo) {
+            warnings.push(ValidationError::new(
+                path,
+                format!("Logo was omitted: asset '{}' could not be used", logo),
+            ));
+        } else {
+            match decode_image(&logo) {
+                Ok(bytes) if bytes.len() > limits.max_asset_bytes => {
+                    warnings.push(ValidationError::new(
+                        path,
+                        "Logo was omitted: the inline image is over the per-asset size limit",
+                    ));
+                }
+                Ok(bytes) => {
+                    let name = format!("{}{}", INLINE_LOGO_PREFIX, index);
+                    assets.insert(name.clone(), bytes);
+                    job.logo = Some(name);
+                }
+                Err(reason) => warnings.push(ValidationError::new(
+                    path,
+                    format!(
+                        "Logo was omitted: it is not the name of a supplied asset, and it is not a usable inline image ({})",
+                        reason
+                    ),
+                )),
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Remove work logos, for renders that have no assets to load them from
+fn drop_logos(resume: &mut Resume) {
+    for job in &mut resume.work {
+        job.logo = None;
+    }
+}
+
+/// Generates a PDF resume from a JSON payload
+///
+/// In stdio mode: saves the PDF to a local file
+/// In HTTP mode: stores the PDF in temporary storage and returns a download URL
+pub async fn generate_resume(input: Value, context: &ToolContext) -> GenerationResult {
+    match prepare_resume(input, context).await {
+        Ok(prepared) => deliver_document(prepared, context).await,
+        Err(error) => *error,
+    }
+}
+
+/// Parse, validate, and transform a generate_resume input
+async fn prepare_resume(input: Value, context: &ToolContext) -> Result<PreparedDocument, Box<GenerationResult>> {
+    // 0. Parse input to get resume and optional filename
+    let parsed_input: GenerateResumeInput = match serde_json::from_value(input.clone()) {
         Ok(v) => v,
         Err(e) => {
-            return GenerationResult::Error {
+            return Err(Box::new(GenerationResult::Error {
                 message: format!("Invalid tool input: expected object with 'resume' field. {}", e),
                 validation_errors: None,
-            };
+                source_excerpt: None,
+            }));
         }
     };
 
-    // 1. Validate
-    let validation_input = serde_json::json!({ "resume": parsed_input.resume });
+    let option_errors = validate_render_options(&parsed_input.options);
+    if !option_errors.is_empty() {
+        return Err(Box::new(GenerationResult::Error {
+            message: "Invalid render options".to_string(),
+            validation_errors: Some(option_errors),
+            source_excerpt: None,
+        }));
+    }
+
+    // 1. Resolve the payload (inline, parsed from content, or fetched from source_url)
+    let resume_value = match (parsed_input.resume, &parsed_input.source_url, &parsed_input.content) {
+        (Some(resume), None, None) => resume,
+        (None, None, Some(content)) => {
+            match parse_content(parsed_input.format.unwrap_or_default(), content) {
+                Ok(value) => value,
+                Err(e) => {
+                    return Err(Box::new(GenerationResult::Error {
+                        message: e,
+                        validation_errors: None,
+                        source_excerpt: None,
+                    }));
+                }
+            }
+        }
+        (None, Some(url), None) => match fetch_json(url, &context.fetch_policy).await {
+            Ok(value) => value,
+            Err(e) => {
+                return Err(Box::new(GenerationResult::Error {
+                    message: format!("Failed to fetch resume from source_url: {}", e),
+                    validation_errors: None,
+                    source_excerpt: None,
+                }));
+            }
+        },
+        (None, None, None) => {
+            return Err(Box::new(GenerationResult::Error {
+                message: "Invalid tool input: expected object with 'resume' field (or 'source_url' or 'content')".to_string(),
+                validation_errors: None,
+                source_excerpt: None,
+            }));
+        }
+        _ => {
+            return Err(Box::new(GenerationResult::Error {
+                message: "Invalid tool input: provide only one of 'resume', 'source_url', or 'content'".to_string(),
+                validation_errors: None,
+                source_excerpt: None,
+            }));
+        }
+    };
+
+    // 2. Validate
+    let validation_input = serde_json::json!({ "resume": resume_value });
     let validation_result = validate_resume(validation_input);
 
-    let resume = match validation_result {
-        ValidationResult::Valid { resume } => resume,
+    let (mut resume, mut warnings) = match validation_result {
+        ValidationResult::Valid {
+            resume, warnings, ..
+        } => (resume, warnings),
         ValidationResult::Invalid { errors } => {
-            return GenerationResult::Error {
+            return Err(Box::new(GenerationResult::Error {
                 message: "Validation failed".to_string(),
                 validation_errors: Some(errors),
-            };
+                source_excerpt: None,
+            }));
         }
     };
 
-    // 2. Transform
-    let source = match transform_resume(&resume) {
+    // Note entries whose highlights are cut by max_highlights_per_entry
+    if let Some(max) = parsed_input.options.highlight_limit() {
+        for (path, count) in resume.truncate_highlights(max) {
+            warnings.push(ValidationError::new(
+                path,
+                format!(
+                    "Only the first {} of {} highlights are rendered (max_highlights_per_entry)",
+                    max, count
+                ),
+            ));
+        }
+    }
+
+    // 3. Decode assets (path-like names and oversized assets reject the
+    //    request; assets that fail to decode are skipped with a warning)
+    let name_errors = check_asset_names(&parsed_input.assets);
+    if !name_errors.is_empty() {
+        return Err(Box::new(GenerationResult::Error {
+            message: "Invalid asset names".to_string(),
+            validation_errors: Some(
+                name_errors
+                    .into_iter()
+                    .map(|e| ValidationError::new(format!("assets.{}", e.name), e.message))
+                    .collect(),
+            ),
+            source_excerpt: None,
+        }));
+    }
+    let size_errors = context.asset_limits.check(&parsed_input.assets);
+    if !size_errors.is_empty() {
+        return Err(Box::new(GenerationResult::Error {
+            message: "Assets exceed the size limit".to_string(),
+            validation_errors: Some(
+                size_errors
+                    .into_iter()
+                    .map(|e| match e.name.as_str() {
+                        "" => ValidationError::new("assets", e.message),
+                        name => ValidationError::new(format!("assets.{}", name), e.message),
+                    })
+                    .collect(),
+            ),
+            source_excerpt: None,
+        }));
+    }
+    let (mut assets, asset_warnings) = decode_assets(&parsed_input.assets);
+    warnings.extend(
+        asset_warnings
+            .into_iter()
+            .map(|w| ValidationError::new(format!("assets.{}", w.name), w.message)),
+    );
+    warnings.extend(resolve_work_logos(
+        &mut resume,
+        &parsed_input.assets,
+        &mut assets,
+        &context.asset_limits,
+    ));
+    if parsed_input.compress {
+        compress_images(&mut assets);
+    }
+
+    // 4. Transform and set up the Typst world
+    let source = match transform_resume(&resume, &parsed_input.options) {
         Ok(s) => s,
         Err(e) => {
-            return GenerationResult::Error {
+            return Err(Box::new(GenerationResult::Error {
                 message: format!("Failed to transform resume to Typst: {}", e),
                 validation_errors: None,
-            };
+                source_excerpt: None,
+            }));
         }
     };
-
-    // 3. Compile
-    let pdf_bytes = match compile(source) {
-        Ok(bytes) => bytes,
-        Err(diags) => {
-            // Convert diagnostics to string
-            let msg = diags
-                .iter()
-                .map(|d| format!("{:?}: {}", d.severity, d.message))
-                .collect::<Vec<_>>()
-                .join("\n");
-            return GenerationResult::Error {
-                message: format!("Typst compilation failed:\n{}", msg),
-                validation_errors: None,
-            };
-        }
+    let world = DocgenWorld::new(source).with_assets(&assets);
+    let export = ExportOptions {
+        treat_warnings_as_errors: parsed_input.treat_warnings_as_errors,
+        max_pages: Some(context.max_pages),
     };
 
-    // 4. Generate filename (use provided or generate from name)
-    let filename = parsed_input.filename.unwrap_or_else(|| {
-        // Sanitize the name to create a safe filename
-        let name = &resume.basics.name;
-        let sanitized = name
-            .to_lowercase()
-            .replace(" ", "-")
-            .chars()
-            .filter(|c| c.is_alphanumeric() || *c == '-')
-            .collect::<String>();
-        format!("{}-resume.pdf", sanitized)
-    });
+    // 5. Generate filename (use provided, sanitized, or derive from name unless redacted)
+    let filename = parsed_input
+        .filename
+        .as_deref()
+        .and_then(sanitize_filename)
+        .unwrap_or_else(|| {
+            if parsed_input.options.redact_contact {
+                "Resume.pdf".to_string()
+            } else {
+                DocumentType::RESUME.default_filename(&resume.basics.name)
+            }
+        });
+
+    Ok(PreparedDocument {
+        document_type: &DocumentType::RESUME,
+        world,
+        export,
+        formats: parsed_input.formats,
+        filename,
+        warnings,
+        thumbnail: parsed_input.thumbnail,
+        ttl_seconds: parsed_input.ttl_seconds,
+        deterministic_id: parsed_input.deterministic_id,
+        debug: parsed_input.debug,
+    })
+}
+
+/// A validated document ready to compile, with how it should be delivered
+struct PreparedDocument {
+    /// Kind of document, for messages and the stored file's MIME type
+    document_type: &'static DocumentType,
+    world: DocgenWorld,
+    export: ExportOptions,
+    /// Formats to return inline; empty means store or save a PDF
+    formats: Vec<OutputFormat>,
+    filename: String,
+    warnings: Vec<ValidationError>,
+    /// Also render a first-page PNG thumbnail
+    thumbnail: bool,
+    /// Requested lifetime of the stored file, clamped by the storage
+    ttl_seconds: Option<u64>,
+    /// Store the file under a content-derived ID instead of a random one
+    deterministic_id: bool,
+    /// Attach a source excerpt to compile errors
+    debug: bool,
+}
+
+/// Compile a prepared document and deliver it the way the caller asked
+///
+/// Shared by every generate tool so all document types support the same
+/// delivery options: with `formats` the document is compiled once and returned
+/// inline as base64; otherwise the PDF is stored for download (HTTP mode) or
+/// written to a local file (stdio mode).
+async fn deliver_document(prepared: PreparedDocument, context: &ToolContext) -> GenerationResult {
+    let PreparedDocument {
+        document_type,
+        world,
+        export,
+        formats,
+        filename,
+        warnings,
+        thumbnail,
+        ttl_seconds,
+        deterministic_id,
+        debug,
+    } = prepared;
+
+    // Multiple formats: compile once, export each, and return them inline
+    if !formats.is_empty() {
+        return match compile_outputs(world, export, formats, thumbnail, debug).await {
+            Ok(compiled) => {
+                let outputs: BTreeMap<String, String> = compiled
+                    .outputs
+                    .iter()
+                    .map(|(format, bytes)| (format.as_str().to_string(), BASE64.encode(bytes)))
+                    .collect();
+                let message = format!(
+                    "{} successfully generated as {}. The base64-encoded files are in 'outputs'.",
+                    document_type.label,
+                    outputs.keys().cloned().collect::<Vec<_>>().join(", ")
+                );
+                GenerationResult::Success {
+                    file_path: None,
+                    download_url: None,
+                    message,
+                    warnings,
+                    outputs,
+                    thumbnail_base64: compiled.thumbnail.as_ref().map(|png| BASE64.encode(png)),
+                }
+            }
+            Err(error) => *error,
+        };
+    }
 
-    // 5. Handle output based on transport mode
+    // The thumbnail is rendered from the same compiled document as the PDF
+    let compiled =
+        match compile_outputs(world, export, vec![OutputFormat::Pdf], thumbnail, debug).await {
+            Ok(compiled) => compiled,
+            Err(error) => return *error,
+        };
+    let pdf_bytes = compiled
+        .outputs
+        .get(&OutputFormat::Pdf)
+        .cloned()
+        .unwrap_or_default();
+    let thumbnail_base64 = compiled.thumbnail.as_ref().map(|png| BASE64.encode(png));
+
+    // Handle output based on transport mode
     match (&context.file_storage, &context.base_url) {
         // HTTP mode: store in temporary storage and return download URL
         (Some(storage), Some(base_url)) => {
-            let file_id = storage.store(pdf_bytes, filename.clone()).await;
+            let ttl = storage.ttl_for(ttl_seconds.map(Duration::from_secs));
+            let session_id = context.session_id.clone();
+            let file_id = if deterministic_id {
+                storage
+                    .store_deterministic(
+                        pdf_bytes,
+                        filename.clone(),
+                        document_type.mime_type,
+                        session_id,
+                        ttl,
+                    )
+                    .await
+            } else {
+                storage
+                    .store_with_ttl(
+                        pdf_bytes,
+                        filename.clone(),
+                        document_type.mime_type,
+                        session_id,
+                        ttl,
+                    )
+                    .await
+            };
             let download_url = format!("{}/files/{}", base_url, file_id);
 
             GenerationResult::Success {
                 file_path: None,
                 download_url: Some(download_url.clone()),
                 message: format!(
-                    "Resume successfully generated. Download it from: {}\n\
+                    "{} successfully generated. Download it from: {}\n\
                      \n\
                      NOTE: You are likely running in a sandboxed environment and cannot access local files directly. \
                      Please provide this URL to the user so they can download the PDF. \
-                     This link will expire in 1 hour.",
-                    download_url
+                     This link will expire in {}.",
+                    document_type.label,
+                    download_url,
+                    describe_duration(ttl)
                 ),
+                warnings,
+                outputs: BTreeMap::new(),
+                thumbnail_base64,
             }
         }
         // Stdio mode: save to local file
@@ -497,39 +1806,466 @@ pub async fn generate_resume(input: Value, context: &ToolContext) -> GenerationR
                     file_path: Some(filename.clone()),
                     download_url: None,
                     message: format!(
-                        "Resume successfully generated and saved to '{}'\n\
+                        "{} successfully generated and saved to '{}'\n\
                          \n\
                          NOTE: If you are running in a sandboxed environment, you may not have direct access to this file. \
                          The file path is provided for reference, but the user should check their working directory.",
-                        filename
+                        document_type.label, filename
                     ),
+                    warnings,
+                    outputs: BTreeMap::new(),
+                    thumbnail_base64,
                 },
                 Err(e) => GenerationResult::Error {
                     message: format!("Failed to write PDF to file '{}': {}", filename, e),
                     validation_errors: None,
+                    source_excerpt: None,
                 },
             }
         }
     }
 }
 
+/// Compile a prepared world to `formats`, reusing a cached compile when possible
+///
+/// Only successful compiles are cached, so a failing document is compiled (and
+/// its diagnostics reported) every time.
+async fn compile_outputs(
+    world: DocgenWorld,
+    export: ExportOptions,
+    formats: Vec<OutputFormat>,
+    thumbnail: bool,
+    debug: bool,
+) -> Result<Arc<CompiledOutputs>, Box<GenerationResult>> {
+    let key = OutputKey::new(&world, &export, &formats, thumbnail);
+    if let Some(compiled) = output_cache().get(&key) {
+        return Ok(compiled);
+    }
+
+    let compiled = compile_pool()
+        .run(move || {
+            let compiled = if thumbnail {
+                compile_world_with_thumbnail(&world, &formats, &export)
+                    .map(|(outputs, png)| (outputs, Some(png)))
+            } else {
+                compile_world_to_formats(&world, &formats, &export).map(|outputs| (outputs, None))
+            };
+            compiled
+                .map(|(outputs, thumbnail)| CompiledOutputs { outputs, thumbnail })
+                .map_err(|diags| Box::new(compilation_error(&diags, debug.then_some(&world))))
+        })
+        .await
+        .map(Arc::new)?;
+    output_cache().insert(key, compiled.clone());
+    Ok(compiled)
+}
+
+/// Generate a resume PDF and extract its text
+///
+/// Takes the same input as `generate_resume`, so the text reflects the same
+/// render options. The PDF itself is neither stored nor written.
+pub async fn verify_resume_text(
+    input: Value,
+    context: &ToolContext,
+) -> Result<String, Box<GenerationResult>> {
+    let PreparedDocument {
+        world,
+        export,
+        debug,
+        ..
+    } = prepare_resume(input, context).await?;
+    let compiled = compile_outputs(world, export, vec![OutputFormat::Pdf], false, debug).await?;
+
+    compile_pool()
+        .run(move || {
+            let pdf = compiled.outputs.get(&OutputFormat::Pdf).map(Vec::as_slice);
+            pdf_extract::extract_text_from_mem(pdf.unwrap_or_default())
+        })
+        .await
+        .map_err(|e| {
+            Box::new(GenerationResult::Error {
+                message: format!("Failed to extract text from the generated PDF: {}", e),
+                validation_errors: None,
+                source_excerpt: None,
+            })
+        })
+}
+
+/// Compile a document to PDF bytes for the raw HTTP route
+///
+/// Takes the same input as the matching generate tool (`resume` or
+/// `cover_letter`) and returns the PDF with its filename. Inline `formats` are
+/// ignored since the response body is the PDF itself.
+pub async fn render_pdf(
+    document_type: &str,
+    input: Value,
+    context: &ToolContext,
+) -> Result<(Vec<u8>, String), Box<GenerationResult>> {
+    let prepared = match document_type {
+        "resume" => prepare_resume(input, context).await?,
+        "cover_letter" => prepare_cover_letter(input, context)?,
+        other => {
+            return Err(Box::new(GenerationResult::Error {
+                message: format!(
+                    "Unknown document type '{}'. Expected 'resume' or 'cover_letter'.",
+                    other
+                ),
+                validation_errors: None,
+                source_excerpt: None,
+            }));
+        }
+    };
+
+    let PreparedDocument {
+        world,
+        export,
+        filename,
+        debug,
+        ..
+    } = prepared;
+    let compiled = compile_outputs(world, export, vec![OutputFormat::Pdf], false, debug).await?;
+    let bytes = compiled
+        .outputs
+        .get(&OutputFormat::Pdf)
+        .cloned()
+        .unwrap_or_default();
+    Ok((bytes, filename))
+}
+
+/// Input for the compare_resumes tool
+///
+/// Every other field (render options, `assets`, `treat_warnings_as_errors`)
+/// is passed on to each resume as it would be to generate_resume.
+#[derive(Debug, Deserialize)]
+pub struct CompareResumesInput {
+    pub left: Value,
+    pub right: Value,
+    pub filename: Option<String>,
+    #[serde(default)]
+    pub formats: Vec<OutputFormat>,
+    #[serde(default)]
+    pub thumbnail: bool,
+    #[serde(default)]
+    pub ttl_seconds: Option<u64>,
+    #[serde(default)]
+    pub deterministic_id: bool,
+    #[serde(default)]
+    pub debug: bool,
+}
+
+/// Fields of a compare_resumes input that describe the comparison itself,
+/// rather than how each resume is rendered
+const COMPARISON_FIELDS: &[&str] = &[
+    "left",
+    "right",
+    "filename",
+    "formats",
+    "thumbnail",
+    "ttl_seconds",
+    "deterministic_id",
+];
+
+/// Renders two resumes side by side in one PDF
+///
+/// Each resume is validated and compiled exactly as generate_resume would,
+/// with the same options; the two PDFs are then placed page by page on
+/// landscape pages and delivered like any generated document.
+pub async fn compare_resumes(input: Value, context: &ToolContext) -> GenerationResult {
+    let parsed_input: CompareResumesInput = match serde_json::from_value(input.clone()) {
+        Ok(v) => v,
+        Err(e) => {
+            return GenerationResult::Error {
+                message: format!(
+                    "Invalid tool input: expected object with 'left' and 'right' resumes. {}",
+                    e
+                ),
+                validation_errors: None,
+                source_excerpt: None,
+            };
+        }
+    };
+
+    let mut warnings = Vec::new();
+    let mut assets = AssetMap::new();
+    let mut pages = [0; 2];
+    let sides = [("left", parsed_input.left), ("right", parsed_input.right)];
+    for (index, (side, resume)) in sides.into_iter().enumerate() {
+        // Each resume is generated from the shared options plus its own payload
+        let mut side_input = input.clone();
+        if let Some(fields) = side_input.as_object_mut() {
+            fields.retain(|key, _| !COMPARISON_FIELDS.contains(&key.as_str()));
+            fields.insert("resume".to_string(), resume);
+        }
+
+        let prepared = match prepare_resume(side_input, context).await {
+            Ok(prepared) => prepared,
+            Err(error) => return side_error(side, *error),
+        };
+        warnings.extend(
+            prepared
+                .warnings
+                .into_iter()
+                .map(|w| ValidationError::new(side_path(side, &w.path), w.message)),
+        );
+
+        let PreparedDocument {
+            world,
+            export,
+            debug,
+            ..
+        } = prepared;
+        let compiled = compile_pool()
+            .run(move || {
+                compile_world_with_page_count(&world, &export)
+                    .map_err(|diags| Box::new(compilation_error(&diags, debug.then_some(&world))))
+            })
+            .await;
+        match compiled {
+            Ok((pdf, count)) => {
+                assets.insert(COMPARISON_ASSETS[index].to_string(), Bytes::new(pdf));
+                pages[index] = count;
+            }
+            Err(error) => return side_error(side, *error),
+        }
+    }
+
+    let source = match transform_comparison(pages) {
+        Ok(s) => s,
+        Err(e) => {
+            return GenerationResult::Error {
+                message: format!("Failed to transform comparison to Typst: {}", e),
+                validation_errors: None,
+                source_excerpt: None,
+            };
+        }
+    };
+
+    let prepared = PreparedDocument {
+        document_type: &DocumentType::RESUME,
+        world: DocgenWorld::new(source).with_assets(&assets),
+        export: ExportOptions {
+            treat_warnings_as_errors: false,
+            max_pages: Some(context.max_pages),
+        },
+        formats: parsed_input.formats,
+        filename: parsed_input
+            .filename
+            .as_deref()
+            .and_then(sanitize_filename)
+            .unwrap_or_else(|| "Resume_Comparison.pdf".to_string()),
+        warnings,
+        thumbnail: parsed_input.thumbnail,
+        ttl_seconds: parsed_input.ttl_seconds,
+        deterministic_id: parsed_input.deterministic_id,
+        debug: parsed_input.debug,
+    };
+    deliver_document(prepared, context).await
+}
+
+/// A path inside one of the compared resumes, e.g. `left.basics.email`
+fn side_path(side: &str, path: &str) -> String {
+    if path.is_empty() {
+        side.to_string()
+    } else {
+        format!("{}.{}", side, path)
+    }
+}
+
+/// Attribute an error generating one of the compared resumes to its side
+fn side_error(side: &str, error: GenerationResult) -> GenerationResult {
+    match error {
+        GenerationResult::Error {
+            message,
+            validation_errors,
+            source_excerpt,
+        } => GenerationResult::Error {
+            message: format!("The {} resume could not be generated: {}", side, message),
+            validation_errors: validation_errors.map(|errors| {
+                errors
+                    .into_iter()
+                    .map(|e| ValidationError {
+                        path: side_path(side, &e.path),
+                        ..e
+                    })
+                    .collect()
+            }),
+            source_excerpt,
+        },
+        other => other,
+    }
+}
+
+/// "1 hour", "90 minutes", "45 seconds": the largest whole unit for a duration
+fn describe_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (count, unit) = if secs.is_multiple_of(3600) {
+        (secs / 3600, "hour")
+    } else if secs.is_multiple_of(60) {
+        (secs / 60, "minute")
+    } else {
+        (secs, "second")
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    format!("{} {}{}", count, unit, plural)
+}
+
+/// Turn Typst diagnostics into an error result
+///
+/// With `debug_world`, the error also carries the generated source around the
+/// failing line. Source is never included otherwise, since it embeds the
+/// document's contents.
+fn compilation_error(
+    diags: &[SourceDiagnostic],
+    debug_world: Option<&DocgenWorld>,
+) -> GenerationResult {
+    let msg = diags
+        .iter()
+        .map(|d| format!("{:?}: {}", d.severity, d.message))
+        .collect::<Vec<_>>()
+        .join("\n");
+    GenerationResult::Error {
+        message: format!("Typst compilation failed:\n{}", msg),
+        validation_errors: None,
+        source_excerpt: debug_world.map(|world| source_excerpt(world, diags)),
+    }
+}
+
+/// Longest accepted filename stem (before the `.pdf` extension)
+const MAX_FILENAME_STEM: usize = 100;
+
+/// Makes a user-supplied filename safe to write locally and to send in a
+/// `Content-Disposition` header
+///
+/// Path separators and characters that are illegal on common filesystems are
+/// replaced with `_`, leading dots are stripped (so `..` cannot escape the
+/// working directory), and a `.pdf` extension is ensured. Returns None when
+/// nothing usable is left.
+fn sanitize_filename(raw: &str) -> Option<String> {
+    let replaced: String = raw
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    let trimmed = replaced
+        .trim_start_matches(|c: char| c == '.' || c == '_' || c.is_whitespace())
+        .trim_end_matches(|c: char| c == '.' || c.is_whitespace());
+    let stem = if trimmed.to_ascii_lowercase().ends_with(".pdf") {
+        &trimmed[..trimmed.len() - 4]
+    } else {
+        trimmed
+    };
+
+    let stem: String = stem.chars().take(MAX_FILENAME_STEM).collect();
+    if stem.trim().is_empty() {
+        return None;
+    }
+    Some(format!("{}.pdf", stem))
+}
+
+/// Whether `value` is a `#RGB` or `#RRGGBB` hex color
+fn is_hex_color(value: &str) -> bool {
+    value.strip_prefix('#').is_some_and(|digits| {
+        matches!(digits.len(), 3 | 6) && digits.chars().all(|c| c.is_ascii_hexdigit())
+    })
+}
+
+/// Check render options that serde cannot validate on its own
+fn validate_render_options(options: &RenderOptions) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    for (i, section) in options.page_breaks.iter().enumerate() {
+        if !SECTION_NAMES.contains(&section.as_str()) {
+            errors.push(ValidationError::new(
+                format!("page_breaks[{}]", i),
+                format!(
+                    "Unknown section '{}'. Valid sections: {}",
+                    section,
+                    SECTION_NAMES.join(", ")
+                ),
+            ));
+        }
+    }
+
+    for (section, color) in &options.section_colors {
+        let path = format!("section_colors.{}", section);
+        if !SECTION_NAMES.contains(&section.as_str()) {
+            errors.push(ValidationError::new(
+                path,
+                format!(
+                    "Unknown section '{}'. Valid sections: {}",
+                    section,
+                    SECTION_NAMES.join(", ")
+                ),
+            ));
+        } else if !is_hex_color(color) {
+            errors.push(ValidationError::new(
+                path,
+                format!(
+                    "Invalid color '{}'; use #RGB or #RRGGBB hex, e.g. #1a73e8",
+                    color
+                ),
+            ));
+        }
+    }
+
+    if let Some(thickness) = options.style_overrides.rule_thickness
+        && !(0.0..=MAX_RULE_THICKNESS).contains(&thickness)
+    {
+        errors.push(ValidationError::new(
+            "style_overrides.rule_thickness",
+            format!(
+                "Rule thickness must be between 0 and {} points, got {}",
+                MAX_RULE_THICKNESS, thickness
+            ),
+        ));
+    }
+
+    errors
+}
+
 /// Parse a serde JSON error into structured validation errors
 ///
 /// Extracts path information from serde error messages to provide
 /// actionable feedback about where validation failed.
-fn parse_serde_error(error: &serde_json::Error) -> Vec<ValidationError> {
+fn parse_serde_error(error: &serde_json::Error, payload: &Value) -> Vec<ValidationError> {
     let message = error.to_string();
 
     // Serde errors often contain path information like "at line X column Y"
     // and messages like "missing field `name`" or "invalid type: expected X, found Y"
     // We extract what we can to provide structured errors
 
+    // Truncated JSON text, or text with something after the closing brace
+    if error.is_eof() || message.starts_with("trailing characters") {
+        return vec![
+            ValidationError::new(
+                "resume",
+                format!(
+                    "The resume JSON appears incomplete or has extra content (line {} column {})",
+                    error.line(),
+                    error.column()
+                ),
+            )
+            .with_hint(Some(
+                "Check for unclosed brackets, braces, or quotes, and for anything after the final closing brace".to_string(),
+            )),
+        ];
+    }
+
     // Check for "missing field" errors
     if let Some(field) = extract_missing_field(&message) {
-        return vec![ValidationError::new(
-            infer_path_from_context(&message, &field),
-            format!("Missing required field: {}", field),
-        )];
+        return vec![
+            ValidationError::new(
+                infer_path_from_context(&message, &field),
+                format!("Missing required field: {}", field),
+            )
+            .with_hint(missing_field_hint::<Resume>(payload, &field)),
+        ];
     }
 
     // Check for type errors
@@ -544,8 +2280,74 @@ fn parse_serde_error(error: &serde_json::Error) -> Vec<ValidationError> {
         return vec![ValidationError::new(path, message.clone())];
     }
 
-    // Default: return the full error message
-    vec![ValidationError::new("", message)]
+    // Default: return the full error message at the location it occurred
+    vec![ValidationError::new(
+        locate_serde_error::<Resume>(error, payload),
+        message,
+    )]
+}
+
+/// Locate an error whose message matched no known pattern
+///
+/// Errors from deserializing a `Value` carry no position, so the payload is
+/// deserialized again with a path-tracking deserializer to find where it
+/// failed (e.g. `work[0].highlights[1]`). Falls back to the error's line and
+/// column when it has one.
+fn locate_serde_error<T: serde::de::DeserializeOwned>(
+    error: &serde_json::Error,
+    payload: &Value,
+) -> String {
+    if let Err(e) = serde_path_to_error::deserialize::<_, T>(payload) {
+        let path = e.path().to_string();
+        if path != "." {
+            return path;
+        }
+    }
+
+    if error.line() > 0 {
+        format!("line {} column {}", error.line(), error.column())
+    } else {
+        String::new()
+    }
+}
+
+/// Schema description of a missing field, used as a hint about what it expects
+///
+/// The object the field is missing from is located with a path-tracking
+/// re-parse (e.g. `basics`), then the field is looked up in that object's
+/// generated schema.
+fn missing_field_hint<T>(payload: &Value, field: &str) -> Option<String>
+where
+    T: schemars::JsonSchema + serde::de::DeserializeOwned,
+{
+    let parent = serde_path_to_error::deserialize::<_, T>(payload)
+        .err()?
+        .path()
+        .to_string();
+    let root = serde_json::to_value(schemars::schema_for!(T)).ok()?;
+
+    let mut schema = &root;
+    let mut value = payload;
+    for part in parent.split('.').filter(|p| !p.is_empty()) {
+        let (key, indices) = part.split_at(part.find('[').unwrap_or(part.len()));
+        if !key.is_empty() {
+            schema = resolve_schema(&root, schema, value)
+                .get("properties")?
+                .get(key)?;
+            value = value.get(key)?;
+        }
+        for index in indices.split(['[', ']']).filter(|i| !i.is_empty()) {
+            schema = resolve_schema(&root, schema, value).get("items")?;
+            value = value.get(index.parse::<usize>().ok()?)?;
+        }
+    }
+
+    resolve_schema(&root, schema, value)
+        .get("properties")?
+        .get(field)?
+        .get("description")?
+        .as_str()
+        .map(str::to_string)
 }
 
 /// Extract field name from "missing field `fieldname`" error messages
@@ -653,6 +2455,9 @@ pub fn get_cover_letter_best_practices() -> Value {
 #[derive(Debug, Deserialize)]
 pub struct ValidateCoverLetterInput {
     pub cover_letter: Value,
+    /// Also check the payload against the published JSON Schema
+    #[serde(default)]
+    pub strict: bool,
 }
 
 /// Input for the generate_cover_letter tool
@@ -660,6 +2465,26 @@ pub struct ValidateCoverLetterInput {
 pub struct GenerateCoverLetterInput {
     pub cover_letter: Value,
     pub filename: Option<String>,
+    /// Fail generation if the Typst compiler reports any warning
+    #[serde(default)]
+    pub treat_warnings_as_errors: bool,
+    /// Return these formats inline (base64) instead of saving or storing a PDF
+    #[serde(default)]
+    pub formats: Vec<OutputFormat>,
+    /// Also return a small PNG of the first page as `thumbnail_base64`
+    #[serde(default)]
+    pub thumbnail: bool,
+    /// How long the download link stays valid, in seconds (HTTP mode only)
+    #[serde(default)]
+    pub ttl_seconds: Option<u64>,
+    /// Store the file under an ID derived from its content (HTTP mode only)
+    #[serde(default)]
+    pub deterministic_id: bool,
+    /// Include the generated source around the error when compilation fails
+    #[serde(default)]
+    pub debug: bool,
+    #[serde(flatten)]
+    pub options: CoverLetterOptions,
 }
 
 /// Result of cover letter validation
@@ -693,25 +2518,50 @@ pub fn validate_cover_letter(input: Value) -> CoverLetterValidationResult {
         }
     };
 
-    match serde_json::from_value::<CoverLetter>(parsed_input.cover_letter) {
+    if parsed_input.strict {
+        let errors = cover_letter_schema_errors(&parsed_input.cover_letter);
+        if !errors.is_empty() {
+            return CoverLetterValidationResult::Invalid { errors };
+        }
+    }
+
+    match CoverLetter::deserialize(&parsed_input.cover_letter) {
+        // A letter with nothing between its opening and closing would render blank
+        Ok(cover_letter) if cover_letter.body.iter().all(|p| p.trim().is_empty()) => {
+            CoverLetterValidationResult::Invalid {
+                errors: vec![
+                    ValidationError::new(
+                        "body",
+                        "The body must contain at least one non-empty paragraph",
+                    )
+                    .with_hint(Some(
+                        "Add the paragraphs (typically 2-3) that show your qualifications and fit"
+                            .to_string(),
+                    )),
+                ],
+            }
+        }
         Ok(cover_letter) => CoverLetterValidationResult::Valid {
             cover_letter: Box::new(cover_letter),
         },
         Err(e) => CoverLetterValidationResult::Invalid {
-            errors: parse_serde_error_cover_letter(&e),
+            errors: parse_serde_error_cover_letter(&e, &parsed_input.cover_letter),
         },
     }
 }
 
 /// Parse serde errors for cover letters
-fn parse_serde_error_cover_letter(error: &serde_json::Error) -> Vec<ValidationError> {
+fn parse_serde_error_cover_letter(error: &serde_json::Error, payload: &Value) -> Vec<ValidationError> {
     let message = error.to_string();
 
     if let Some(field) = extract_missing_field(&message) {
-        return vec![ValidationError::new(
-            infer_path_from_context_cover_letter(&message, &field),
-            format!("Missing required field: {}", field),
-        )];
+        return vec![
+            ValidationError::new(
+                infer_path_from_context_cover_letter(&message, &field),
+                format!("Missing required field: {}", field),
+            )
+            .with_hint(missing_field_hint::<CoverLetter>(payload, &field)),
+        ];
     }
 
     if message.contains("invalid type") {
@@ -724,7 +2574,10 @@ fn parse_serde_error_cover_letter(error: &serde_json::Error) -> Vec<ValidationEr
         return vec![ValidationError::new(path, message.clone())];
     }
 
-    vec![ValidationError::new("", message)]
+    vec![ValidationError::new(
+        locate_serde_error::<CoverLetter>(error, payload),
+        message,
+    )]
 }
 
 fn extract_path_hint_cover_letter(message: &str) -> String {
@@ -751,111 +2604,331 @@ fn infer_path_from_context_cover_letter(message: &str, field: &str) -> String {
 }
 
 /// Generates a PDF cover letter from a JSON payload
+///
+/// Supports the same delivery options as generate_resume.
 pub async fn generate_cover_letter(input: Value, context: &ToolContext) -> GenerationResult {
+    match prepare_cover_letter(input, context) {
+        Ok(prepared) => deliver_document(prepared, context).await,
+        Err(error) => *error,
+    }
+}
+
+/// Parse, validate, and transform a generate_cover_letter input
+fn prepare_cover_letter(input: Value, context: &ToolContext) -> Result<PreparedDocument, Box<GenerationResult>> {
     let parsed_input: GenerateCoverLetterInput = match serde_json::from_value(input.clone()) {
         Ok(v) => v,
         Err(e) => {
-            return GenerationResult::Error {
+            return Err(Box::new(GenerationResult::Error {
                 message: format!(
                     "Invalid tool input: expected object with 'cover_letter' field. {}",
                     e
                 ),
                 validation_errors: None,
-            };
+                source_excerpt: None,
+            }));
         }
     };
 
     let validation_input = serde_json::json!({ "cover_letter": parsed_input.cover_letter });
     let validation_result = validate_cover_letter(validation_input);
 
-    let cover_letter = match validation_result {
+    let mut cover_letter = match validation_result {
         CoverLetterValidationResult::Valid { cover_letter } => cover_letter,
         CoverLetterValidationResult::Invalid { errors } => {
-            return GenerationResult::Error {
+            return Err(Box::new(GenerationResult::Error {
                 message: "Validation failed".to_string(),
                 validation_errors: Some(errors),
-            };
+                source_excerpt: None,
+            }));
         }
     };
 
-    let source = match transform_cover_letter(&cover_letter) {
+    // Fall back to the server-wide sign-off when none was given
+    if cover_letter
+        .signature
+        .as_deref()
+        .is_none_or(|s| s.trim().is_empty())
+    {
+        cover_letter.signature = Some(context.default_signature.clone());
+    }
+
+    let source = match transform_cover_letter(&cover_letter, &parsed_input.options) {
         Ok(s) => s,
         Err(e) => {
-            return GenerationResult::Error {
+            return Err(Box::new(GenerationResult::Error {
                 message: format!("Failed to transform cover letter to Typst: {}", e),
                 validation_errors: None,
-            };
+                source_excerpt: None,
+            }));
         }
     };
 
-    let pdf_bytes = match compile(source) {
-        Ok(bytes) => bytes,
-        Err(diags) => {
-            let msg = diags
-                .iter()
-                .map(|d| format!("{:?}: {}", d.severity, d.message))
-                .collect::<Vec<_>>()
-                .join("\n");
-            return GenerationResult::Error {
-                message: format!("Typst compilation failed:\n{}", msg),
-                validation_errors: None,
-            };
+    let filename = parsed_input
+        .filename
+        .as_deref()
+        .and_then(sanitize_filename)
+        .unwrap_or_else(|| DocumentType::COVER_LETTER.default_filename(&cover_letter.sender.name));
+
+    Ok(PreparedDocument {
+        document_type: &DocumentType::COVER_LETTER,
+        world: DocgenWorld::new(source),
+        export: ExportOptions {
+            treat_warnings_as_errors: parsed_input.treat_warnings_as_errors,
+            max_pages: Some(context.max_pages),
+        },
+        formats: parsed_input.formats,
+        filename,
+        warnings: vec![],
+        thumbnail: parsed_input.thumbnail,
+        ttl_seconds: parsed_input.ttl_seconds,
+        deterministic_id: parsed_input.deterministic_id,
+        debug: parsed_input.debug,
+    })
+}
+
+// ============================================================================
+// COMPATIBILITY CHECK
+// ============================================================================
+
+/// Input for the check_compatibility tool
+#[derive(Debug, Deserialize)]
+pub struct CheckCompatibilityInput {
+    pub document_type: String,
+    pub document: Value,
+}
+
+/// Field-level breakdown of how a payload lines up with a document schema
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompatibilityReport {
+    /// The document type the payload was checked against
+    pub document_type: String,
+    /// True when no required fields are missing
+    pub compatible: bool,
+    /// Paths of fields that exist in the schema
+    pub matched: Vec<String>,
+    /// Paths of fields the schema does not know about (ignored on generation)
+    pub unknown: Vec<String>,
+    /// Paths of required fields that are absent
+    pub missing: Vec<String>,
+}
+
+/// Input for the get_typescript_types tool
+#[derive(Debug, Deserialize)]
+pub struct GetTypescriptTypesInput {
+    #[serde(default = "default_document_type")]
+    pub document_type: String,
+}
+
+fn default_document_type() -> String {
+    "resume".to_string()
+}
+
+/// Returns TypeScript declarations for a document type's schema
+pub fn get_typescript_types(input: Value) -> Result<Value, String> {
+    let parsed_input: GetTypescriptTypesInput =
+        serde_json::from_value(input).map_err(|e| format!("Invalid tool input: {}", e))?;
+
+    let schema = match parsed_input.document_type.as_str() {
+        "resume" => schemars::schema_for!(Resume).to_value(),
+        "cover_letter" => schemars::schema_for!(CoverLetter).to_value(),
+        other => {
+            return Err(format!(
+                "Unknown document type '{}'. Expected 'resume' or 'cover_letter'.",
+                other
+            ));
         }
     };
 
-    let filename = parsed_input.filename.unwrap_or_else(|| {
-        let name = &cover_letter.sender.name;
-        let company = &cover_letter.recipient.company;
-        let sanitized_name = name
-            .to_lowercase()
-            .replace(" ", "-")
-            .chars()
-            .filter(|c| c.is_alphanumeric() || *c == '-')
-            .collect::<String>();
-        let sanitized_company = company
-            .to_lowercase()
-            .replace(" ", "-")
-            .chars()
-            .filter(|c| c.is_alphanumeric() || *c == '-')
-            .collect::<String>();
-        format!("{}-{}-cover-letter.pdf", sanitized_name, sanitized_company)
-    });
+    Ok(serde_json::json!({
+        "document_type": parsed_input.document_type,
+        "typescript": schema_to_typescript(&schema)
+    }))
+}
 
-    match (&context.file_storage, &context.base_url) {
-        (Some(storage), Some(base_url)) => {
-            let file_id = storage.store(pdf_bytes, filename.clone()).await;
-            let download_url = format!("{}/files/{}", base_url, file_id);
+/// Accepted values and defaults of the resume style options
+///
+/// Built from the same enums the input is deserialized into, so the listing
+/// can't drift from what `generate_resume` accepts.
+pub fn list_style_options() -> Value {
+    fn choice<T: Serialize>(values: &[T], default: Option<T>) -> Value {
+        serde_json::json!({ "values": values, "default": default })
+    }
 
-            GenerationResult::Success {
-                file_path: None,
-                download_url: Some(download_url.clone()),
-                message: format!(
-                    "Cover letter successfully generated. Download it from: {}\n\
-                     \n\
-                     NOTE: You are likely running in a sandboxed environment and cannot access local files directly. \
-                     Please provide this URL to the user so they can download the PDF. \
-                     This link will expire in 1 hour.",
-                    download_url
-                ),
+    serde_json::json!({
+        "icon_style": choice(&IconStyle::ALL, Some(IconStyle::default())),
+        "summary_placement": choice(&SummaryPlacement::ALL, Some(SummaryPlacement::default())),
+        "summary_style": choice(&SummaryStyle::ALL, Some(SummaryStyle::default())),
+        "skills_style": choice(&SkillsStyle::ALL, Some(SkillsStyle::default())),
+        "contact_style": choice(&ContactStyle::ALL, Some(ContactStyle::default())),
+        "language_style": choice(&LanguageStyle::ALL, Some(LanguageStyle::default())),
+        "divider_style": choice(&DividerStyle::ALL, Some(DividerStyle::default())),
+        "density": choice(&Density::ALL, Some(Density::default())),
+        "emphasis": choice(&Emphasis::ALL, None),
+        "style_overrides": {
+            "justify": { "values": [true, false], "default": true },
+            "heading_weight": choice(&HeadingWeight::ALL, Some(HeadingWeight::Bold)),
+            "rule_thickness": { "minimum": 0.0, "maximum": MAX_RULE_THICKNESS, "default": 0.5 }
+        }
+    })
+}
+
+/// Checks an arbitrary JSON payload against a document schema
+///
+/// Unlike validation, this walks the generated JSON Schema instead of relying
+/// on serde, so it reports every mismatch rather than stopping at the first one.
+pub fn check_compatibility(input: Value) -> Result<CompatibilityReport, String> {
+    let parsed_input: CheckCompatibilityInput = serde_json::from_value(input).map_err(|e| {
+        format!(
+            "Invalid tool input: expected object with 'document_type' and 'document' fields. {}",
+            e
+        )
+    })?;
+
+    let schema = match parsed_input.document_type.as_str() {
+        "resume" => schemars::schema_for!(Resume).to_value(),
+        "cover_letter" => schemars::schema_for!(CoverLetter).to_value(),
+        other => {
+            return Err(format!(
+                "Unknown document type '{}'. Expected 'resume' or 'cover_letter'.",
+                other
+            ));
+        }
+    };
+
+    let mut report = CompatibilityReport {
+        document_type: parsed_input.document_type,
+        ..Default::default()
+    };
+    let mut document = parsed_input.document;
+    canonicalize_aliases(&schema, &schema, &mut document);
+    walk_schema(&schema, &schema, &document, "", &mut report);
+    report.compatible = report.missing.is_empty();
+
+    Ok(report)
+}
+
+/// Recursively compare a value with its schema node, recording results in the report
+fn walk_schema(root: &Value, schema: &Value, value: &Value, path: &str, report: &mut CompatibilityReport) {
+    let schema = resolve_schema(root, schema, value);
+
+    match value {
+        Value::Object(fields) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+
+            // Free-form maps (e.g. sectionTitles) accept any key
+            let Some(properties) = properties else {
+                return;
+            };
+
+            for (key, child) in fields {
+                let child_path = join_path(path, key);
+                match properties.get(key) {
+                    Some(child_schema) => {
+                        report.matched.push(child_path.clone());
+                        walk_schema(root, child_schema, child, &child_path, report);
+                    }
+                    None => report.unknown.push(child_path),
+                }
+            }
+
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for field in required.iter().filter_map(Value::as_str) {
+                    if !fields.contains_key(field) {
+                        report.missing.push(join_path(path, field));
+                    }
+                }
             }
         }
-        _ => match fs::write(&filename, pdf_bytes) {
-            Ok(_) => GenerationResult::Success {
-                file_path: Some(filename.clone()),
-                download_url: None,
-                message: format!(
-                    "Cover letter successfully generated and saved to '{}'\n\
-                     \n\
-                     NOTE: If you are running in a sandboxed environment, you may not have direct access to this file. \
-                     The file path is provided for reference, but the user should check their working directory.",
-                    filename
-                ),
-            },
-            Err(e) => GenerationResult::Error {
-                message: format!("Failed to write PDF to file '{}': {}", filename, e),
-                validation_errors: None,
-            },
-        },
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    walk_schema(root, item_schema, item, &format!("{}[{}]", path, i), report);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Rename alternate field names to their canonical names, recursively
+///
+/// Serde accepts the names a property lists under `x-aliases`, but the
+/// schema's `properties` and `required` only know the canonical name. An alias
+/// is left alone if the canonical name is also present.
+fn canonicalize_aliases(root: &Value, schema: &Value, value: &mut Value) {
+    let schema = resolve_schema(root, schema, value);
+
+    match value {
+        Value::Object(fields) => {
+            let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+                return;
+            };
+
+            for (name, property) in properties {
+                let aliases = property.get("x-aliases").and_then(Value::as_array);
+                for alias in aliases.into_iter().flatten().filter_map(Value::as_str) {
+                    if !fields.contains_key(name)
+                        && let Some(aliased) = fields.remove(alias)
+                    {
+                        fields.insert(name.clone(), aliased);
+                    }
+                }
+            }
+
+            for (key, child) in fields.iter_mut() {
+                if let Some(child_schema) = properties.get(key) {
+                    canonicalize_aliases(root, child_schema, child);
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for item in items {
+                    canonicalize_aliases(root, item_schema, item);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Follow `$ref` pointers and pick the `anyOf`/`oneOf` branch that fits the value
+fn resolve_schema<'a>(root: &'a Value, schema: &'a Value, value: &Value) -> &'a Value {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str)
+        && let Some(target) = root.pointer(reference.trim_start_matches('#'))
+    {
+        return resolve_schema(root, target, value);
+    }
+
+    for key in ["anyOf", "oneOf"] {
+        if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+            let wanted = match value {
+                Value::Object(_) => "object",
+                Value::Array(_) => "array",
+                _ => return schema,
+            };
+            for variant in variants {
+                let variant = resolve_schema(root, variant, value);
+                let matches = variant.get("type").is_some_and(|t| {
+                    t.as_str() == Some(wanted)
+                        || t.as_array().is_some_and(|ts| ts.iter().any(|t| t == wanted))
+                });
+                if matches {
+                    return variant;
+                }
+            }
+        }
+    }
+
+    schema
+}
+
+/// Append a field name to a dotted path
+fn join_path(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", path, field)
     }
 }
 
@@ -868,7 +2941,7 @@ pub fn get_document_types() -> Value {
     serde_json::json!({
         "document_types": [
             {
-                "name": "resume",
+                "name": DocumentType::RESUME.name,
                 "display_name": "Resume",
                 "description": "A concise (1-2 page) summary of professional experience, skills, and education. Standard for industry jobs in North America.",
                 "use_cases": [
@@ -882,10 +2955,12 @@ pub fn get_document_types() -> Value {
                     "get_best_practices": "get_resume_best_practices",
                     "validate": "validate_resume",
                     "generate": "generate_resume"
-                }
+                },
+                "default_filename": DocumentType::RESUME.filename_pattern,
+                "mime_type": DocumentType::RESUME.mime_type
             },
             {
-                "name": "cover_letter",
+                "name": DocumentType::COVER_LETTER.name,
                 "display_name": "Cover Letter",
                 "description": "A one-page letter introducing yourself and explaining why you're interested in and qualified for a specific position at a specific company.",
                 "use_cases": [
@@ -900,7 +2975,9 @@ pub fn get_document_types() -> Value {
                     "get_best_practices": "get_cover_letter_best_practices",
                     "validate": "validate_cover_letter",
                     "generate": "generate_cover_letter"
-                }
+                },
+                "default_filename": DocumentType::COVER_LETTER.filename_pattern,
+                "mime_type": DocumentType::COVER_LETTER.mime_type
             },
             {
                 "name": "cv",
@@ -927,6 +3004,48 @@ pub fn get_document_types() -> Value {
     })
 }
 
+/// Server instructions sent in `initialize`, built from `get_document_types`
+///
+/// Lists each implemented document type with its tools, so the description
+/// stays accurate as document types are added.
+pub fn server_instructions() -> String {
+    let registry = get_document_types();
+    let mut types = Vec::new();
+    for doc_type in registry["document_types"].as_array().into_iter().flatten() {
+        if doc_type.get("status").is_some() {
+            continue; // Not implemented yet
+        }
+        let tool = |step: &str| doc_type["tools"][step].as_str().unwrap_or_default();
+        types.push(format!(
+            "- {} ('{}'): {}\n  Tools: '{}', '{}', '{}', '{}'",
+            doc_type["display_name"].as_str().unwrap_or_default(),
+            doc_type["name"].as_str().unwrap_or_default(),
+            doc_type["description"].as_str().unwrap_or_default(),
+            tool("get_best_practices"),
+            tool("get_schema"),
+            tool("validate"),
+            tool("generate"),
+        ));
+    }
+
+    format!(
+        "A Model Context Protocol server for programmatic document generation, \
+         powered by Typst. Use this server to generate professionally typeset documents.\n\n\
+         AVAILABLE DOCUMENT TYPES:\n{}\n\n\
+         RECOMMENDED WORKFLOW FOR AI AGENTS (using the tools of the chosen document type, in the order listed):\n\
+         1. Use the best practices tool to understand writing guidelines\n\
+         2. Use the schema tool to see the exact JSON structure required\n\
+         3. Gather information from the user and construct the JSON\n\
+         4. Use the validate tool to check the JSON structure\n\
+         5. Use the generate tool to create the final PDF\n\n\
+         Not sure which document the user needs? Call '{}' first.\n\n\
+         ALTERNATIVE: Advanced agents can also read the best practices as PROMPTS \
+         and the schemas as RESOURCES (under 'docgen://schemas/').",
+        types.join("\n"),
+        GET_DOCUMENT_TYPES_TOOL
+    )
+}
+
 /// Returns the document type guide
 pub fn get_document_type_guide() -> Value {
     match prompts::get_prompt(prompts::DOCUMENT_TYPE_GUIDE_PROMPT) {
@@ -949,6 +3068,42 @@ pub fn get_document_type_guide() -> Value {
     }
 }
 
+/// URI of a generated PDF embedded in a tool result
+pub const GENERATED_PDF_URI: &str = "docgen://generated/resume.pdf";
+
+/// Wrap a tool's result for the MCP layer
+///
+/// A PDF that `generate_resume` returns inline is moved out of the JSON into an
+/// embedded blob resource with the `application/pdf` MIME type, so clients can
+/// handle the binary natively instead of decoding a base64 field.
+pub fn into_call_tool_result(name: &str, result: Result<Value, String>) -> CallToolResult {
+    let mut value = match result {
+        Ok(value) => value,
+        Err(e) => return CallToolResult::structured_error(serde_json::json!({ "error": e })),
+    };
+
+    let pdf = match name {
+        GENERATE_RESUME_TOOL => value
+            .get_mut("outputs")
+            .and_then(Value::as_object_mut)
+            .and_then(|outputs| outputs.remove(OutputFormat::Pdf.as_str())),
+        _ => None,
+    };
+
+    let mut call_result = CallToolResult::structured(value);
+    if let Some(Value::String(blob)) = pdf {
+        call_result
+            .content
+            .push(Content::resource(ResourceContents::BlobResourceContents {
+                uri: GENERATED_PDF_URI.to_string(),
+                mime_type: Some("application/pdf".to_string()),
+                blob,
+                meta: None,
+            }));
+    }
+    call_result
+}
+
 /// Execute a tool by name with the given arguments
 pub async fn call_tool(name: &str, arguments: Value, context: &ToolContext) -> Result<Value, String> {
     match name {
@@ -971,13 +3126,37 @@ pub async fn call_tool(name: &str, arguments: Value, context: &ToolContext) -> R
             Ok(get_resume_best_practices())
         }
         VALIDATE_RESUME_TOOL => {
-            let result = validate_resume(arguments);
+            let result = validate_resume_with_estimate(arguments, context).await;
+            serde_json::to_value(result).map_err(|e| format!("Failed to serialize result: {}", e))
+        }
+        VALIDATE_RESUME_BATCH_TOOL => {
+            let result = validate_resume_batch(arguments)?;
             serde_json::to_value(result).map_err(|e| format!("Failed to serialize result: {}", e))
         }
         GENERATE_RESUME_TOOL => {
             let result = generate_resume(arguments, context).await;
             serde_json::to_value(result).map_err(|e| format!("Failed to serialize result: {}", e))
         }
+        PREVIEW_SECTION_TOOL => {
+            let result = preview_section(arguments, context).await;
+            serde_json::to_value(result).map_err(|e| format!("Failed to serialize result: {}", e))
+        }
+        EXPORT_RESUME_TEXT_TOOL => match validate_resume(arguments) {
+            ValidationResult::Valid { resume, .. } => Ok(serde_json::json!({
+                "status": "success",
+                "text": resume_to_text(&resume)
+            })),
+            invalid => serde_json::to_value(invalid)
+                .map_err(|e| format!("Failed to serialize result: {}", e)),
+        },
+        EXPORT_RESUME_LATEX_TOOL => match validate_resume(arguments) {
+            ValidationResult::Valid { resume, .. } => Ok(serde_json::json!({
+                "status": "success",
+                "latex": resume_to_latex(&resume)
+            })),
+            invalid => serde_json::to_value(invalid)
+                .map_err(|e| format!("Failed to serialize result: {}", e)),
+        },
         // Cover letter tools
         GET_COVER_LETTER_SCHEMA_TOOL => {
             let _ = arguments;
@@ -995,6 +3174,42 @@ pub async fn call_tool(name: &str, arguments: Value, context: &ToolContext) -> R
             let result = generate_cover_letter(arguments, context).await;
             serde_json::to_value(result).map_err(|e| format!("Failed to serialize result: {}", e))
         }
+        // Diagnostic tools
+        CHECK_COMPATIBILITY_TOOL => {
+            let report = check_compatibility(arguments)?;
+            serde_json::to_value(report).map_err(|e| format!("Failed to serialize result: {}", e))
+        }
+        GET_TYPESCRIPT_TYPES_TOOL => get_typescript_types(arguments),
+        LIST_STYLE_OPTIONS_TOOL => {
+            let _ = arguments; // Takes no arguments
+            Ok(list_style_options())
+        }
+        ATS_SCORE_TOOL => match validate_resume(arguments) {
+            ValidationResult::Valid { resume, .. } => serde_json::to_value(score_resume(&resume))
+                .map_err(|e| format!("Failed to serialize result: {}", e)),
+            invalid => serde_json::to_value(invalid)
+                .map_err(|e| format!("Failed to serialize result: {}", e)),
+        },
+        RESUME_COMPLETENESS_TOOL => match validate_resume(arguments) {
+            ValidationResult::Valid { resume, .. } => {
+                serde_json::to_value(resume_completeness(&resume))
+                    .map_err(|e| format!("Failed to serialize result: {}", e))
+            }
+            invalid => serde_json::to_value(invalid)
+                .map_err(|e| format!("Failed to serialize result: {}", e)),
+        },
+        COMPARE_RESUMES_TOOL => {
+            let result = compare_resumes(arguments, context).await;
+            serde_json::to_value(result).map_err(|e| format!("Failed to serialize result: {}", e))
+        }
+        VERIFY_RESUME_TEXT_TOOL => match verify_resume_text(arguments, context).await {
+            Ok(text) => Ok(serde_json::json!({
+                "status": "success",
+                "text": text
+            })),
+            Err(error) => serde_json::to_value(error)
+                .map_err(|e| format!("Failed to serialize result: {}", e)),
+        },
         _ => Err(format!("Unknown tool: {}", name)),
     }
 }
@@ -1006,7 +3221,7 @@ mod tests {
     #[test]
     fn test_list_tools() {
         let tools = list_tools();
-        assert_eq!(tools.len(), 10);
+        assert_eq!(tools.len(), 21);
         // Document type discovery tools
         assert_eq!(tools[0].name, GET_DOCUMENT_TYPES_TOOL);
         assert_eq!(tools[1].name, GET_DOCUMENT_TYPE_GUIDE_TOOL);
@@ -1014,12 +3229,57 @@ mod tests {
         assert_eq!(tools[2].name, GET_RESUME_SCHEMA_TOOL);
         assert_eq!(tools[3].name, GET_RESUME_BEST_PRACTICES_TOOL);
         assert_eq!(tools[4].name, VALIDATE_RESUME_TOOL);
-        assert_eq!(tools[5].name, GENERATE_RESUME_TOOL);
+        assert_eq!(tools[5].name, VALIDATE_RESUME_BATCH_TOOL);
+        assert_eq!(tools[6].name, GENERATE_RESUME_TOOL);
+        assert_eq!(tools[7].name, PREVIEW_SECTION_TOOL);
+        assert_eq!(tools[8].name, EXPORT_RESUME_TEXT_TOOL);
+        assert_eq!(tools[9].name, EXPORT_RESUME_LATEX_TOOL);
+        assert_eq!(tools[10].name, COMPARE_RESUMES_TOOL);
         // Cover letter tools
-        assert_eq!(tools[6].name, GET_COVER_LETTER_SCHEMA_TOOL);
-        assert_eq!(tools[7].name, GET_COVER_LETTER_BEST_PRACTICES_TOOL);
-        assert_eq!(tools[8].name, VALIDATE_COVER_LETTER_TOOL);
-        assert_eq!(tools[9].name, GENERATE_COVER_LETTER_TOOL);
+        assert_eq!(tools[11].name, GET_COVER_LETTER_SCHEMA_TOOL);
+        assert_eq!(tools[12].name, GET_COVER_LETTER_BEST_PRACTICES_TOOL);
+        assert_eq!(tools[13].name, VALIDATE_COVER_LETTER_TOOL);
+        assert_eq!(tools[14].name, GENERATE_COVER_LETTER_TOOL);
+        // Diagnostics
+        assert_eq!(tools[15].name, CHECK_COMPATIBILITY_TOOL);
+        assert_eq!(tools[16].name, ATS_SCORE_TOOL);
+        assert_eq!(tools[17].name, RESUME_COMPLETENESS_TOOL);
+        assert_eq!(tools[18].name, VERIFY_RESUME_TEXT_TOOL);
+        // Integration
+        assert_eq!(tools[19].name, GET_TYPESCRIPT_TYPES_TOOL);
+        assert_eq!(tools[20].name, LIST_STYLE_OPTIONS_TOOL);
+    }
+
+    #[test]
+    fn test_generate_tool_schema_describes_resume_fields() {
+        let tools = list_tools();
+        let generate = tools
+            .iter()
+            .find(|t| t.name == GENERATE_RESUME_TOOL)
+            .unwrap();
+        let schema = Value::Object(generate.input_schema.as_ref().clone());
+
+        let resume = &schema["properties"]["resume"];
+        assert_eq!(resume["type"], "object");
+        assert!(
+            resume["required"]
+                .as_array()
+                .unwrap()
+                .contains(&"basics".into())
+        );
+
+        // References into the hoisted definitions resolve from the input schema root
+        let basics_ref = resume["properties"]["basics"]["$ref"].as_str().unwrap();
+        let basics = schema.pointer(basics_ref.trim_start_matches('#')).unwrap();
+        assert!(basics["properties"]["email"].is_object());
+
+        let validate = tools
+            .iter()
+            .find(|t| t.name == VALIDATE_COVER_LETTER_TOOL)
+            .unwrap();
+        let schema = Value::Object(validate.input_schema.as_ref().clone());
+        assert!(schema["properties"]["cover_letter"]["properties"]["sender"].is_object());
+        assert!(schema["$defs"]["ContactInfo"].is_object());
     }
 
     #[test]
@@ -1082,6 +3342,28 @@ mod tests {
     }
 
     // ... existing validate tests ...
+    #[test]
+    fn test_strict_validation_enforces_schema_formats() {
+        let resume = serde_json::json!({
+            "basics": { "name": "John Doe", "email": "not-an-email" },
+            "work": [{ "company": "Tech Corp", "position": "Engineer" }]
+        });
+
+        // Serde accepts any string as an email
+        let lenient = validate_resume(serde_json::json!({ "resume": resume }));
+        assert!(matches!(lenient, ValidationResult::Valid { .. }));
+
+        let strict = validate_resume(serde_json::json!({ "resume": resume, "strict": true }));
+        match strict {
+            ValidationResult::Invalid { errors } => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].path, "basics.email");
+                assert_eq!(errors[0].pointer.as_deref(), Some("/basics/email"));
+            }
+            ValidationResult::Valid { .. } => panic!("Expected a format violation"),
+        }
+    }
+
     #[test]
     fn test_validate_valid_resume() {
         let input = serde_json::json!({
@@ -1102,7 +3384,7 @@ mod tests {
         let result = validate_resume(input);
 
         match result {
-            ValidationResult::Valid { resume } => {
+            ValidationResult::Valid { resume, .. } => {
                 assert_eq!(resume.basics.name, "John Doe");
                 assert_eq!(resume.basics.email, "john@example.com");
             }
@@ -1112,6 +3394,56 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_validate_resume_batch_isolates_errors() {
+        let context = ToolContext::stdio();
+        let valid = serde_json::json!({
+            "basics": { "name": "John Doe", "email": "john@example.com" },
+            "work": []
+        });
+        let input = serde_json::json!({
+            "resumes": [valid.clone(), { "basics": { "name": "No Email" } }, valid]
+        });
+
+        let result = call_tool(VALIDATE_RESUME_BATCH_TOOL, input, &context)
+            .await
+            .unwrap();
+        let results = result["results"].as_array().unwrap();
+
+        assert_eq!(results.len(), 3);
+        for (i, item) in results.iter().enumerate() {
+            assert_eq!(item["index"], i);
+        }
+        assert_eq!(results[0]["status"], "valid");
+        assert_eq!(results[1]["status"], "invalid");
+        assert!(!results[1]["errors"].as_array().unwrap().is_empty());
+        assert_eq!(results[2]["status"], "valid");
+    }
+
+    #[tokio::test]
+    async fn test_validate_resume_batch_failures_only() {
+        let context = ToolContext::stdio();
+        let valid = serde_json::json!({
+            "basics": { "name": "John Doe", "email": "john@example.com" },
+            "work": []
+        });
+        let invalid = serde_json::json!({ "basics": { "name": "No Email" } });
+        let input = serde_json::json!({
+            "resumes": [valid.clone(), valid.clone(), invalid, valid],
+            "failures_only": true
+        });
+
+        let result = call_tool(VALIDATE_RESUME_BATCH_TOOL, input, &context)
+            .await
+            .unwrap();
+        let results = result["results"].as_array().unwrap();
+
+        assert_eq!(results.len(), 1, "{:?}", results);
+        assert_eq!(results[0]["index"], 2);
+        assert_eq!(results[0]["status"], "invalid");
+        assert!(!results[0]["errors"].as_array().unwrap().is_empty());
+    }
+
     // Ensure all previous tests are kept
     #[test]
     fn test_validate_full_resume_fixture() {
@@ -1125,7 +3457,7 @@ mod tests {
         let result = validate_resume(input);
 
         match result {
-            ValidationResult::Valid { resume } => {
+            ValidationResult::Valid { resume, .. } => {
                 assert_eq!(resume.basics.name, "Jane Smith");
                 assert_eq!(resume.work.len(), 2);
             }
@@ -1135,6 +3467,114 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_resume_returns_normalized_resume() {
+        let resume = serde_json::json!({
+            "basics": {
+                "name": "  Jane Smith ",
+                "email": "jane@example.com\n",
+                "phone": "   ",
+                "profiles": [{ "network": " github", "url": "https://github.com/jane " }]
+            },
+            "work": [{
+                "company": "\tTech Corp",
+                "position": "Engineer  ",
+                "highlights": ["  Shipped the billing service  "]
+            }],
+            "skills": [{ "name": " Languages", "keywords": [" Rust ", "Go"] }]
+        });
+
+        match validate_resume(serde_json::json!({ "resume": resume.clone() })) {
+            ValidationResult::Valid { normalized, .. } => assert!(normalized.is_none()),
+            ValidationResult::Invalid { errors } => panic!("{:?}", errors),
+        }
+
+        let result = validate_resume(serde_json::json!({ "resume": resume, "normalize": true }));
+        let ValidationResult::Valid {
+            resume, normalized, ..
+        } = result
+        else {
+            panic!("Expected valid result, got {:?}", result);
+        };
+        // The echoed resume is left as submitted
+        assert_eq!(resume.basics.name, "  Jane Smith ");
+
+        let normalized = serde_json::to_value(normalized.unwrap()).unwrap();
+        assert_eq!(normalized["basics"]["name"], "Jane Smith");
+        assert_eq!(normalized["basics"]["email"], "jane@example.com");
+        assert!(normalized["basics"].get("phone").is_none());
+        assert_eq!(normalized["basics"]["profiles"][0]["network"], "GitHub");
+        assert_eq!(
+            normalized["basics"]["profiles"][0]["url"],
+            "https://github.com/jane"
+        );
+        assert_eq!(normalized["work"][0]["company"], "Tech Corp");
+        assert_eq!(normalized["work"][0]["position"], "Engineer");
+        assert_eq!(
+            normalized["work"][0]["highlights"][0],
+            "Shipped the billing service"
+        );
+        assert_eq!(
+            normalized["skills"][0]["keywords"],
+            serde_json::json!(["Rust", "Go"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_resume_estimates_pages() {
+        let fixture: Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+
+        let context = ToolContext::stdio();
+        let estimated = |input: Value| {
+            let context = &context;
+            async move {
+                match validate_resume_with_estimate(input, context).await {
+                    ValidationResult::Valid {
+                        estimated_pages, ..
+                    } => estimated_pages,
+                    ValidationResult::Invalid { errors } => {
+                        panic!("Expected valid result, got errors: {:?}", errors);
+                    }
+                }
+            }
+        };
+
+        assert_eq!(
+            estimated(serde_json::json!({ "resume": fixture })).await,
+            None
+        );
+
+        let pages = estimated(serde_json::json!({ "resume": fixture, "estimate_pages": true }))
+            .await
+            .expect("estimated_pages should be reported");
+        assert!((1..=3).contains(&pages), "{} pages", pages);
+
+        // A resume over the page limit is aborted instead of laid out in full
+        let context = ToolContext {
+            max_pages: 1,
+            ..ToolContext::stdio()
+        };
+        let highlights: Vec<String> = (1..=120)
+            .map(|i| format!("Delivered milestone {} of the platform migration", i))
+            .collect();
+        let oversized = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John Doe", "email": "john@example.com" },
+                "work": [{ "company": "Tech Corp", "position": "Engineer", "highlights": highlights }]
+            },
+            "estimate_pages": true
+        });
+        match validate_resume_with_estimate(oversized, &context).await {
+            ValidationResult::Valid {
+                estimated_pages, ..
+            } => assert_eq!(estimated_pages, None),
+            ValidationResult::Invalid { errors } => {
+                panic!("Expected valid result, got errors: {:?}", errors);
+            }
+        }
+    }
+
     #[test]
     fn test_validate_missing_basics() {
         let input = serde_json::json!({
@@ -1290,7 +3730,7 @@ mod tests {
         let result = validate_resume(input);
 
         match result {
-            ValidationResult::Valid { resume } => {
+            ValidationResult::Valid { resume, .. } => {
                 assert!(resume.work.is_empty());
             }
             ValidationResult::Invalid { errors } => {
@@ -1371,11 +3811,15 @@ mod tests {
                 awards: vec![],
                 languages: vec![],
                 publications: vec![],
+                custom_sections: vec![],
                 section_order: None,
                 section_titles: None,
                 show_header: None,
                 show_page_numbers: None,
             }),
+            warnings: vec![],
+            estimated_pages: None,
+            normalized: None,
         };
 
         let json = serde_json::to_string(&valid_result).unwrap();
@@ -1454,6 +3898,95 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_null_byte_in_name_is_stripped_with_warning() {
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John\u{0} Doe", "email": "john@example.com" },
+                "work": [{ "company": "Tech Corp", "position": "Engineer" }]
+            }
+        });
+
+        match validate_resume(input) {
+            ValidationResult::Valid {
+                resume, warnings, ..
+            } => {
+                assert_eq!(resume.basics.name, "John Doe");
+                let warning = warnings
+                    .iter()
+                    .find(|w| w.path == "basics.name")
+                    .expect("Expected a warning for basics.name");
+                assert!(warning.message.contains("U+0000"), "{}", warning.message);
+            }
+            ValidationResult::Invalid { errors } => panic!("Unexpected errors: {:?}", errors),
+        }
+    }
+
+    #[test]
+    fn test_truncated_resume_json_has_friendly_error() {
+        let full = r#"{"basics": {"name": "John Doe", "email": "john@example.com"}, "work": []}"#;
+
+        for text in [
+            full[..full.len() - 12].to_string(),
+            format!("{} {{}}", full),
+        ] {
+            match validate_resume(serde_json::json!({ "resume": &text })) {
+                ValidationResult::Invalid { errors } => {
+                    assert_eq!(errors.len(), 1);
+                    assert_eq!(errors[0].path, "resume");
+                    assert!(
+                        errors[0]
+                            .message
+                            .starts_with("The resume JSON appears incomplete or has extra content"),
+                        "{}",
+                        errors[0].message
+                    );
+                }
+                ValidationResult::Valid { .. } => panic!("Expected an error for {}", text),
+            }
+        }
+
+        // Complete JSON text is accepted like an inline object
+        assert!(matches!(
+            validate_resume(serde_json::json!({ "resume": full })),
+            ValidationResult::Valid { .. }
+        ));
+    }
+
+    #[test]
+    fn test_missing_field_error_has_schema_hint() {
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John Doe" },
+                "work": []
+            }
+        });
+
+        match validate_resume(input) {
+            ValidationResult::Invalid { errors } => {
+                assert_eq!(errors[0].message, "Missing required field: email");
+                let hint = errors[0].hint.as_deref().expect("Expected a hint");
+                assert!(hint.contains("Professional email"), "{}", hint);
+            }
+            ValidationResult::Valid { .. } => panic!("Expected missing email"),
+        }
+
+        // Missing fields inside array items are found too
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John Doe", "email": "john@example.com" },
+                "work": [{ "company": "Tech Corp" }]
+            }
+        });
+        match validate_resume(input) {
+            ValidationResult::Invalid { errors } => {
+                let hint = errors[0].hint.as_deref().unwrap_or_default();
+                assert!(hint.contains("title"), "{}", hint);
+            }
+            ValidationResult::Valid { .. } => panic!("Expected missing position"),
+        }
+    }
+
     #[test]
     fn test_validate_fixture_wrong_type_work() {
         let fixture = include_str!("../../tests/fixtures/invalid_wrong_type_work.json");
@@ -1527,7 +4060,7 @@ mod tests {
         let result = generate_resume(input, &context).await;
 
         match result {
-            GenerationResult::Success { file_path, download_url, message } => {
+            GenerationResult::Success { file_path, download_url, message, .. } => {
                 assert_eq!(file_path, Some("test-generate-resume-valid.pdf".to_string()));
                 assert_eq!(download_url, None); // stdio mode doesn't have download URL
                 assert!(message.contains("successfully"));
@@ -1563,6 +4096,7 @@ mod tests {
             GenerationResult::Error {
                 message,
                 validation_errors,
+                ..
             } => {
                 assert!(message.contains("Validation failed"));
                 assert!(validation_errors.is_some());
@@ -1622,7 +4156,7 @@ mod tests {
         let result = generate_resume(input, &context).await;
 
         match result {
-            GenerationResult::Success { file_path, download_url, message } => {
+            GenerationResult::Success { file_path, download_url, message, .. } => {
                 assert_eq!(file_path, Some("custom-resume.pdf".to_string()));
                 assert!(message.contains("custom-resume.pdf"));
                 assert_eq!(download_url, None); // stdio mode
@@ -1640,32 +4174,1630 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_generate_resume_default_filename() {
-        let context = ToolContext::stdio();
+    async fn test_generate_resume_sanitizes_stored_filename() {
+        let storage = FileStorage::new();
+        let context = ToolContext::http(storage.clone(), "http://localhost:3000".to_string());
         let input = serde_json::json!({
             "resume": {
-                "basics": {
-                    "name": "Alice Wonder",
-                    "email": "alice@example.com"
-                },
+                "basics": { "name": "Jane Smith", "email": "jane@example.com" },
                 "work": []
-            }
+            },
+            "filename": "../../reports/jane\\final:v2"
         });
 
-        let result = generate_resume(input, &context).await;
+        match generate_resume(input, &context).await {
+            GenerationResult::Success { download_url, .. } => {
+                let url = download_url.expect("HTTP mode returns a download URL");
+                let id = uuid::Uuid::parse_str(url.rsplit('/').next().unwrap()).unwrap();
+                let file = storage.retrieve(&id).await.expect("file was stored");
+                assert_eq!(file.filename, "reports_jane_final_v2.pdf");
+            }
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success, got error: {}", message);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ttl_seconds_shortens_download_lifetime() {
+        let storage = FileStorage::new();
+        let context = ToolContext::http(storage.clone(), "http://localhost:3000".to_string());
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "Jane Smith", "email": "jane@example.com" },
+                "work": []
+            },
+            "ttl_seconds": 120
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Success {
+                download_url,
+                message,
+                ..
+            } => {
+                assert!(message.contains("expire in 2 minutes"), "{}", message);
+                let url = download_url.expect("HTTP mode returns a download URL");
+                let id = uuid::Uuid::parse_str(url.rsplit('/').next().unwrap()).unwrap();
+                let file = storage.retrieve(&id).await.expect("file was stored");
+                let lifetime = file.expires_at.duration_since(file.created_at).unwrap();
+                assert_eq!(lifetime, Duration::from_secs(120));
+            }
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success, got error: {}", message);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_id_returns_the_same_url() {
+        let storage = FileStorage::new();
+        let context = ToolContext::http(storage.clone(), "http://localhost:3000".to_string());
+        let input = |deterministic_id| {
+            serde_json::json!({
+                "resume": {
+                    "basics": { "name": "Jane Smith", "email": "jane@example.com" },
+                    "work": []
+                },
+                "deterministic_id": deterministic_id
+            })
+        };
+        let download_url = |result: GenerationResult| match result {
+            GenerationResult::Success { download_url, .. } => download_url.unwrap(),
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success, got error: {}", message);
+            }
+        };
+
+        let first = download_url(generate_resume(input(true), &context).await);
+        let second = download_url(generate_resume(input(true), &context).await);
+        assert_eq!(first, second);
+        assert_eq!(storage.count().await, 1);
+
+        let random = download_url(generate_resume(input(false), &context).await);
+        assert_ne!(random, first);
+    }
+
+    #[test]
+    fn test_validate_cover_letter_rejects_empty_body() {
+        let letter = |body: Value| {
+            serde_json::json!({
+                "cover_letter": {
+                    "sender": { "name": "Jane Doe", "email": "jane@example.com" },
+                    "recipient": { "company": "Tech Corp" },
+                    "opening": "Opening paragraph.",
+                    "body": body,
+                    "closing": "Closing paragraph."
+                }
+            })
+        };
+
+        for body in [serde_json::json!([]), serde_json::json!(["", "  \n"])] {
+            match validate_cover_letter(letter(body)) {
+                CoverLetterValidationResult::Invalid { errors } => {
+                    assert_eq!(errors.len(), 1);
+                    assert_eq!(errors[0].path, "body");
+                    assert!(
+                        errors[0]
+                            .message
+                            .contains("at least one non-empty paragraph"),
+                        "{}",
+                        errors[0].message
+                    );
+                }
+                CoverLetterValidationResult::Valid { .. } => {
+                    panic!("Expected an empty body to be rejected");
+                }
+            }
+        }
+
+        assert!(matches!(
+            validate_cover_letter(letter(serde_json::json!(["", "Body paragraph."]))),
+            CoverLetterValidationResult::Valid { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stored_cover_letter_uses_its_filename_pattern() {
+        let storage = FileStorage::new();
+        let context = ToolContext::http(storage.clone(), "http://localhost:3000".to_string());
+        let input = serde_json::json!({
+            "cover_letter": {
+                "sender": { "name": "Jane Doe", "email": "jane@example.com" },
+                "recipient": { "company": "Tech Corp" },
+                "opening": "I am writing to apply for the position.",
+                "body": ["I have relevant experience."],
+                "closing": "Thank you for your consideration."
+            }
+        });
+
+        match generate_cover_letter(input, &context).await {
+            GenerationResult::Success { download_url, .. } => {
+                let url = download_url.expect("HTTP mode returns a download URL");
+                let id = uuid::Uuid::parse_str(url.rsplit('/').next().unwrap()).unwrap();
+                let file = storage.retrieve(&id).await.expect("file was stored");
+                assert_eq!(file.filename, "Jane_Doe_Cover_Letter.pdf");
+                assert_eq!(file.mime_type, DocumentType::COVER_LETTER.mime_type);
+            }
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success, got error: {}", message);
+            }
+        }
+
+        let registry = get_document_types();
+        assert_eq!(
+            registry["document_types"][1]["default_filename"],
+            "*_Cover_Letter.pdf"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stored_files_are_listed_per_session() {
+        let storage = FileStorage::new();
+        let context = ToolContext {
+            session_id: Some("session-a".to_string()),
+            ..ToolContext::http(storage.clone(), "http://localhost:3000".to_string())
+        };
+
+        for name in ["Jane Smith", "John Doe"] {
+            let input = serde_json::json!({
+                "resume": {
+                    "basics": { "name": name, "email": "someone@example.com" },
+                    "work": []
+                }
+            });
+            assert!(matches!(
+                generate_resume(input, &context).await,
+                GenerationResult::Success { .. }
+            ));
+        }
+
+        let mut filenames: Vec<String> = storage
+            .list_session("session-a")
+            .await
+            .into_iter()
+            .map(|f| f.filename)
+            .collect();
+        filenames.sort();
+        assert_eq!(filenames, vec!["Jane_Smith_Resume.pdf", "John_Doe_Resume.pdf"]);
+
+        assert!(storage.list_session("session-b").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_download_url_uses_public_base_url() {
+        let base = crate::base_url::BaseUrl::Fixed("https://docs.example.com".to_string());
+        let context = ToolContext::http(FileStorage::new(), base.resolve(None));
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "Jane Smith", "email": "jane@example.com" },
+                "work": []
+            }
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Success { download_url, .. } => {
+                let url = download_url.expect("HTTP mode returns a download URL");
+                assert!(url.starts_with("https://docs.example.com/files/"), "{}", url);
+            }
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success, got error: {}", message);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(
+            sanitize_filename("custom-resume.pdf").as_deref(),
+            Some("custom-resume.pdf")
+        );
+        assert_eq!(
+            sanitize_filename("My Resume.PDF").as_deref(),
+            Some("My Resume.pdf")
+        );
+        assert_eq!(sanitize_filename("..").as_deref(), None);
+        assert_eq!(
+            sanitize_filename("/etc/passwd").as_deref(),
+            Some("etc_passwd.pdf")
+        );
+        assert_eq!(
+            sanitize_filename("a\"b<c>.pdf").as_deref(),
+            Some("a_b_c_.pdf")
+        );
+        assert_eq!(
+            DocumentType::RESUME.default_filename("Jane  Smith"),
+            "Jane_Smith_Resume.pdf"
+        );
+        assert_eq!(
+            DocumentType::RESUME.default_filename("José O'Neil"),
+            "José_ONeil_Resume.pdf"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_default_filename() {
+        let context = ToolContext::stdio();
+        let input = serde_json::json!({
+            "resume": {
+                "basics": {
+                    "name": "Alice Wonder",
+                    "email": "alice@example.com"
+                },
+                "work": []
+            }
+        });
+
+        let result = generate_resume(input, &context).await;
 
         match result {
             GenerationResult::Success { file_path, download_url, .. } => {
                 // Should generate filename from name
-                assert_eq!(file_path, Some("alice-wonder-resume.pdf".to_string()));
+                assert_eq!(file_path, Some("Alice_Wonder_Resume.pdf".to_string()));
                 assert_eq!(download_url, None); // stdio mode
 
                 // Clean up
-                let _ = fs::remove_file("alice-wonder-resume.pdf");
+                let _ = fs::remove_file("Alice_Wonder_Resume.pdf");
+            }
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success, got error: {}", message);
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_compatibility_partial_resume() {
+        let input = serde_json::json!({
+            "document_type": "resume",
+            "document": {
+                "basics": {
+                    "name": "Jane Smith",
+                    "phone": "+1-555-123-4567",
+                    "twitter": "@janesmith"
+                },
+                "jobs": [],
+                "skills": [
+                    { "name": "Languages", "keywords": ["Rust"], "level": "expert" }
+                ]
+            }
+        });
+
+        let report = check_compatibility(input).expect("Check should not fail");
+
+        assert!(!report.compatible);
+        assert!(report.matched.contains(&"basics.name".to_string()));
+        assert!(report.matched.contains(&"basics.phone".to_string()));
+        assert!(report.matched.contains(&"skills[0].keywords".to_string()));
+        assert!(report.unknown.contains(&"basics.twitter".to_string()));
+        assert!(report.unknown.contains(&"jobs".to_string()));
+        assert!(report.unknown.contains(&"skills[0].level".to_string()));
+        assert!(report.missing.contains(&"basics.email".to_string()));
+        assert!(report.missing.contains(&"work".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_get_typescript_types_tool() {
+        let context = ToolContext::stdio();
+
+        let result = call_tool(GET_TYPESCRIPT_TYPES_TOOL, serde_json::json!({}), &context)
+            .await
+            .unwrap();
+        assert_eq!(result["document_type"], "resume");
+        let ts = result["typescript"].as_str().unwrap();
+        assert!(ts.contains("export interface Resume {"));
+        assert!(ts.contains("  basics: Basics;"));
+
+        let input = serde_json::json!({ "document_type": "cover_letter" });
+        let result = call_tool(GET_TYPESCRIPT_TYPES_TOOL, input, &context)
+            .await
+            .unwrap();
+        assert!(
+            result["typescript"]
+                .as_str()
+                .unwrap()
+                .contains("export interface CoverLetter {")
+        );
+
+        let input = serde_json::json!({ "document_type": "invoice" });
+        assert!(
+            call_tool(GET_TYPESCRIPT_TYPES_TOOL, input, &context)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_style_options_matches_implemented_values() {
+        let result = call_tool(
+            LIST_STYLE_OPTIONS_TOOL,
+            serde_json::json!({}),
+            &ToolContext::stdio(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            result["emphasis"]["values"],
+            serde_json::json!(["experienced", "student"])
+        );
+        assert_eq!(result["summary_placement"]["default"], "header");
+        assert_eq!(
+            result["style_overrides"]["heading_weight"]["default"],
+            "bold"
+        );
+
+        // Every listed value is accepted by generate_resume's option parsing
+        for option in [
+            "icon_style",
+            "summary_placement",
+            "summary_style",
+            "skills_style",
+            "contact_style",
+            "language_style",
+            "divider_style",
+            "density",
+        ] {
+            for value in result[option]["values"].as_array().unwrap() {
+                let options: RenderOptions =
+                    serde_json::from_value(serde_json::json!({ option: value })).unwrap();
+                assert_eq!(serde_json::to_value(&options).unwrap()[option], *value);
+            }
+        }
+        for value in result["style_overrides"]["heading_weight"]["values"]
+            .as_array()
+            .unwrap()
+        {
+            assert!(serde_json::from_value::<HeadingWeight>(value.clone()).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_server_instructions_list_registered_document_types() {
+        let instructions = server_instructions();
+
+        assert!(
+            instructions.contains("- Resume ('resume')"),
+            "{}",
+            instructions
+        );
+        assert!(instructions.contains("'generate_resume'"));
+        assert!(instructions.contains("- Cover Letter ('cover_letter')"));
+        assert!(instructions.contains("'generate_cover_letter'"));
+        // Planned types are not advertised
+        assert!(!instructions.contains("'cv'"));
+    }
+
+    #[tokio::test]
+    async fn test_inline_pdf_is_returned_as_embedded_resource() {
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "Embed Tester", "email": "embed@example.com" },
+                "work": []
+            },
+            "formats": ["pdf", "png"]
+        });
+        let result = call_tool(GENERATE_RESUME_TOOL, input, &ToolContext::stdio()).await;
+        let call_result = into_call_tool_result(GENERATE_RESUME_TOOL, result);
+
+        let blob = call_result
+            .content
+            .iter()
+            .find_map(|content| match &content.as_resource()?.resource {
+                ResourceContents::BlobResourceContents {
+                    uri,
+                    mime_type,
+                    blob,
+                    ..
+                } => {
+                    assert_eq!(uri, GENERATED_PDF_URI);
+                    assert_eq!(mime_type.as_deref(), Some("application/pdf"));
+                    Some(blob.clone())
+                }
+                _ => None,
+            })
+            .expect("Expected an embedded PDF resource");
+        assert!(BASE64.decode(blob).unwrap().starts_with(b"%PDF"));
+
+        // The PDF is no longer duplicated as base64 in the JSON; other formats stay
+        let structured = call_result.structured_content.unwrap();
+        assert!(structured["outputs"].get("pdf").is_none());
+        assert!(structured["outputs"]["png"].is_string());
+    }
+
+    #[test]
+    fn test_field_aliases_pass_compatibility_and_strict_checks() {
+        let resume = serde_json::json!({
+            "basics": { "name": "John Doe", "email": "john@example.com" },
+            "work": [{ "name": "Tech Corp", "jobTitle": "Engineer" }],
+            "education": [{ "school": "State University", "studyType": "BS" }]
+        });
+
+        let report = check_compatibility(serde_json::json!({
+            "document_type": "resume",
+            "document": resume
+        }))
+        .expect("Check should not fail");
+        assert!(report.compatible, "{:?}", report);
+        assert!(report.unknown.is_empty());
+        assert!(report.matched.contains(&"work[0].company".to_string()));
+        assert!(report.matched.contains(&"work[0].position".to_string()));
+        assert!(report.matched.contains(&"education[0].institution".to_string()));
+
+        let strict = validate_resume(serde_json::json!({ "resume": resume, "strict": true }));
+        match strict {
+            ValidationResult::Valid { resume, .. } => {
+                assert_eq!(resume.work[0].position, "Engineer");
+            }
+            ValidationResult::Invalid { errors } => {
+                panic!("Expected aliases to validate, got errors: {:?}", errors);
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_compatibility_unknown_document_type() {
+        let input = serde_json::json!({
+            "document_type": "invoice",
+            "document": {}
+        });
+
+        let result = check_compatibility(input);
+        assert!(result.unwrap_err().contains("Unknown document type"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_from_source_url() {
+        use axum::{Router, routing::get};
+
+        let fixture = include_str!("../../tests/fixtures/sample_resume.json");
+        let app = Router::new().route("/resume.json", get(move || async move { fixture }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let url = format!("http://{}/resume.json", addr);
+
+        // The local test server is only reachable when private hosts are allowed
+        let mut context = ToolContext::stdio();
+        context.fetch_policy.allow_private_hosts = true;
+        let input = serde_json::json!({
+            "source_url": url,
+            "filename": "test-generate-source-url.pdf"
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Success { file_path, .. } => {
+                assert_eq!(file_path, Some("test-generate-source-url.pdf".to_string()));
+                let _ = fs::remove_file("test-generate-source-url.pdf");
+            }
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success, got error: {}", message);
+            }
+        }
+
+        // With the default policy the same URL is rejected
+        let input = serde_json::json!({ "source_url": url });
+        match generate_resume(input, &ToolContext::stdio()).await {
+            GenerationResult::Error { message, .. } => {
+                assert!(message.contains("non-public"), "Unexpected error: {}", message);
+            }
+            GenerationResult::Success { .. } => {
+                panic!("Expected loopback source_url to be rejected");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_rejects_unknown_page_break() {
+        let context = ToolContext::stdio();
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John Doe", "email": "john@example.com" },
+                "work": []
+            },
+            "page_breaks": ["publications", "hobbies"]
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Error { validation_errors, .. } => {
+                let errors = validation_errors.expect("Expected validation errors");
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].path, "page_breaks[1]");
+                assert!(errors[0].message.contains("hobbies"));
+            }
+            GenerationResult::Success { .. } => {
+                panic!("Expected error for unknown page break section");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_rejects_invalid_section_colors() {
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John Doe", "email": "john@example.com" },
+                "work": []
+            },
+            "section_colors": { "skills": "#1A73E8", "education": "blue", "hobbies": "#fff" }
+        });
+
+        match generate_resume(input, &ToolContext::stdio()).await {
+            GenerationResult::Error { validation_errors, .. } => {
+                let errors = validation_errors.expect("Expected validation errors");
+                let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+                assert_eq!(
+                    paths,
+                    vec!["section_colors.education", "section_colors.hobbies"]
+                );
+                assert!(errors[0].message.contains("'blue'"));
+                assert!(errors[1].message.contains("Unknown section"));
+            }
+            GenerationResult::Success { .. } => {
+                panic!("Expected error for invalid section colors");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_output_cache_keys_on_render_options() {
+        async fn pdf(style: Value) -> String {
+            let mut input = serde_json::json!({
+                "resume": {
+                    "basics": { "name": "Cache Tester", "email": "cache@example.com" },
+                    "work": [{ "company": "Tech Corp", "position": "Engineer" }],
+                    "skills": [{ "name": "Languages", "keywords": ["Rust"] }]
+                },
+                "formats": ["pdf"]
+            });
+            input
+                .as_object_mut()
+                .unwrap()
+                .extend(style.as_object().unwrap().clone());
+            match generate_resume(input, &ToolContext::stdio()).await {
+                GenerationResult::Success { outputs, .. } => outputs["pdf"].clone(),
+                GenerationResult::Error { message, .. } => panic!("{}", message),
+            }
+        }
+
+        let plain = pdf(serde_json::json!({})).await;
+        assert_eq!(pdf(serde_json::json!({})).await, plain);
+
+        // A cached compile must not be served for different options
+        let short_rules = pdf(serde_json::json!({ "divider_style": "short" })).await;
+        assert_ne!(short_rules, plain);
+        let no_rules = pdf(serde_json::json!({ "divider_style": "none" })).await;
+        assert_ne!(no_rules, plain);
+        assert_ne!(no_rules, short_rules);
+
+        assert_eq!(pdf(serde_json::json!({})).await, plain);
+        assert_eq!(
+            pdf(serde_json::json!({ "divider_style": "short" })).await,
+            short_rules
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_with_thumbnail() {
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "Thumbnail Tester", "email": "thumb@example.com" },
+                "work": [{ "company": "Tech Corp", "position": "Engineer" }]
+            },
+            "filename": "test-generate-thumbnail.pdf",
+            "thumbnail": true
+        });
+
+        match generate_resume(input, &ToolContext::stdio()).await {
+            GenerationResult::Success {
+                file_path,
+                thumbnail_base64,
+                ..
+            } => {
+                let file_path = file_path.expect("PDF should be saved");
+                let pdf = fs::read(&file_path).unwrap();
+                let _ = fs::remove_file(&file_path);
+                assert!(pdf.starts_with(b"%PDF"));
+
+                let png = BASE64.decode(thumbnail_base64.expect("thumbnail")).unwrap();
+                assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+            }
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success, got error: {}", message);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_multiple_formats() {
+        let context = ToolContext::stdio();
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "Format Tester", "email": "formats@example.com" },
+                "work": [{ "company": "Tech Corp", "position": "Engineer" }]
+            },
+            "formats": ["pdf", "png"]
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Success {
+                file_path,
+                download_url,
+                outputs,
+                ..
+            } => {
+                assert!(file_path.is_none() && download_url.is_none());
+                assert_eq!(outputs.len(), 2);
+
+                let pdf = BASE64.decode(&outputs["pdf"]).unwrap();
+                assert!(pdf.starts_with(b"%PDF"));
+                let text = pdf_extract::extract_text_from_mem(&pdf).unwrap();
+                assert!(text.contains("Format Tester"));
+
+                let png = BASE64.decode(&outputs["png"]).unwrap();
+                assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
             }
             GenerationResult::Error { message, .. } => {
                 panic!("Expected success, got error: {}", message);
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_strict_generation_accepts_valid_documents() {
+        let context = ToolContext::stdio();
+
+        // The generated sources themselves must compile without warnings
+        let resume = serde_json::json!({
+            "resume": {
+                "basics": { "name": "Strict Tester", "email": "strict@example.com" },
+                "work": [{ "company": "Tech Corp", "position": "Engineer" }]
+            },
+            "formats": ["pdf"],
+            "treat_warnings_as_errors": true
+        });
+        let cover_letter = serde_json::json!({
+            "cover_letter": {
+                "sender": { "name": "Strict Tester", "email": "strict@example.com" },
+                "recipient": { "company": "Tech Corp" },
+                "opening": "I am writing to apply.",
+                "body": ["I have relevant experience."],
+                "closing": "Thank you."
+            },
+            "formats": ["pdf"],
+            "treat_warnings_as_errors": true
+        });
+
+        for result in [
+            generate_resume(resume, &context).await,
+            generate_cover_letter(cover_letter, &context).await,
+        ] {
+            match result {
+                GenerationResult::Success { outputs, .. } => {
+                    assert!(BASE64.decode(&outputs["pdf"]).unwrap().starts_with(b"%PDF"));
+                }
+                GenerationResult::Error { message, .. } => {
+                    panic!("Expected success, got error: {}", message);
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preview_section_tool() {
+        let context = ToolContext::stdio();
+        let resume: Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+
+        let result = call_tool(
+            PREVIEW_SECTION_TOOL,
+            serde_json::json!({ "resume": resume.clone(), "section": "skills", "format": "pdf" }),
+            &context,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result["status"], "success");
+        let pdf = BASE64.decode(result["outputs"]["pdf"].as_str().unwrap()).unwrap();
+        let text = pdf_extract::extract_text_from_mem(&pdf).unwrap();
+        assert!(text.contains("Rust"), "skills missing: {}", text);
+        assert!(!text.contains("Tech Innovations"), "other sections rendered");
+
+        // PNG is the default format
+        let result = call_tool(
+            PREVIEW_SECTION_TOOL,
+            serde_json::json!({ "resume": resume.clone(), "section": "skills" }),
+            &context,
+        )
+        .await
+        .unwrap();
+        assert!(result["outputs"]["png"].is_string());
+
+        let result = call_tool(
+            PREVIEW_SECTION_TOOL,
+            serde_json::json!({ "resume": resume.clone(), "section": "hobbies" }),
+            &context,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result["status"], "error");
+        assert_eq!(result["validation_errors"][0]["path"], "section");
+
+        // Previews are held to the page limit like generated documents; a
+        // preview is a single auto-height page, so only a zero limit trips it
+        let context = ToolContext {
+            max_pages: 0,
+            ..ToolContext::stdio()
+        };
+        let result = call_tool(
+            PREVIEW_SECTION_TOOL,
+            serde_json::json!({ "resume": resume, "section": "skills" }),
+            &context,
+        )
+        .await
+        .unwrap();
+        assert_eq!(result["status"], "error");
+        let message = result["message"].as_str().unwrap();
+        assert!(message.contains("more than the limit of 0"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_generate_cover_letter_delivery_options() {
+        let input = serde_json::json!({
+            "cover_letter": {
+                "sender": { "name": "Jane Doe", "email": "jane@example.com" },
+                "recipient": { "company": "Tech Corp" },
+                "opening": "I am writing to apply for the position.",
+                "body": ["I have relevant experience."],
+                "closing": "Thank you for your consideration."
+            },
+            "formats": ["pdf", "png"]
+        });
+
+        match generate_cover_letter(input.clone(), &ToolContext::stdio()).await {
+            GenerationResult::Success { file_path, outputs, .. } => {
+                assert!(file_path.is_none());
+                let pdf = BASE64.decode(&outputs["pdf"]).unwrap();
+                assert!(pdf.starts_with(b"%PDF"));
+                assert!(outputs.contains_key("png"));
+            }
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success, got error: {}", message);
+            }
+        }
+
+        let (pdf, filename) = render_pdf("cover_letter", input, &ToolContext::stdio())
+            .await
+            .unwrap_or_else(|_| panic!("Expected raw rendering to succeed"));
+        assert!(pdf.starts_with(b"%PDF"));
+        assert_eq!(filename, "Jane_Doe_Cover_Letter.pdf");
+
+        assert!(render_pdf("invoice", serde_json::json!({}), &ToolContext::stdio())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_cover_letter_default_signature() {
+        let cover_letter = serde_json::json!({
+            "sender": { "name": "Jane Doe", "email": "jane@example.com" },
+            "recipient": { "company": "Tech Corp" },
+            "opening": "I am writing to apply for the position.",
+            "body": ["I have relevant experience."],
+            "closing": "Thank you for your consideration."
+        });
+
+        let render = |context: ToolContext, filename: &'static str| {
+            let input = serde_json::json!({
+                "cover_letter": cover_letter.clone(),
+                "filename": filename
+            });
+            async move {
+                match generate_cover_letter(input, &context).await {
+                    GenerationResult::Success { .. } => {
+                        let pdf_bytes = fs::read(filename).unwrap();
+                        let _ = fs::remove_file(filename);
+                        pdf_extract::extract_text_from_mem(&pdf_bytes).unwrap()
+                    }
+                    GenerationResult::Error { message, .. } => {
+                        panic!("Expected success, got error: {}", message);
+                    }
+                }
+            }
+        };
+
+        let context = ToolContext {
+            default_signature: DEFAULT_SIGNATURE.to_string(),
+            ..ToolContext::stdio()
+        };
+        let text = render(context, "test-cover-letter-default-signature.pdf").await;
+        assert!(text.contains("Sincerely,"), "Signature missing: {}", text);
+        let after_signature = &text[text.find("Sincerely,").unwrap()..];
+        assert!(after_signature.contains("Jane Doe"), "Name missing: {}", text);
+
+        let context = ToolContext {
+            default_signature: "Best regards".to_string(),
+            ..ToolContext::stdio()
+        };
+        let text = render(context, "test-cover-letter-server-signature.pdf").await;
+        assert!(text.contains("Best regards,"), "Signature missing: {}", text);
+        assert!(!text.contains("Sincerely"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_basics_only_resume() {
+        let context = ToolContext::stdio();
+        let resume = serde_json::json!({
+            "basics": {
+                "name": "Minimal Person",
+                "email": "minimal@example.com"
+            },
+            "work": []
+        });
+        let input = serde_json::json!({
+            "resume": resume.clone(),
+            "filename": "test-generate-basics-only.pdf"
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Success { warnings, .. } => {
+                assert!(warnings.iter().any(|w| w.message.contains("sparse")));
+
+                let pdf_bytes = fs::read("test-generate-basics-only.pdf").unwrap();
+                let _ = fs::remove_file("test-generate-basics-only.pdf");
+                assert!(pdf_bytes.starts_with(b"%PDF"));
+
+                let text = pdf_extract::extract_text_from_mem(&pdf_bytes).unwrap();
+                assert!(text.contains("Minimal Person"), "Name missing from PDF: {}", text);
+            }
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success, got error: {}", message);
+            }
+        }
+
+        // The minimal document should fit on a single page
+        let resume: Resume = serde_json::from_value(resume).unwrap();
+        let source = transform_resume(&resume, &RenderOptions::default()).unwrap();
+        let document = crate::typst::compiler::compile_document(source).unwrap();
+        assert_eq!(document.pages.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_ats_score_tool() {
+        let context = ToolContext::stdio();
+        let fixture: Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+
+        let input = serde_json::json!({ "resume": fixture });
+        let result = call_tool(ATS_SCORE_TOOL, input, &context).await.unwrap();
+        let score = result["score"].as_u64().unwrap();
+        assert!(score > 0 && score <= 100);
+        assert!(result["suggestions"].is_array());
+
+        let input = serde_json::json!({ "resume": {} });
+        let invalid = call_tool(ATS_SCORE_TOOL, input, &context).await.unwrap();
+        assert_eq!(invalid["status"], "invalid");
+    }
+
+    #[tokio::test]
+    async fn test_resume_completeness_tool() {
+        let context = ToolContext::stdio();
+        let fixture: Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+
+        let input = serde_json::json!({ "resume": fixture });
+        let full = call_tool(RESUME_COMPLETENESS_TOOL, input, &context)
+            .await
+            .unwrap();
+        let input = serde_json::json!({
+            "resume": { "basics": { "name": "Jane Smith", "email": "jane@example.com" }, "work": [] }
+        });
+        let minimal = call_tool(RESUME_COMPLETENESS_TOOL, input, &context)
+            .await
+            .unwrap();
+
+        assert!(full["percentage"].as_u64().unwrap() > minimal["percentage"].as_u64().unwrap());
+        assert!(!minimal["recommended"].as_array().unwrap().is_empty());
+        assert!(full["empty_sections"].is_array());
+    }
+
+    #[tokio::test]
+    async fn test_compare_resumes_combines_both_resumes() {
+        let context = ToolContext::stdio();
+        let input = serde_json::json!({
+            "left": {
+                "basics": { "name": "Priya Raman", "email": "priya@example.com" },
+                "work": [{ "company": "Quartz Labs", "position": "Engineer" }]
+            },
+            "right": {
+                "basics": { "name": "Tomas Okafor", "email": "tomas@example.com" },
+                "work": [{ "company": "Basalt Foundry", "position": "Analyst" }]
+            },
+            "divider_style": "short",
+            "formats": ["pdf"]
+        });
+
+        let result = compare_resumes(input, &context).await;
+        let GenerationResult::Success { outputs, .. } = result else {
+            panic!("Expected success, got {:?}", result);
+        };
+        let pdf = BASE64.decode(&outputs["pdf"]).unwrap();
+        let text = pdf_extract::extract_text_from_mem(&pdf).unwrap();
+        for expected in [
+            "Priya Raman",
+            "Quartz Labs",
+            "Tomas Okafor",
+            "Basalt Foundry",
+        ] {
+            assert!(text.contains(expected), "missing {}: {}", expected, text);
+        }
+
+        // Errors name the resume they came from
+        let input = serde_json::json!({
+            "left": {
+                "basics": { "name": "Priya Raman", "email": "priya@example.com" },
+                "work": [{ "company": "Quartz Labs", "position": "Engineer" }]
+            },
+            "right": { "basics": { "name": "No Email" }, "work": [] }
+        });
+        let GenerationResult::Error {
+            message,
+            validation_errors,
+            ..
+        } = compare_resumes(input, &context).await
+        else {
+            panic!("Expected an error for the invalid right-hand resume");
+        };
+        assert!(message.contains("right resume"), "{}", message);
+        assert!(
+            validation_errors
+                .unwrap()
+                .iter()
+                .all(|e| e.path.starts_with("right"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_page_numbers_option_controls_footer() {
+        let context = ToolContext::stdio();
+        let input = |page_numbers: bool| {
+            serde_json::json!({
+                "resume": {
+                    "basics": { "name": "Priya Raman", "email": "priya@example.com" },
+                    "work": [{ "company": "Quartz Labs", "position": "Engineer" }],
+                    "education": [{ "institution": "Lakeside Polytechnic", "degree": "B.S." }],
+                    "showPageNumbers": !page_numbers
+                },
+                "page_breaks": ["education"],
+                "page_numbers": page_numbers
+            })
+        };
+
+        let result = call_tool(VERIFY_RESUME_TEXT_TOOL, input(true), &context)
+            .await
+            .unwrap();
+        let text = result["text"].as_str().unwrap();
+        assert!(text.contains("Page 1 of 2"), "{}", text);
+        assert!(text.contains("Page 2 of 2"), "{}", text);
+
+        let result = call_tool(VERIFY_RESUME_TEXT_TOOL, input(false), &context)
+            .await
+            .unwrap();
+        let text = result["text"].as_str().unwrap();
+        assert!(!text.contains("Page 2 of 2"), "{}", text);
+    }
+
+    #[tokio::test]
+    async fn test_verify_resume_text_tool() {
+        let context = ToolContext::stdio();
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "Priya Raman", "email": "priya@example.com" },
+                "work": [{ "company": "Quartz Labs", "position": "Engineer" }]
+            },
+            "filename": "test-verify-text.pdf"
+        });
+
+        let result = call_tool(VERIFY_RESUME_TEXT_TOOL, input, &context)
+            .await
+            .unwrap();
+        assert_eq!(result["status"], "success");
+        let text = result["text"].as_str().unwrap();
+        assert!(text.contains("Priya Raman"), "{}", text);
+        assert!(text.contains("Quartz Labs"), "{}", text);
+        // The PDF is only extracted, never written
+        assert!(!std::path::Path::new("test-verify-text.pdf").exists());
+
+        let input = serde_json::json!({ "resume": { "basics": { "name": "No Email" } } });
+        let invalid = call_tool(VERIFY_RESUME_TEXT_TOOL, input, &context)
+            .await
+            .unwrap();
+        assert_eq!(invalid["status"], "error");
+    }
+
+    #[tokio::test]
+    async fn test_export_resume_text_tool() {
+        let context = ToolContext::stdio();
+        let fixture: Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+
+        let input = serde_json::json!({ "resume": fixture });
+        let result = call_tool(EXPORT_RESUME_TEXT_TOOL, input, &context)
+            .await
+            .unwrap();
+        assert_eq!(result["status"], "success");
+        let text = result["text"].as_str().unwrap();
+        assert!(text.contains("Jane Smith"));
+        assert!(text.contains("Tech Innovations Inc."));
+        assert!(text.contains("StartupXYZ"));
+        let education = text.find("EDUCATION").unwrap();
+        let experience = text.find("EXPERIENCE").unwrap();
+        let skills = text.find("TECHNICAL SKILLS").unwrap();
+        assert!(education < experience && experience < skills);
+
+        let input = serde_json::json!({ "resume": {} });
+        let invalid = call_tool(EXPORT_RESUME_TEXT_TOOL, input, &context)
+            .await
+            .unwrap();
+        assert_eq!(invalid["status"], "invalid");
+    }
+
+    #[tokio::test]
+    async fn test_export_resume_latex_tool() {
+        let context = ToolContext::stdio();
+        let fixture: Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+
+        let input = serde_json::json!({ "resume": fixture });
+        let result = call_tool(EXPORT_RESUME_LATEX_TOOL, input, &context)
+            .await
+            .unwrap();
+        assert_eq!(result["status"], "success");
+        let latex = result["latex"].as_str().unwrap();
+        assert!(latex.starts_with('%'));
+        assert!(latex.contains("\\documentclass"));
+        assert!(latex.contains("\\name{Jane}{Smith}"));
+        assert!(latex.trim_end().ends_with("\\end{document}"));
+
+        let input = serde_json::json!({ "resume": {} });
+        let invalid = call_tool(EXPORT_RESUME_LATEX_TOOL, input, &context)
+            .await
+            .unwrap();
+        assert_eq!(invalid["status"], "invalid");
+    }
+
+    #[tokio::test]
+    async fn test_max_highlights_per_entry_truncates_with_warning() {
+        let highlights = ["Alpha", "Bravo", "Charlie", "Delta", "Echo"]
+            .map(|word| format!("{} highlight", word));
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "Test User", "email": "test@example.com" },
+                "work": [{
+                    "company": "Tech Corp",
+                    "position": "Engineer",
+                    "startDate": "2020-01",
+                    "endDate": "2021-01",
+                    "highlights": highlights
+                }]
+            },
+            "max_highlights_per_entry": 2,
+            "formats": ["pdf"]
+        });
+
+        match generate_resume(input, &ToolContext::stdio()).await {
+            GenerationResult::Success {
+                outputs, warnings, ..
+            } => {
+                let pdf = BASE64.decode(&outputs["pdf"]).unwrap();
+                let text = pdf_extract::extract_text_from_mem(&pdf).unwrap();
+                assert!(text.contains("Alpha highlight"));
+                assert!(text.contains("Bravo highlight"));
+                assert!(!text.contains("Charlie highlight"));
+                assert!(!text.contains("Echo highlight"));
+
+                let truncation: Vec<_> = warnings
+                    .iter()
+                    .filter(|w| w.path == "work[0].highlights")
+                    .collect();
+                assert_eq!(truncation.len(), 1);
+                assert!(truncation[0].message.contains("first 2 of 5"));
+            }
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success, got error: {}", message);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_rejects_thick_rule() {
+        let context = ToolContext::stdio();
+        let fixture: Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+        let input = serde_json::json!({
+            "resume": fixture,
+            "style_overrides": { "rule_thickness": 12.0 }
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Error { validation_errors, .. } => {
+                let errors = validation_errors.expect("Expected validation errors");
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].path, "style_overrides.rule_thickness");
+            }
+            GenerationResult::Success { .. } => {
+                panic!("Expected error for out-of-range rule thickness");
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicate_key_in_content_is_flagged() {
+        let input = serde_json::json!({
+            "content": r#"{
+                "basics": { "name": "John Doe", "email": "john@example.com", "email": "jd@example.com" },
+                "work": []
+            }"#
+        });
+
+        match validate_resume(input) {
+            ValidationResult::Invalid { errors } => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].path, "content");
+                assert!(
+                    errors[0].message.contains("duplicate key `email` at basics.email"),
+                    "unexpected message: {}",
+                    errors[0].message
+                );
+            }
+            ValidationResult::Valid { .. } => panic!("Expected duplicate key to be flagged"),
+        }
+    }
+
+    #[test]
+    fn test_duplicate_key_in_string_resume_is_flagged() {
+        let input = serde_json::json!({
+            "resume": r#"{
+                "basics": { "name": "John Doe", "email": "john@example.com", "email": "jd@example.com" },
+                "work": []
+            }"#
+        });
+
+        match validate_resume(input) {
+            ValidationResult::Invalid { errors } => {
+                assert_eq!(errors.len(), 1);
+                assert!(
+                    errors[0]
+                        .message
+                        .contains("duplicate key `email` at basics.email"),
+                    "unexpected message: {}",
+                    errors[0].message
+                );
+            }
+            ValidationResult::Valid { .. } => panic!("Expected duplicate key to be flagged"),
+        }
+    }
+
+    #[test]
+    fn test_unrecognized_serde_error_has_path() {
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John Doe", "email": "john@example.com" },
+                "work": [{
+                    "company": "Tech Corp",
+                    "position": "Engineer",
+                    "highlights": ["Shipped the thing", 42]
+                }]
+            }
+        });
+
+        match validate_resume(input) {
+            ValidationResult::Invalid { errors } => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].path, "work[0].highlights[1]");
+                assert!(errors[0].message.contains("untagged enum"));
+            }
+            ValidationResult::Valid { .. } => panic!("Expected invalid highlight"),
+        }
+    }
+
+    #[test]
+    fn test_validate_resume_normalizes_profile_networks() {
+        let input = serde_json::json!({
+            "resume": {
+                "basics": {
+                    "name": "John Doe",
+                    "email": "john@example.com",
+                    "profiles": [
+                        { "network": "linkedin", "url": "https://linkedin.com/in/johndoe" },
+                        { "network": "Linked In", "url": "https://linkedin.com/in/johndoe2" },
+                        { "network": "Polywork", "url": "https://polywork.com/johndoe" }
+                    ]
+                },
+                "work": [{ "company": "Tech Corp", "position": "Engineer" }]
+            }
+        });
+
+        match validate_resume(input) {
+            ValidationResult::Valid {
+                resume, warnings, ..
+            } => {
+                let networks: Vec<&str> = resume
+                    .basics
+                    .profiles
+                    .iter()
+                    .map(|p| p.network.as_str())
+                    .collect();
+                assert_eq!(networks, vec!["LinkedIn", "LinkedIn", "Polywork"]);
+
+                assert_eq!(warnings.len(), 1);
+                assert_eq!(warnings[0].path, "basics.profiles[2].network");
+                assert!(warnings[0].message.contains("Polywork"));
+            }
+            ValidationResult::Invalid { errors } => {
+                panic!("Expected valid resume, got errors: {:?}", errors);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_rejects_unknown_summary_placement() {
+        let context = ToolContext::stdio();
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John Doe", "email": "john@example.com" },
+                "work": []
+            },
+            "summary_placement": "footer"
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Error { message, .. } => {
+                assert!(message.contains("footer"), "unexpected message: {}", message);
+            }
+            GenerationResult::Success { .. } => {
+                panic!("Expected error for unknown summary placement");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_rejects_unknown_skills_style() {
+        let context = ToolContext::stdio();
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John Doe", "email": "john@example.com" },
+                "work": []
+            },
+            "skills_style": "columns"
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Error { message, .. } => {
+                assert!(message.contains("columns"), "unexpected message: {}", message);
+            }
+            GenerationResult::Success { .. } => {
+                panic!("Expected error for unknown skills style");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_debug_compile_error_includes_source_excerpt() {
+        let prepared = |debug| PreparedDocument {
+            document_type: &DocumentType::RESUME,
+            world: DocgenWorld::new("Intro\n#undefined-function()\nOutro".to_string()),
+            export: ExportOptions::default(),
+            formats: vec![OutputFormat::Pdf],
+            filename: "test-debug-resume.pdf".to_string(),
+            warnings: vec![],
+            thumbnail: false,
+            ttl_seconds: None,
+            deterministic_id: false,
+            debug,
+        };
+
+        match deliver_document(prepared(true), &ToolContext::stdio()).await {
+            GenerationResult::Error {
+                message,
+                source_excerpt,
+                ..
+            } => {
+                assert!(message.contains("unknown variable"), "{}", message);
+                let excerpt = source_excerpt.expect("Expected a source excerpt in debug mode");
+                assert!(
+                    excerpt.contains(">2 | #undefined-function()"),
+                    "{}",
+                    excerpt
+                );
+                assert!(excerpt.contains("1 | Intro"), "{}", excerpt);
+            }
+            GenerationResult::Success { .. } => panic!("Expected a compile error"),
+        }
+
+        // Source is never returned unless asked for
+        match deliver_document(prepared(false), &ToolContext::stdio()).await {
+            GenerationResult::Error { source_excerpt, .. } => assert!(source_excerpt.is_none()),
+            GenerationResult::Success { .. } => panic!("Expected a compile error"),
+        }
+    }
+
+    /// Number of images placed in a frame
+    fn image_count(frame: &typst::layout::Frame) -> usize {
+        use typst::layout::FrameItem;
+
+        frame
+            .items()
+            .map(|(_, item)| match item {
+                FrameItem::Group(group) => image_count(&group.frame),
+                FrameItem::Image(..) => 1,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    #[tokio::test]
+    async fn test_work_logo_is_embedded() {
+        let png = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAIAAACQd1PeAAAADElEQVR4nGP4z8AAAAMBAQDJ/pLvAAAAAElFTkSuQmCC";
+        let prepare = |work: Value, assets: Value| async move {
+            let input = serde_json::json!({
+                "resume": {
+                    "basics": { "name": "John Doe", "email": "john@example.com" },
+                    "work": work
+                },
+                "assets": assets
+            });
+            let Ok(prepared) = prepare_resume(input, &ToolContext::stdio()).await else {
+                panic!("Expected the resume to prepare");
+            };
+            let document = typst::compile::<typst::layout::PagedDocument>(&prepared.world)
+                .output
+                .expect("Compilation failed");
+            (image_count(&document.pages[0].frame), prepared.warnings)
+        };
+
+        // Inline base64 on one entry; the other has no logo
+        let (images, warnings) = prepare(
+            serde_json::json!([
+                { "company": "Tech Corp", "position": "Engineer", "logo": png },
+                { "company": "Old Corp", "position": "Intern" }
+            ]),
+            serde_json::json!({}),
+        )
+        .await;
+        assert_eq!(images, 1);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+
+        // A supplied asset referenced by name
+        let (images, _) = prepare(
+            serde_json::json!([
+                { "company": "Tech Corp", "position": "Engineer", "logo": "acme.png" }
+            ]),
+            serde_json::json!({ "acme.png": png }),
+        )
+        .await;
+        assert_eq!(images, 1);
+
+        // An unusable logo is dropped with a warning instead of failing the compile
+        let (images, warnings) = prepare(
+            serde_json::json!([
+                { "company": "Tech Corp", "position": "Engineer", "logo": "missing.png" }
+            ]),
+            serde_json::json!({}),
+        )
+        .await;
+        assert_eq!(images, 0);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, "work[0].logo");
+    }
+
+    #[tokio::test]
+    async fn test_compress_shrinks_image_heavy_resume() {
+        let context = ToolContext::stdio();
+        let photo = BASE64.encode(crate::typst::assets::noise_png(1200, 900));
+        let generate = |compress: bool| {
+            let input = serde_json::json!({
+                "resume": {
+                    "basics": { "name": "John Doe", "email": "john@example.com" },
+                    "work": [
+                        { "company": "Tech Corp", "position": "Engineer", "logo": "tech.png" },
+                        { "company": "Old Corp", "position": "Intern", "logo": photo }
+                    ]
+                },
+                "assets": { "tech.png": photo },
+                "formats": ["pdf"],
+                "compress": compress
+            });
+            let context = &context;
+            async move {
+                match generate_resume(input, context).await {
+                    GenerationResult::Success { outputs, .. } => {
+                        BASE64.decode(&outputs["pdf"]).unwrap()
+                    }
+                    GenerationResult::Error { message, .. } => {
+                        panic!("Expected success, got error: {}", message);
+                    }
+                }
+            }
+        };
+
+        let plain = generate(false).await;
+        let compressed = generate(true).await;
+        assert!(compressed.starts_with(b"%PDF"));
+        assert!(
+            compressed.len() < plain.len() / 2,
+            "compressed {} bytes, uncompressed {} bytes",
+            compressed.len(),
+            plain.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_skips_corrupt_asset() {
+        let context = ToolContext::stdio();
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John Doe", "email": "john@example.com" },
+                "work": [{ "company": "Tech Corp", "position": "Engineer" }]
+            },
+            "filename": "test-corrupt-asset-resume.pdf",
+            "assets": {
+                "dot.png": "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAIAAACQd1PeAAAADElEQVR4nGP4z8AAAAMBAQDJ/pLvAAAAAElFTkSuQmCC",
+                "broken.png": "iVBORw0KGgoAAAANSUhEUgAA"
+            }
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Success { file_path, warnings, .. } => {
+                assert_eq!(warnings.len(), 1);
+                assert_eq!(warnings[0].path, "assets.broken.png");
+
+                let file_path = file_path.expect("Expected file path in stdio mode");
+                assert!(fs::metadata(&file_path).is_ok());
+                let _ = fs::remove_file(file_path);
+            }
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success despite corrupt asset, got: {}", message);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_aborts_over_page_limit() {
+        let context = ToolContext {
+            max_pages: 1,
+            ..ToolContext::stdio()
+        };
+        let highlights: Vec<String> = (1..=120)
+            .map(|i| format!("Delivered milestone {} of the platform migration", i))
+            .collect();
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John Doe", "email": "john@example.com" },
+                "work": [{ "company": "Tech Corp", "position": "Engineer", "highlights": highlights }]
+            },
+            "filename": "test-page-limit-resume.pdf"
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Error { message, .. } => {
+                assert!(message.contains("more than the limit of 1"), "{}", message);
+            }
+            GenerationResult::Success { .. } => panic!("Expected the page limit to abort"),
+        }
+        assert!(!std::path::Path::new("test-page-limit-resume.pdf").exists());
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_rejects_oversized_asset() {
+        let context = ToolContext {
+            asset_limits: AssetLimits {
+                max_asset_bytes: 1024,
+                max_total_bytes: 4096,
+            },
+            ..ToolContext::stdio()
+        };
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John Doe", "email": "john@example.com" },
+                "work": []
+            },
+            "filename": "test-oversized-asset-resume.pdf",
+            "assets": {
+                "dot.png": "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAIAAACQd1PeAAAADElEQVR4nGP4z8AAAAMBAQDJ/pLvAAAAAElFTkSuQmCC",
+                "logo.png": BASE64.encode(vec![0u8; 3000])
+            }
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Error {
+                message,
+                validation_errors,
+                ..
+            } => {
+                assert_eq!(message, "Assets exceed the size limit");
+                let errors = validation_errors.expect("Expected per-asset errors");
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].path, "assets.logo.png");
+                let error = &errors[0].message;
+                assert!(error.contains("'logo.png'"), "{}", error);
+                assert!(error.contains("limit"), "{}", error);
+            }
+            GenerationResult::Success { .. } => panic!("Expected a size-limit error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_path_traversal_asset_name_is_rejected() {
+        let input = serde_json::json!({
+            "resume": {
+                "basics": { "name": "John Doe", "email": "john@example.com" },
+                "work": []
+            },
+            "filename": "test-traversal-asset-resume.pdf",
+            "assets": {
+                "../etc/passwd": "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAIAAACQd1PeAAAADElEQVR4nGP4z8AAAAMBAQDJ/pLvAAAAAElFTkSuQmCC"
+            }
+        });
+
+        match generate_resume(input, &ToolContext::stdio()).await {
+            GenerationResult::Error {
+                message,
+                validation_errors,
+                ..
+            } => {
+                assert_eq!(message, "Invalid asset names");
+                let errors = validation_errors.expect("Expected per-asset errors");
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].path, "assets.../etc/passwd");
+                assert!(
+                    errors[0].message.contains("path separator"),
+                    "{}",
+                    errors[0].message
+                );
+            }
+            GenerationResult::Success { .. } => panic!("Expected an asset name error"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_resume_from_yaml_content() {
+        let context = ToolContext::stdio();
+        let yaml = r#"
+basics:
+  name: Yaml User
+  email: yaml@example.com
+work:
+  - company: Tech Corp
+    position: Engineer
+    startDate: 2020-01
+    endDate: Present
+    highlights:
+      - Kept resume data in YAML
+"#;
+        let input = serde_json::json!({
+            "format": "yaml",
+            "content": yaml,
+            "filename": "test-yaml-resume.pdf"
+        });
+
+        match generate_resume(input, &context).await {
+            GenerationResult::Success { file_path, .. } => {
+                let file_path = file_path.expect("Expected file path in stdio mode");
+                assert!(fs::metadata(&file_path).is_ok());
+                let _ = fs::remove_file(file_path);
+            }
+            GenerationResult::Error { message, .. } => {
+                panic!("Expected success from YAML content, got: {}", message);
+            }
+        }
+
+        let malformed = serde_json::json!({ "format": "toml", "content": "basics = " });
+        match generate_resume(malformed, &context).await {
+            GenerationResult::Error { message, .. } => {
+                assert!(message.starts_with("Invalid TOML content"), "{}", message);
+            }
+            GenerationResult::Success { .. } => panic!("Expected malformed TOML to fail"),
+        }
+    }
 }
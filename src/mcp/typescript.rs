@@ -0,0 +1,229 @@
+//! TypeScript declarations generated from the document schemas
+//!
+//! Frontends integrating the server usually want typed interfaces rather than
+//! raw JSON Schema. This walks the schemars output for a document type and
+//! emits one declaration per definition: an `interface` for object schemas and
+//! a `type` alias for unions and enums. Field descriptions become doc comments.
+
+use serde_json::{Map, Value};
+
+/// Convert a generated root schema into TypeScript declarations
+///
+/// The root schema becomes `interface <title>`; every entry under `$defs`
+/// becomes its own declaration, referenced by name.
+pub fn schema_to_typescript(schema: &Value) -> String {
+    let title = schema
+        .get("title")
+        .and_then(Value::as_str)
+        .unwrap_or("Document");
+
+    let mut declarations = vec![declaration(title, schema)];
+    if let Some(defs) = schema.get("$defs").and_then(Value::as_object) {
+        for (name, def) in defs {
+            declarations.push(declaration(name, def));
+        }
+    }
+
+    let mut output = declarations.join("\n\n");
+    output.push('\n');
+    output
+}
+
+/// One exported declaration, with the schema description as a doc comment
+fn declaration(name: &str, schema: &Value) -> String {
+    let mut out = doc_comment(schema, "");
+    match schema.get("properties").and_then(Value::as_object) {
+        Some(properties) => {
+            out.push_str(&format!("export interface {} ", name));
+            out.push_str(&object_body(schema, properties, ""));
+        }
+        None => out.push_str(&format!(
+            "export type {} = {};",
+            name,
+            type_expr(schema, "")
+        )),
+    }
+    out
+}
+
+/// `{ ... }` listing each property, marking non-required ones optional
+fn object_body(schema: &Value, properties: &Map<String, Value>, indent: &str) -> String {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let inner = format!("{}  ", indent);
+
+    let mut out = String::from("{\n");
+    for (name, property) in properties {
+        out.push_str(&doc_comment(property, &inner));
+        let optional = if required.contains(&name.as_str()) {
+            ""
+        } else {
+            "?"
+        };
+        out.push_str(&format!(
+            "{}{}{}: {};\n",
+            inner,
+            property_name(name),
+            optional,
+            type_expr(property, &inner)
+        ));
+    }
+    out.push_str(indent);
+    out.push('}');
+    out
+}
+
+/// TypeScript type for a schema
+fn type_expr(schema: &Value, indent: &str) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return reference
+            .rsplit('/')
+            .next()
+            .unwrap_or("unknown")
+            .to_string();
+    }
+    if let Some(value) = schema.get("const") {
+        return value.to_string();
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return union(values.iter().map(Value::to_string).collect());
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+            return union(variants.iter().map(|v| type_expr(v, indent)).collect());
+        }
+    }
+
+    match schema.get("type") {
+        Some(Value::String(kind)) => primitive(kind, schema, indent),
+        Some(Value::Array(kinds)) => union(
+            kinds
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|kind| primitive(kind, schema, indent))
+                .collect(),
+        ),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// TypeScript type for a single JSON Schema `type`
+fn primitive(kind: &str, schema: &Value, indent: &str) -> String {
+    match kind {
+        "string" => "string".to_string(),
+        "integer" | "number" => "number".to_string(),
+        "boolean" => "boolean".to_string(),
+        "null" => "null".to_string(),
+        "array" => {
+            let item = schema
+                .get("items")
+                .map(|items| type_expr(items, indent))
+                .unwrap_or_else(|| "unknown".to_string());
+            if item.contains(' ') && !item.starts_with('{') {
+                format!("({})[]", item)
+            } else {
+                format!("{}[]", item)
+            }
+        }
+        "object" => match schema.get("properties").and_then(Value::as_object) {
+            Some(properties) => object_body(schema, properties, indent),
+            None => {
+                let value = match schema.get("additionalProperties") {
+                    Some(Value::Object(_)) => type_expr(&schema["additionalProperties"], indent),
+                    _ => "unknown".to_string(),
+                };
+                format!("Record<string, {}>", value)
+            }
+        },
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Join alternatives with `|`, dropping repeated neighbours
+fn union(mut members: Vec<String>) -> String {
+    members.dedup();
+    match members.len() {
+        0 => "unknown".to_string(),
+        _ => members.join(" | "),
+    }
+}
+
+/// Property names that aren't valid identifiers are quoted
+fn property_name(name: &str) -> String {
+    let identifier = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if identifier {
+        name.to_string()
+    } else {
+        format!("{:?}", name)
+    }
+}
+
+/// `/** description */` line for a schema, if it has one
+fn doc_comment(schema: &Value, indent: &str) -> String {
+    match schema.get("description").and_then(Value::as_str) {
+        Some(description) => format!(
+            "{}/** {} */\n",
+            indent,
+            description.replace("*/", "* /").replace('\n', " ")
+        ),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::{CoverLetter, Resume};
+
+    fn typescript_for(schema: schemars::Schema) -> String {
+        schema_to_typescript(&schema.to_value())
+    }
+
+    #[test]
+    fn test_resume_interfaces() {
+        let ts = typescript_for(schemars::schema_for!(Resume));
+
+        assert!(ts.contains("export interface Resume {"));
+        assert!(ts.contains("  basics: Basics;"));
+        assert!(ts.contains("  work: WorkExperience[];"));
+        assert!(ts.contains("export interface Basics {"));
+        assert!(ts.contains("  email: string;"));
+        assert!(ts.contains("  phone?: string | null;"));
+        assert!(ts.contains("export type Highlight = string | {"));
+        assert!(ts.contains("sectionTitles?: Record<string, string> | null;"));
+    }
+
+    #[test]
+    fn test_cover_letter_interfaces() {
+        let ts = typescript_for(schemars::schema_for!(CoverLetter));
+
+        assert!(ts.contains("export interface CoverLetter {"));
+        assert!(ts.contains("  body: string[];"));
+    }
+
+    #[test]
+    fn test_enums_and_quoted_names() {
+        let schema = serde_json::json!({
+            "title": "Options",
+            "type": "object",
+            "properties": {
+                "style": { "enum": ["inline", "grouped"] },
+                "odd-name": { "type": "array", "items": { "type": ["string", "null"] } }
+            },
+            "required": ["style"]
+        });
+        let ts = schema_to_typescript(&schema);
+
+        assert!(ts.contains("  style: \"inline\" | \"grouped\";"));
+        assert!(ts.contains("  \"odd-name\"?: (string | null)[];"));
+    }
+}
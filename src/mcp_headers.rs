@@ -0,0 +1,159 @@
+//! Header checks for the Streamable HTTP MCP endpoint
+//!
+//! The MCP transport requires clients to POST JSON-RPC messages as
+//! `application/json` and to accept both `application/json` and
+//! `text/event-stream` responses; a GET opens an event stream and must accept
+//! `text/event-stream`. Requests that miss these are answered here with a
+//! status and a message naming the header, the expected value, and what was
+//! received, instead of an opaque failure further down the stack.
+
+use axum::extract::Request;
+use axum::http::{HeaderMap, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+const JSON: &str = "application/json";
+const EVENT_STREAM: &str = "text/event-stream";
+
+/// Middleware rejecting `/mcp` requests with unusable `Content-Type` or `Accept` headers
+pub async fn require_mcp_headers(request: Request, next: Next) -> Response {
+    match check(request.method(), request.headers()) {
+        Ok(()) => next.run(request).await,
+        Err((status, message)) => (status, message).into_response(),
+    }
+}
+
+/// Check the headers an MCP request with `method` must carry
+fn check(method: &Method, headers: &HeaderMap) -> Result<(), (StatusCode, String)> {
+    let accept = header_value(headers, header::ACCEPT);
+
+    if method == Method::POST {
+        let content_type = header_value(headers, header::CONTENT_TYPE);
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+        if !essence.eq_ignore_ascii_case(JSON) {
+            return Err((
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!(
+                    "Unsupported Content-Type {}: MCP messages must be JSON-RPC sent with 'Content-Type: {}'",
+                    describe(&content_type),
+                    JSON
+                ),
+            ));
+        }
+        if !accepts(&accept, JSON) || !accepts(&accept, EVENT_STREAM) {
+            return Err((
+                StatusCode::NOT_ACCEPTABLE,
+                format!(
+                    "Not Acceptable: Accept was {}, but POST /mcp may answer with JSON or an event stream, so send 'Accept: {}, {}'",
+                    describe(&accept),
+                    JSON,
+                    EVENT_STREAM
+                ),
+            ));
+        }
+    } else if method == Method::GET && !accepts(&accept, EVENT_STREAM) {
+        return Err((
+            StatusCode::NOT_ACCEPTABLE,
+            format!(
+                "Not Acceptable: Accept was {}, but GET /mcp opens an event stream, so send 'Accept: {}'",
+                describe(&accept),
+                EVENT_STREAM
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// A header's value, or an empty string if it is missing or not text
+fn header_value(headers: &HeaderMap, name: header::HeaderName) -> String {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Whether an `Accept` header lists `mime` explicitly
+///
+/// Wildcards are not enough: the transport chooses between JSON and an event
+/// stream per response, so clients must be able to handle both by name.
+fn accepts(accept: &str, mime: &str) -> bool {
+    accept.split(',').any(|range| {
+        range
+            .split(';')
+            .next()
+            .is_some_and(|essence| essence.trim().eq_ignore_ascii_case(mime))
+    })
+}
+
+/// A received header value quoted for an error message
+fn describe(value: &str) -> String {
+    if value.is_empty() {
+        "missing".to_string()
+    } else {
+        format!("'{}'", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(header::HeaderName, &str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.clone(), value.parse().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_post_requires_json_and_both_accept_types() {
+        let valid = headers(&[
+            (header::CONTENT_TYPE, "application/json; charset=utf-8"),
+            (header::ACCEPT, "application/json, text/event-stream"),
+        ]);
+        assert!(check(&Method::POST, &valid).is_ok());
+
+        let form = headers(&[
+            (header::CONTENT_TYPE, "application/x-www-form-urlencoded"),
+            (header::ACCEPT, "application/json, text/event-stream"),
+        ]);
+        let (status, message) = check(&Method::POST, &form).unwrap_err();
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert!(
+            message.contains("'application/x-www-form-urlencoded'"),
+            "{message}"
+        );
+        assert!(
+            message.contains("Content-Type: application/json"),
+            "{message}"
+        );
+
+        let json_only = headers(&[
+            (header::CONTENT_TYPE, "application/json"),
+            (header::ACCEPT, "application/json"),
+        ]);
+        let (status, message) = check(&Method::POST, &json_only).unwrap_err();
+        assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+        assert!(message.contains("text/event-stream"), "{message}");
+
+        let (status, message) = check(&Method::POST, &HeaderMap::new()).unwrap_err();
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert!(message.contains("missing"), "{message}");
+    }
+
+    #[test]
+    fn test_get_requires_event_stream() {
+        let stream = headers(&[(header::ACCEPT, "text/event-stream")]);
+        assert!(check(&Method::GET, &stream).is_ok());
+
+        let wildcard = headers(&[(header::ACCEPT, "*/*")]);
+        let (status, message) = check(&Method::GET, &wildcard).unwrap_err();
+        assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+        assert!(message.contains("'*/*'"), "{message}");
+
+        // Session termination carries no body and needs no particular headers
+        assert!(check(&Method::DELETE, &HeaderMap::new()).is_ok());
+    }
+}
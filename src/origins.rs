@@ -0,0 +1,260 @@
+//! Origin allowlist (CORS) for the HTTP transport
+//!
+//! When `DOCGEN_ALLOWED_ORIGINS` is set, browser requests, i.e. those with an
+//! `Origin` header, are only accepted from the listed origins. Others get
+//! `403 Forbidden`, which also guards against DNS rebinding. Accepted origins
+//! are echoed in `Access-Control-Allow-Origin`, and their preflight requests
+//! are answered directly. Requests without an `Origin` header, such as those
+//! from non-browser MCP clients, are not affected.
+//!
+//! Each entry is a `scheme://host[:port]` origin. A host may start with `*.`
+//! to accept any subdomain: `https://*.claude.ai` accepts
+//! `https://foo.claude.ai` and `https://a.b.claude.ai`, but not
+//! `https://claude.ai` itself.
+
+use axum::extract::{Request, State};
+use axum::http::{HeaderValue, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+/// Environment variable holding the comma-separated accepted origins
+pub const ALLOWED_ORIGINS_ENV: &str = "DOCGEN_ALLOWED_ORIGINS";
+
+/// Methods browsers may use, as announced in preflight responses
+const ALLOWED_METHODS: &str = "GET, POST, DELETE, OPTIONS";
+
+/// Response headers browser clients need to read
+const EXPOSED_HEADERS: &str = "Mcp-Session-Id, Content-Disposition, Retry-After";
+
+/// The parts of an origin that are compared, lower-cased
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Origin {
+    scheme: String,
+    host: String,
+    port: Option<u16>,
+}
+
+impl Origin {
+    fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim().to_ascii_lowercase();
+        let (scheme, authority) = value
+            .split_once("://")
+            .ok_or_else(|| format!("'{}' is not an origin (expected scheme://host)", value))?;
+        // The colons inside a bracketed IPv6 host don't start a port
+        let (host, port) = match authority
+            .rsplit_once(':')
+            .filter(|(_, port)| !port.ends_with(']'))
+        {
+            Some((host, port)) => {
+                let port = port
+                    .parse()
+                    .map_err(|_| format!("'{}' has an invalid port", value))?;
+                (host, Some(port))
+            }
+            None => (authority, None),
+        };
+        if scheme.is_empty() || host.is_empty() || host.contains('/') {
+            return Err(format!(
+                "'{}' is not an origin (expected scheme://host[:port])",
+                value
+            ));
+        }
+
+        Ok(Self {
+            scheme: scheme.to_string(),
+            host: host.to_string(),
+            port,
+        })
+    }
+}
+
+/// One allowlist entry: an exact origin, or all subdomains of a host
+#[derive(Debug, Clone)]
+struct OriginPattern {
+    origin: Origin,
+    /// `*.` was stripped from the host, which must be a proper subdomain of it
+    subdomains: bool,
+}
+
+impl OriginPattern {
+    fn parse(value: &str) -> Result<Self, String> {
+        let mut origin = Origin::parse(value)?;
+        let subdomains = match origin.host.strip_prefix("*.") {
+            Some(parent) => {
+                origin.host = parent.to_string();
+                true
+            }
+            None => false,
+        };
+        if origin.host.contains('*') {
+            return Err(format!(
+                "'{}' has a misplaced wildcard (only a leading '*.' is supported)",
+                value.trim()
+            ));
+        }
+        Ok(Self { origin, subdomains })
+    }
+
+    /// The entry as it would be written in the allowlist
+    fn describe(&self) -> String {
+        let wildcard = if self.subdomains { "*." } else { "" };
+        let port = self
+            .origin
+            .port
+            .map(|p| format!(":{}", p))
+            .unwrap_or_default();
+        format!(
+            "{}://{}{}{}",
+            self.origin.scheme, wildcard, self.origin.host, port
+        )
+    }
+
+    fn matches(&self, origin: &Origin) -> bool {
+        let host_matches = if self.subdomains {
+            origin
+                .host
+                .strip_suffix(&self.origin.host)
+                .and_then(|sub| sub.strip_suffix('.'))
+                .is_some_and(|sub| !sub.is_empty())
+        } else {
+            origin.host == self.origin.host
+        };
+        host_matches && origin.scheme == self.origin.scheme && origin.port == self.origin.port
+    }
+}
+
+/// Origins browser requests are accepted from
+#[derive(Debug, Clone)]
+pub struct OriginAllowlist {
+    patterns: Vec<OriginPattern>,
+}
+
+impl OriginAllowlist {
+    /// Parse a comma-separated list of origins
+    pub fn parse(list: &str) -> Result<Self, String> {
+        let patterns = list
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(OriginPattern::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if patterns.is_empty() {
+            return Err("no origins listed".to_string());
+        }
+        Ok(Self { patterns })
+    }
+
+    /// Load the allowlist from the environment, or `None` if no origins are configured
+    pub fn from_env() -> Result<Option<Self>, String> {
+        match std::env::var(ALLOWED_ORIGINS_ENV) {
+            Ok(list) if !list.trim().is_empty() => Self::parse(&list)
+                .map(Some)
+                .map_err(|e| format!("Invalid {}: {}", ALLOWED_ORIGINS_ENV, e)),
+            _ => Ok(None),
+        }
+    }
+
+    /// The accepted origins, for logging
+    pub fn describe(&self) -> String {
+        let entries: Vec<String> = self.patterns.iter().map(OriginPattern::describe).collect();
+        entries.join(", ")
+    }
+
+    /// Whether requests from `origin` are accepted
+    pub fn allows(&self, origin: &str) -> bool {
+        Origin::parse(origin)
+            .is_ok_and(|origin| self.patterns.iter().any(|pattern| pattern.matches(&origin)))
+    }
+}
+
+/// Middleware rejecting browser requests from origins outside the allowlist
+pub async fn check_origin(
+    State(allowlist): State<OriginAllowlist>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(origin) = request.headers().get(header::ORIGIN).cloned() else {
+        return next.run(request).await;
+    };
+    if !origin.to_str().is_ok_and(|o| allowlist.allows(o)) {
+        return (
+            StatusCode::FORBIDDEN,
+            format!(
+                "Origin '{}' is not allowed; add it to {} to accept it",
+                String::from_utf8_lossy(origin.as_bytes()),
+                ALLOWED_ORIGINS_ENV
+            ),
+        )
+            .into_response();
+    }
+
+    let preflight = request.method() == Method::OPTIONS
+        && request
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_REQUEST_METHOD);
+    let mut response = if preflight {
+        let mut response = StatusCode::NO_CONTENT.into_response();
+        let headers = response.headers_mut();
+        headers.insert(
+            header::ACCESS_CONTROL_ALLOW_METHODS,
+            HeaderValue::from_static(ALLOWED_METHODS),
+        );
+        if let Some(requested) = request
+            .headers()
+            .get(header::ACCESS_CONTROL_REQUEST_HEADERS)
+        {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, requested.clone());
+        }
+        response
+    } else {
+        next.run(request).await
+    };
+
+    let headers = response.headers_mut();
+    headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+    headers.insert(
+        header::ACCESS_CONTROL_EXPOSE_HEADERS,
+        HeaderValue::from_static(EXPOSED_HEADERS),
+    );
+    headers.append(header::VARY, HeaderValue::from_static("Origin"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_accepts_subdomains_only() {
+        let allowlist =
+            OriginAllowlist::parse("https://*.claude.ai, http://localhost:5173").unwrap();
+
+        assert!(allowlist.allows("https://foo.claude.ai"));
+        assert!(allowlist.allows("https://a.b.claude.ai"));
+        assert!(allowlist.allows("HTTPS://Foo.Claude.AI"));
+        assert!(allowlist.allows("http://localhost:5173"));
+
+        assert!(!allowlist.allows("https://evil.com"));
+        assert!(!allowlist.allows("https://claude.ai"));
+        assert!(!allowlist.allows("https://evilclaude.ai"));
+        assert!(!allowlist.allows("https://foo.claude.ai.evil.com"));
+        assert!(!allowlist.allows("http://foo.claude.ai"));
+        assert!(!allowlist.allows("https://foo.claude.ai:8443"));
+        assert!(!allowlist.allows("http://localhost:3000"));
+        assert!(!allowlist.allows("null"));
+    }
+
+    #[test]
+    fn test_invalid_entries_are_rejected() {
+        assert!(OriginAllowlist::parse("claude.ai").is_err());
+        assert!(OriginAllowlist::parse("https://foo.*.claude.ai").is_err());
+        assert!(OriginAllowlist::parse("https://claude.ai:port").is_err());
+        assert!(OriginAllowlist::parse(" , ").is_err());
+
+        let allowlist = OriginAllowlist::parse("https://*.Claude.ai,http://[::1]:3000").unwrap();
+        assert_eq!(
+            allowlist.describe(),
+            "https://*.claude.ai, http://[::1]:3000"
+        );
+        assert!(allowlist.allows("http://[::1]:3000"));
+    }
+}
@@ -0,0 +1,136 @@
+//! Per-IP rate limiting for the HTTP transport
+//!
+//! Each client IP gets a token bucket holding up to `per_minute` tokens that
+//! refills continuously at `per_minute` tokens per minute. Every request takes
+//! one token; a request that finds the bucket empty is answered with
+//! `429 Too Many Requests` and a `Retry-After` header. Health endpoints are not
+//! routed through the limiter.
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Environment variable holding the allowed requests per minute per IP (0 disables)
+pub const RATE_LIMIT_ENV: &str = "DOCGEN_RATE_LIMIT_PER_MINUTE";
+
+/// Requests per minute per IP when the environment variable is unset
+const DEFAULT_PER_MINUTE: u32 = 60;
+
+/// Number of tracked IPs above which idle (fully refilled) buckets are pruned
+const PRUNE_THRESHOLD: usize = 10_000;
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Shared token-bucket limiter keyed by client IP
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    per_minute: u32,
+    buckets: Arc<Mutex<HashMap<IpAddr, Bucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new(per_minute: u32) -> Self {
+        Self {
+            per_minute,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Build a limiter from the environment, or `None` if limiting is disabled
+    pub fn from_env() -> Option<Self> {
+        let per_minute = std::env::var(RATE_LIMIT_ENV)
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_PER_MINUTE);
+
+        (per_minute > 0).then(|| Self::new(per_minute))
+    }
+
+    pub fn per_minute(&self) -> u32 {
+        self.per_minute
+    }
+
+    /// Take a token for `ip`, or return how long until one is available
+    fn check(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let capacity = f64::from(self.per_minute);
+        let per_second = capacity / 60.0;
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() > PRUNE_THRESHOLD {
+            buckets.retain(|_, b| {
+                b.tokens + now.duration_since(b.updated).as_secs_f64() * per_second < capacity
+            });
+        }
+
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_second))
+        }
+    }
+}
+
+/// Middleware rejecting requests from IPs that exceeded their rate
+pub async fn limit(
+    State(limiter): State<RateLimiter>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match limiter.check(addr.ip(), Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            let retry_after = wait.as_secs_f64().ceil().max(1.0) as u64;
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                "Rate limit exceeded",
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_empties_and_refills() {
+        let limiter = RateLimiter::new(2);
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+        let other: IpAddr = "203.0.113.8".parse().unwrap();
+        let start = Instant::now();
+
+        assert!(limiter.check(ip, start).is_ok());
+        assert!(limiter.check(ip, start).is_ok());
+        let wait = limiter.check(ip, start).unwrap_err();
+        assert_eq!(wait.as_secs_f64().round(), 30.0);
+
+        // Other clients have their own bucket
+        assert!(limiter.check(other, start).is_ok());
+
+        // One token is back after half a minute
+        let later = start + Duration::from_secs(31);
+        assert!(limiter.check(ip, later).is_ok());
+        assert!(limiter.check(ip, later).is_err());
+    }
+}
@@ -2,17 +2,32 @@
 //!
 //! This module provides secure, time-limited storage for generated PDF files
 //! that are served via HTTP. Files are identified by UUIDs and automatically
-//! expire after a configurable duration.
+//! expire after a configurable duration. Callers may ask for a shorter or
+//! longer lifetime per file, up to a maximum set by `DOCGEN_MAX_FILE_TTL_SECONDS`.
+//! Files generated within an MCP session are tagged with its id so the session
+//! can list them later. Idempotent pipelines can instead store a file under an
+//! ID derived from its content, so storing the same bytes again returns the
+//! same ID (and URL) rather than a new copy.
 
+#[cfg(test)]
+use crate::documents::PDF_MIME_TYPE;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 use tokio::sync::RwLock;
-use uuid::Uuid;
+use uuid::{Builder, Uuid};
 
 /// Duration that files remain available (1 hour)
 const FILE_EXPIRATION: Duration = Duration::from_secs(3600);
 
+/// Environment variable holding the longest lifetime a request may ask for
+pub const MAX_FILE_TTL_ENV: &str = "DOCGEN_MAX_FILE_TTL_SECONDS";
+
+/// Longest lifetime a request may ask for unless configured (24 hours)
+const DEFAULT_MAX_FILE_TTL: Duration = Duration::from_secs(24 * 3600);
+
 /// How often to run cleanup of expired files (every 5 minutes)
 const CLEANUP_INTERVAL: Duration = Duration::from_secs(300);
 
@@ -28,6 +43,10 @@ pub struct StoredFile {
     pub expires_at: SystemTime,
     /// Original filename (for Content-Disposition header)
     pub filename: String,
+    /// MIME type of the content (for Content-Type header), taken from its `DocumentType`
+    pub mime_type: &'static str,
+    /// MCP session that generated the file, if any
+    pub session_id: Option<String>,
 }
 
 impl StoredFile {
@@ -37,10 +56,30 @@ impl StoredFile {
     }
 }
 
+/// Metadata for a stored file, as returned by session listings
+#[derive(Debug, Serialize)]
+pub struct FileSummary {
+    pub id: Uuid,
+    pub filename: String,
+    pub size_bytes: usize,
+    /// Creation time in seconds since the Unix epoch
+    pub created_at: u64,
+    /// Expiry time in seconds since the Unix epoch
+    pub expires_at: u64,
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
 /// Thread-safe storage manager for temporary files
 #[derive(Clone)]
 pub struct FileStorage {
     files: Arc<RwLock<HashMap<Uuid, StoredFile>>>,
+    /// Upper bound for per-file lifetimes requested by callers
+    max_ttl: Duration,
 }
 
 impl FileStorage {
@@ -48,29 +87,123 @@ impl FileStorage {
     pub fn new() -> Self {
         Self {
             files: Arc::new(RwLock::new(HashMap::new())),
+            max_ttl: DEFAULT_MAX_FILE_TTL,
         }
     }
 
-    /// Store a file and return its unique ID
+    /// Create a storage instance whose maximum file lifetime comes from the environment
+    pub fn from_env() -> Self {
+        let max_ttl = std::env::var(MAX_FILE_TTL_ENV)
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .filter(|&secs: &u64| secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_MAX_FILE_TTL);
+        Self::new().with_max_ttl(max_ttl)
+    }
+
+    /// Set the longest lifetime a caller may request
+    pub fn with_max_ttl(mut self, max_ttl: Duration) -> Self {
+        self.max_ttl = max_ttl;
+        self
+    }
+
+    /// Longest lifetime a caller may request
+    pub fn max_ttl(&self) -> Duration {
+        self.max_ttl
+    }
+
+    /// Lifetime for a file given the caller's requested TTL, if any
+    ///
+    /// Without a request the default of one hour applies (capped by the
+    /// maximum); requests are clamped to between one second and the maximum.
+    pub fn ttl_for(&self, requested: Option<Duration>) -> Duration {
+        requested.unwrap_or(FILE_EXPIRATION).clamp(
+            Duration::from_secs(1),
+            self.max_ttl.max(Duration::from_secs(1)),
+        )
+    }
+
+    /// Store a PDF and return its unique ID
     ///
     /// # Arguments
     /// * `data` - The PDF file content
     /// * `filename` - The original filename (for download)
+    /// * `session_id` - The MCP session that generated the file, if any
     ///
     /// # Returns
     /// A UUID that can be used to retrieve the file
-    pub async fn store(&self, data: Vec<u8>, filename: String) -> Uuid {
-        let id = Uuid::new_v4();
+    #[cfg(test)]
+    pub async fn store(&self, data: Vec<u8>, filename: String, session_id: Option<String>) -> Uuid {
+        let ttl = self.ttl_for(None);
+        self.store_with_ttl(data, filename, PDF_MIME_TYPE, session_id, ttl)
+            .await
+    }
+
+    /// Store a file that expires after `ttl` and return its unique ID
+    ///
+    /// The lifetime is used as given; clamp caller input with `ttl_for` first.
+    pub async fn store_with_ttl(
+        &self,
+        data: Vec<u8>,
+        filename: String,
+        mime_type: &'static str,
+        session_id: Option<String>,
+        ttl: Duration,
+    ) -> Uuid {
+        self.insert(Uuid::new_v4(), data, filename, mime_type, session_id, ttl)
+            .await
+    }
+
+    /// Store a file under its `content_id` and return that ID
+    ///
+    /// Storing identical content again returns the same ID. While the earlier
+    /// entry is live it keeps its session, filename and creation time, and its
+    /// expiry is only ever extended: another session producing the same bytes
+    /// cannot take the file out of the first session's list, and a shorter TTL
+    /// cannot cut short a link that was already handed out.
+    pub async fn store_deterministic(
+        &self,
+        data: Vec<u8>,
+        filename: String,
+        mime_type: &'static str,
+        session_id: Option<String>,
+        ttl: Duration,
+    ) -> Uuid {
+        let id = content_id(&data);
+        self.insert(id, data, filename, mime_type, session_id, ttl)
+            .await
+    }
+
+    async fn insert(
+        &self,
+        id: Uuid,
+        data: Vec<u8>,
+        filename: String,
+        mime_type: &'static str,
+        session_id: Option<String>,
+        ttl: Duration,
+    ) -> Uuid {
         let now = SystemTime::now();
+        let mut files = self.files.write().await;
 
-        let stored_file = StoredFile {
+        let mut stored_file = StoredFile {
             data,
             created_at: now,
-            expires_at: now + FILE_EXPIRATION,
+            expires_at: now + ttl,
             filename,
+            mime_type,
+            session_id,
         };
 
-        let mut files = self.files.write().await;
+        // A live file already under this ID (a deterministic re-store) keeps
+        // its owner and name, and its link never expires sooner than promised
+        if let Some(existing) = files.get(&id).filter(|existing| !existing.is_expired()) {
+            stored_file.created_at = existing.created_at;
+            stored_file.expires_at = stored_file.expires_at.max(existing.expires_at);
+            stored_file.filename = existing.filename.clone();
+            stored_file.session_id = existing.session_id.clone();
+        }
         files.insert(id, stored_file);
 
         id
@@ -96,6 +229,37 @@ impl FileStorage {
         }
     }
 
+    /// Remove a file before it expires
+    ///
+    /// Returns true if a live file was removed, false if it didn't exist or
+    /// had already expired.
+    pub async fn remove(&self, id: &Uuid) -> bool {
+        let mut files = self.files.write().await;
+        files.remove(id).is_some_and(|file| !file.is_expired())
+    }
+
+    /// List the live files generated within a session, oldest first
+    ///
+    /// Only files tagged with exactly this session id are returned, so one
+    /// session can never see another's files.
+    pub async fn list_session(&self, session_id: &str) -> Vec<FileSummary> {
+        let files = self.files.read().await;
+        let mut summaries: Vec<FileSummary> = files
+            .iter()
+            .filter(|(_, file)| file.session_id.as_deref() == Some(session_id))
+            .filter(|(_, file)| !file.is_expired())
+            .map(|(id, file)| FileSummary {
+                id: *id,
+                filename: file.filename.clone(),
+                size_bytes: file.data.len(),
+                created_at: unix_seconds(file.created_at),
+                expires_at: unix_seconds(file.expires_at),
+            })
+            .collect();
+        summaries.sort_by_key(|f| (f.created_at, f.id));
+        summaries
+    }
+
     /// Clean up all expired files
     ///
     /// This is called periodically by the cleanup task
@@ -125,6 +289,15 @@ impl FileStorage {
     }
 }
 
+/// ID derived from a file's content: the leading bytes of its SHA-256 digest,
+/// formatted as a version 8 (custom) UUID
+pub fn content_id(data: &[u8]) -> Uuid {
+    let digest = Sha256::digest(data);
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    Builder::from_custom_bytes(bytes).into_uuid()
+}
+
 impl Default for FileStorage {
     fn default() -> Self {
         Self::new()
@@ -141,7 +314,7 @@ mod tests {
         let data = vec![1, 2, 3, 4];
         let filename = "test.pdf".to_string();
 
-        let id = storage.store(data.clone(), filename.clone()).await;
+        let id = storage.store(data.clone(), filename.clone(), None).await;
         let retrieved = storage.retrieve(&id).await;
 
         assert!(retrieved.is_some());
@@ -165,7 +338,7 @@ mod tests {
 
         // Store a file
         let data = vec![1, 2, 3];
-        let id = storage.store(data, "test.pdf".to_string()).await;
+        let id = storage.store(data, "test.pdf".to_string(), None).await;
 
         // Manually expire it
         {
@@ -181,4 +354,162 @@ mod tests {
         let count = storage.count().await;
         assert_eq!(count, 0);
     }
+
+    #[tokio::test]
+    async fn test_requested_ttl_overrides_default() {
+        let storage = FileStorage::new().with_max_ttl(Duration::from_secs(7200));
+        assert_eq!(storage.ttl_for(None), FILE_EXPIRATION);
+        assert_eq!(
+            storage.ttl_for(Some(Duration::from_secs(60))),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            storage.ttl_for(Some(Duration::from_secs(86_400))),
+            Duration::from_secs(7200)
+        );
+
+        let default_id = storage
+            .store(vec![1], "default.pdf".to_string(), None)
+            .await;
+        let ttl = storage.ttl_for(Some(Duration::from_secs(60)));
+        let short_id = storage
+            .store_with_ttl(vec![2], "short.pdf".to_string(), PDF_MIME_TYPE, None, ttl)
+            .await;
+
+        let default_file = storage.retrieve(&default_id).await.unwrap();
+        let short_file = storage.retrieve(&short_id).await.unwrap();
+        assert!(short_file.expires_at < default_file.expires_at);
+        assert!(short_file.expires_at <= SystemTime::now() + Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_ids_follow_content() {
+        let storage = FileStorage::new();
+        let ttl = storage.ttl_for(None);
+        let store = |data: Vec<u8>, filename: &str| {
+            storage.store_deterministic(data, filename.to_string(), PDF_MIME_TYPE, None, ttl)
+        };
+
+        let first = store(vec![1, 2, 3], "first.pdf").await;
+        let second = store(vec![1, 2, 3], "second.pdf").await;
+        assert_eq!(first, second);
+        assert_eq!(first, content_id(&[1, 2, 3]));
+        assert_eq!(storage.count().await, 1);
+        // The live entry keeps the name it was first stored under
+        assert_eq!(
+            storage.retrieve(&first).await.unwrap().filename,
+            "first.pdf"
+        );
+
+        let other = store(vec![4, 5, 6], "other.pdf").await;
+        assert_ne!(first, other);
+
+        // Re-storing from another session leaves the file with its first owner
+        let owned = storage
+            .store_deterministic(
+                vec![7],
+                "a.pdf".to_string(),
+                PDF_MIME_TYPE,
+                Some("session-a".to_string()),
+                ttl,
+            )
+            .await;
+        storage
+            .store_deterministic(
+                vec![7],
+                "b.pdf".to_string(),
+                PDF_MIME_TYPE,
+                Some("session-b".to_string()),
+                ttl,
+            )
+            .await;
+        let listed = storage.list_session("session-a").await;
+        assert_eq!(listed.len(), 1);
+        assert_eq!(
+            (listed[0].id, listed[0].filename.as_str()),
+            (owned, "a.pdf")
+        );
+        assert!(storage.list_session("session-b").await.is_empty());
+
+        // Random IDs are unaffected
+        let random = storage
+            .store(vec![1, 2, 3], "random.pdf".to_string(), None)
+            .await;
+        assert_ne!(random, first);
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_restore_never_shortens_expiry() {
+        let storage = FileStorage::new();
+        let store = |ttl: Duration| {
+            storage.store_deterministic(
+                vec![1, 2, 3],
+                "a.pdf".to_string(),
+                PDF_MIME_TYPE,
+                None,
+                ttl,
+            )
+        };
+
+        let id = store(Duration::from_secs(3600)).await;
+        let promised = storage.retrieve(&id).await.unwrap().expires_at;
+
+        store(Duration::from_secs(1)).await;
+        assert_eq!(storage.retrieve(&id).await.unwrap().expires_at, promised);
+
+        // A longer TTL still extends the link
+        store(Duration::from_secs(7200)).await;
+        assert!(storage.retrieve(&id).await.unwrap().expires_at > promised);
+    }
+
+    #[tokio::test]
+    async fn test_remove() {
+        let storage = FileStorage::new();
+        let id = storage
+            .store(vec![1, 2, 3], "test.pdf".to_string(), None)
+            .await;
+
+        assert!(storage.remove(&id).await);
+        assert!(storage.retrieve(&id).await.is_none());
+
+        // Removing again reports the file as absent
+        assert!(!storage.remove(&id).await);
+    }
+
+    #[tokio::test]
+    async fn test_list_session_is_scoped() {
+        let storage = FileStorage::new();
+        let session = Some("session-a".to_string());
+        let first = storage
+            .store(vec![1], "a.pdf".to_string(), session.clone())
+            .await;
+        let second = storage
+            .store(vec![2, 3], "b.pdf".to_string(), session)
+            .await;
+        storage
+            .store(
+                vec![4],
+                "other.pdf".to_string(),
+                Some("session-b".to_string()),
+            )
+            .await;
+        storage.store(vec![5], "stdio.pdf".to_string(), None).await;
+
+        let mut ids: Vec<Uuid> = storage
+            .list_session("session-a")
+            .await
+            .iter()
+            .map(|f| f.id)
+            .collect();
+        ids.sort();
+        let mut expected = vec![first, second];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        let other = storage.list_session("session-b").await;
+        assert_eq!(other.len(), 1);
+        assert_eq!(other[0].filename, "other.pdf");
+
+        assert!(storage.list_session("session-c").await.is_empty());
+    }
 }
@@ -0,0 +1,412 @@
+//! User-supplied assets (images) that templates can load by name
+//!
+//! Assets arrive base64-encoded in the tool input, keyed by name, and are
+//! served to Typst under `assets/<name>`. Each one is decoded and checked up
+//! front; an asset that fails is dropped with a warning so that a single bad
+//! image never aborts the whole document.
+//!
+//! Sizes are the exception: assets over the per-asset or total size limit
+//! reject the request before anything is decoded, since silently dropping a
+//! large logo would be more surprising than an error. Names are checked the
+//! same way: each must be a plain file name, so a key like `../etc/passwd`
+//! can never become a path outside `assets/`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Cursor;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use typst::foundations::Bytes;
+use typst::visualize::{ExchangeFormat, RasterImage, SvgImage};
+
+/// Directory (relative to the project root) that assets are served from
+pub const ASSET_DIR: &str = "assets";
+
+/// Environment variable holding the maximum decoded size of one asset, in bytes
+pub const MAX_ASSET_BYTES_ENV: &str = "DOCGEN_MAX_ASSET_BYTES";
+
+/// Environment variable holding the maximum decoded size of all assets together, in bytes
+pub const MAX_TOTAL_ASSET_BYTES_ENV: &str = "DOCGEN_MAX_TOTAL_ASSET_BYTES";
+
+/// Default limit for a single asset (5 MiB)
+const DEFAULT_MAX_ASSET_BYTES: usize = 5 * 1024 * 1024;
+
+/// Default limit for all assets of one request (10 MiB)
+const DEFAULT_MAX_TOTAL_ASSET_BYTES: usize = 10 * 1024 * 1024;
+
+/// Longest side, in pixels, that compression scales raster images down to;
+/// about 2.7 inches at 300 dpi, more than any logo or photo on a resume needs
+const COMPRESSED_MAX_DIMENSION: u32 = 800;
+
+/// JPEG quality used when compression re-encodes JPEG images
+const COMPRESSED_JPEG_QUALITY: u8 = 80;
+
+/// Decoded assets keyed by name
+pub type AssetMap = HashMap<String, Bytes>;
+
+/// Size limits applied to the assets of one request
+#[derive(Debug, Clone)]
+pub struct AssetLimits {
+    /// Maximum decoded size of any single asset
+    pub max_asset_bytes: usize,
+    /// Maximum decoded size of all assets together
+    pub max_total_bytes: usize,
+}
+
+impl Default for AssetLimits {
+    fn default() -> Self {
+        Self {
+            max_asset_bytes: DEFAULT_MAX_ASSET_BYTES,
+            max_total_bytes: DEFAULT_MAX_TOTAL_ASSET_BYTES,
+        }
+    }
+}
+
+impl AssetLimits {
+    /// Limits from the environment, falling back to the defaults
+    pub fn from_env() -> Self {
+        let limit = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|&n: &usize| n > 0)
+        };
+        let defaults = Self::default();
+        Self {
+            max_asset_bytes: limit(MAX_ASSET_BYTES_ENV).unwrap_or(defaults.max_asset_bytes),
+            max_total_bytes: limit(MAX_TOTAL_ASSET_BYTES_ENV).unwrap_or(defaults.max_total_bytes),
+        }
+    }
+
+    /// Every limit the encoded assets exceed, checked before any decoding
+    ///
+    /// Oversized assets are named individually; exceeding the total limit is
+    /// reported once with an empty name.
+    pub fn check(&self, encoded: &BTreeMap<String, String>) -> Vec<AssetWarning> {
+        let mut errors = Vec::new();
+        let mut total = 0;
+
+        for (name, data) in encoded {
+            let size = decoded_size(data);
+            total += size;
+            if size > self.max_asset_bytes {
+                errors.push(AssetWarning {
+                    name: name.clone(),
+                    message: format!(
+                        "Asset '{}' is {}, over the {} per-asset limit",
+                        name,
+                        format_size(size),
+                        format_size(self.max_asset_bytes)
+                    ),
+                });
+            }
+        }
+
+        if total > self.max_total_bytes {
+            errors.push(AssetWarning {
+                name: String::new(),
+                message: format!(
+                    "Assets total {}, over the {} limit per request",
+                    format_size(total),
+                    format_size(self.max_total_bytes)
+                ),
+            });
+        }
+
+        errors
+    }
+}
+
+/// Every asset whose name is not a plain relative file name
+///
+/// Names become paths under `assets/`, so directory separators, `.` and `..`,
+/// and control characters are rejected rather than resolved.
+pub fn check_asset_names(encoded: &BTreeMap<String, String>) -> Vec<AssetWarning> {
+    encoded
+        .keys()
+        .filter_map(|name| {
+            name_error(name).map(|reason| AssetWarning {
+                name: name.clone(),
+                message: format!(
+                    "Asset name {:?} is not allowed: {}. Use a plain file name such as 'logo.png'",
+                    name, reason
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Why a name can't be used as a file name under `assets/`, if it can't
+fn name_error(name: &str) -> Option<&'static str> {
+    if name.trim().is_empty() {
+        Some("it is empty")
+    } else if name.contains(['/', '\\']) {
+        Some("it contains a path separator")
+    } else if name == "." || name == ".." {
+        Some("it refers to a directory")
+    } else if name.chars().any(char::is_control) {
+        Some("it contains control characters")
+    } else {
+        None
+    }
+}
+
+/// A problem with one asset: why it was skipped, or which limit it exceeds
+#[derive(Debug)]
+pub struct AssetWarning {
+    pub name: String,
+    pub message: String,
+}
+
+/// Decode base64 assets, skipping (and reporting) any that are not usable images
+pub fn decode_assets(encoded: &BTreeMap<String, String>) -> (AssetMap, Vec<AssetWarning>) {
+    let mut assets = AssetMap::new();
+    let mut warnings = Vec::new();
+
+    for (name, data) in encoded {
+        match decode_image(data) {
+            Ok(bytes) => {
+                assets.insert(name.clone(), bytes);
+            }
+            Err(reason) => warnings.push(AssetWarning {
+                name: name.clone(),
+                message: format!("Asset '{}' was skipped: {}", name, reason),
+            }),
+        }
+    }
+
+    (assets, warnings)
+}
+
+/// The base64 payload of an asset, without any `data:` URL prefix
+fn base64_payload(encoded: &str) -> &str {
+    match encoded.split_once(";base64,") {
+        Some((prefix, payload)) if prefix.starts_with("data:") => payload,
+        _ => encoded,
+    }
+}
+
+/// Decoded size of an asset, computed from its base64 length
+fn decoded_size(encoded: &str) -> usize {
+    let symbols = base64_payload(encoded)
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace() && *b != b'=')
+        .count();
+    symbols * 3 / 4
+}
+
+/// Human-readable byte count ("512 KiB", "2.5 MiB")
+fn format_size(bytes: usize) -> String {
+    const MIB: usize = 1024 * 1024;
+    if bytes >= MIB {
+        format!("{:.1} MiB", bytes as f64 / MIB as f64)
+    } else {
+        format!("{} KiB", bytes.div_ceil(1024))
+    }
+}
+
+/// Decode one base64 image (optionally a `data:` URL) and check that Typst can load it
+pub fn decode_image(encoded: &str) -> Result<Bytes, String> {
+    let payload = base64_payload(encoded);
+
+    let data = STANDARD
+        .decode(payload.trim())
+        .map_err(|e| format!("not valid base64 ({})", e))?;
+    let bytes = Bytes::new(data);
+
+    if let Some(format) = ExchangeFormat::detect(&bytes) {
+        RasterImage::plain(bytes.clone(), format)
+            .map_err(|e| format!("could not decode image ({})", e))?;
+    } else if is_svg(&bytes) {
+        SvgImage::new(bytes.clone()).map_err(|_| "could not parse SVG".to_string())?;
+    } else {
+        return Err("unsupported image format (expected PNG, JPEG, GIF, WebP, or SVG)".to_string());
+    }
+
+    Ok(bytes)
+}
+
+/// Shrink raster assets for a smaller PDF
+///
+/// PNG and JPEG images are scaled down to at most `COMPRESSED_MAX_DIMENSION`
+/// pixels on their longest side and re-encoded in their own format, JPEGs at
+/// `COMPRESSED_JPEG_QUALITY`. Other formats, and images whose re-encoding
+/// would not be smaller, are left untouched.
+pub fn compress_images(assets: &mut AssetMap) {
+    for bytes in assets.values_mut() {
+        if let Some(smaller) = compress_image(bytes) {
+            *bytes = Bytes::new(smaller);
+        }
+    }
+}
+
+fn compress_image(data: &[u8]) -> Option<Vec<u8>> {
+    let format = image::guess_format(data).ok()?;
+    if !matches!(format, image::ImageFormat::Png | image::ImageFormat::Jpeg) {
+        return None;
+    }
+    let mut decoded = image::load_from_memory_with_format(data, format).ok()?;
+    if decoded.width().max(decoded.height()) > COMPRESSED_MAX_DIMENSION {
+        decoded = decoded.resize(
+            COMPRESSED_MAX_DIMENSION,
+            COMPRESSED_MAX_DIMENSION,
+            FilterType::Lanczos3,
+        );
+    }
+
+    let mut encoded = Cursor::new(Vec::new());
+    if format == image::ImageFormat::Jpeg {
+        let encoder = JpegEncoder::new_with_quality(&mut encoded, COMPRESSED_JPEG_QUALITY);
+        decoded.to_rgb8().write_with_encoder(encoder).ok()?;
+    } else {
+        decoded.write_to(&mut encoded, format).ok()?;
+    }
+    let encoded = encoded.into_inner();
+    (encoded.len() < data.len()).then_some(encoded)
+}
+
+fn is_svg(data: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&data[..data.len().min(512)]);
+    head.contains("<svg")
+}
+
+/// A PNG of pseudo-random noise, which compresses poorly at full size
+#[cfg(test)]
+pub(crate) fn noise_png(width: u32, height: u32) -> Vec<u8> {
+    let mut state: u32 = 0x2545_f491;
+    let noise = image::RgbImage::from_fn(width, height, |_, _| {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        let [r, g, b, _] = state.to_le_bytes();
+        image::Rgb([r, g, b])
+    });
+    let mut png = Cursor::new(Vec::new());
+    noise.write_to(&mut png, image::ImageFormat::Png).unwrap();
+    png.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 1x1 PNG
+    const TINY_PNG: &str = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAIAAACQd1PeAAAADElEQVR4nGP4z8AAAAMBAQDJ/pLvAAAAAElFTkSuQmCC";
+
+    #[test]
+    fn test_bad_assets_are_skipped_with_warnings() {
+        let encoded = BTreeMap::from([
+            ("dot.png".to_string(), TINY_PNG.to_string()),
+            (
+                "inline.png".to_string(),
+                format!("data:image/png;base64,{}", TINY_PNG),
+            ),
+            ("garbage.png".to_string(), "not base64!".to_string()),
+            ("truncated.png".to_string(), TINY_PNG[..24].to_string()),
+            ("notes.txt".to_string(), STANDARD.encode("hello")),
+        ]);
+
+        let (assets, warnings) = decode_assets(&encoded);
+
+        assert!(assets.contains_key("dot.png"));
+        assert!(assets.contains_key("inline.png"));
+        assert_eq!(assets.len(), 2);
+
+        let skipped: Vec<&str> = warnings.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(skipped, vec!["garbage.png", "notes.txt", "truncated.png"]);
+        assert!(warnings[0].message.contains("base64"));
+        assert!(warnings[1].message.contains("unsupported"));
+    }
+
+    #[test]
+    fn test_path_like_asset_names_are_rejected() {
+        let encoded = BTreeMap::from([
+            ("logo.png".to_string(), TINY_PNG.to_string()),
+            ("../etc/passwd".to_string(), TINY_PNG.to_string()),
+            ("/abs.png".to_string(), TINY_PNG.to_string()),
+            ("..".to_string(), TINY_PNG.to_string()),
+            ("dir\\x.png".to_string(), TINY_PNG.to_string()),
+            ("nul\0.png".to_string(), TINY_PNG.to_string()),
+        ]);
+
+        let errors = check_asset_names(&encoded);
+        let rejected: Vec<&str> = errors.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            rejected,
+            vec!["..", "../etc/passwd", "/abs.png", "dir\\x.png", "nul\0.png"]
+        );
+        assert!(
+            errors[1]
+                .message
+                .contains("\"../etc/passwd\" is not allowed: it contains a path separator"),
+            "{}",
+            errors[1].message
+        );
+    }
+
+    #[test]
+    fn test_size_limits_name_oversized_assets() {
+        let limits = AssetLimits {
+            max_asset_bytes: 1024,
+            max_total_bytes: 1536,
+        };
+        let encoded = BTreeMap::from([
+            ("dot.png".to_string(), TINY_PNG.to_string()),
+            ("big.png".to_string(), STANDARD.encode(vec![0u8; 2048])),
+        ]);
+
+        assert_eq!(decoded_size(&STANDARD.encode(vec![0u8; 2048])), 2048);
+        assert_eq!(
+            decoded_size(&format!(
+                "data:image/png;base64,{}",
+                STANDARD.encode([1, 2])
+            )),
+            2
+        );
+
+        let errors = limits.check(&encoded);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].name, "big.png");
+        assert!(
+            errors[0].message.contains("'big.png' is 2 KiB"),
+            "{}",
+            errors[0].message
+        );
+        assert!(
+            errors[0].message.contains("1 KiB per-asset limit"),
+            "{}",
+            errors[0].message
+        );
+        assert_eq!(errors[1].name, "");
+        assert!(errors[1].message.contains("total"), "{}", errors[1].message);
+
+        assert!(AssetLimits::default().check(&encoded).is_empty());
+    }
+
+    #[test]
+    fn test_compress_images_scales_large_rasters_down() {
+        let large = noise_png(1600, 1200);
+        let mut assets = AssetMap::from([
+            ("photo.png".to_string(), Bytes::new(large.clone())),
+            ("dot.png".to_string(), decode_image(TINY_PNG).unwrap()),
+            (
+                "icon.svg".to_string(),
+                Bytes::new(br#"<svg xmlns="http://www.w3.org/2000/svg"/>"#.to_vec()),
+            ),
+        ]);
+        let untouched = assets.clone();
+        compress_images(&mut assets);
+
+        let photo = &assets["photo.png"];
+        assert!(photo.len() < large.len() / 2, "{} bytes", photo.len());
+        let decoded = image::load_from_memory(photo).unwrap();
+        assert_eq!(image::guess_format(photo).unwrap(), image::ImageFormat::Png);
+        assert_eq!((decoded.width(), decoded.height()), (800, 600));
+
+        // Small images and vector formats are kept as they were
+        assert_eq!(assets["dot.png"], untouched["dot.png"]);
+        assert_eq!(assets["icon.svg"], untouched["icon.svg"]);
+    }
+}
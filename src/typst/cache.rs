@@ -0,0 +1,176 @@
+//! Cache of compiled outputs
+//!
+//! Generating the same document twice, e.g. when a client retries or asks for
+//! a download after an inline preview, reuses the first compile's bytes. The
+//! key covers everything that affects the output: the world's SHA-256
+//! fingerprint (generated source with the normalized document and every
+//! render option, template modules, assets and the date), the export options,
+//! the requested formats and whether a thumbnail was rendered. Changing any
+//! render option therefore misses the cache instead of returning another
+//! style's bytes.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, LazyLock, Mutex};
+
+use crate::typst::compiler::{ExportOptions, OutputFormat};
+use crate::typst::world::DocgenWorld;
+
+/// Number of compiled documents kept, least recently used evicted first
+const CAPACITY: usize = 16;
+
+static OUTPUT_CACHE: LazyLock<OutputCache> = LazyLock::new(|| OutputCache::new(CAPACITY));
+
+/// The shared cache used for all document compilation
+pub fn output_cache() -> &'static OutputCache {
+    &OUTPUT_CACHE
+}
+
+/// Everything a compile's output depends on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputKey {
+    world: [u8; 32],
+    export: ExportOptions,
+    formats: Vec<OutputFormat>,
+    thumbnail: bool,
+}
+
+impl OutputKey {
+    pub fn new(
+        world: &DocgenWorld,
+        export: &ExportOptions,
+        formats: &[OutputFormat],
+        thumbnail: bool,
+    ) -> Self {
+        Self {
+            world: world.fingerprint(),
+            export: *export,
+            formats: formats.to_vec(),
+            thumbnail,
+        }
+    }
+}
+
+/// Bytes produced by one compile
+#[derive(Debug)]
+pub struct CompiledOutputs {
+    pub outputs: BTreeMap<OutputFormat, Vec<u8>>,
+    /// First-page PNG, if one was requested
+    pub thumbnail: Option<Vec<u8>>,
+}
+
+/// A small least-recently-used map from output keys to compiled bytes
+pub struct OutputCache {
+    capacity: usize,
+    entries: Mutex<VecDeque<(OutputKey, Arc<CompiledOutputs>)>>,
+}
+
+impl OutputCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Look up a compile, marking it as recently used
+    pub fn get(&self, key: &OutputKey) -> Option<Arc<CompiledOutputs>> {
+        let mut entries = self.entries.lock().unwrap();
+        let index = entries.iter().position(|(k, _)| k == key)?;
+        let entry = entries.remove(index)?;
+        let outputs = entry.1.clone();
+        entries.push_back(entry);
+        Some(outputs)
+    }
+
+    /// Remember a successful compile, evicting the least recently used one if full
+    pub fn insert(&self, key: OutputKey, outputs: Arc<CompiledOutputs>) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|(k, _)| *k != key);
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back((key, outputs));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Resume;
+    use crate::typst::transform::{RenderOptions, transform_resume};
+
+    fn world(options: &RenderOptions) -> DocgenWorld {
+        let resume: Resume =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+        DocgenWorld::new(transform_resume(&resume, options).unwrap())
+    }
+
+    fn outputs(byte: u8) -> Arc<CompiledOutputs> {
+        Arc::new(CompiledOutputs {
+            outputs: BTreeMap::from([(OutputFormat::Pdf, vec![byte])]),
+            thumbnail: None,
+        })
+    }
+
+    #[test]
+    fn test_key_covers_render_options_formats_and_thumbnail() {
+        let export = ExportOptions::default();
+        let pdf = [OutputFormat::Pdf];
+        let plain = OutputKey::new(&world(&RenderOptions::default()), &export, &pdf, false);
+
+        assert_eq!(
+            plain,
+            OutputKey::new(&world(&RenderOptions::default()), &export, &pdf, false)
+        );
+
+        let styled: RenderOptions =
+            serde_json::from_value(serde_json::json!({ "divider_style": "none" })).unwrap();
+        assert_ne!(plain, OutputKey::new(&world(&styled), &export, &pdf, false));
+
+        let default_world = world(&RenderOptions::default());
+        assert_ne!(plain, OutputKey::new(&default_world, &export, &pdf, true));
+        assert_ne!(
+            plain,
+            OutputKey::new(
+                &default_world,
+                &export,
+                &[OutputFormat::Pdf, OutputFormat::Png],
+                false
+            )
+        );
+        let strict = ExportOptions {
+            treat_warnings_as_errors: true,
+            ..export
+        };
+        assert_ne!(plain, OutputKey::new(&default_world, &strict, &pdf, false));
+    }
+
+    #[test]
+    fn test_least_recently_used_entry_is_evicted() {
+        let cache = OutputCache::new(2);
+        let export = ExportOptions::default();
+        let key = |thumbnail, formats: &[OutputFormat]| {
+            OutputKey::new(
+                &DocgenWorld::new("A".to_string()),
+                &export,
+                formats,
+                thumbnail,
+            )
+        };
+        let (a, b, c) = (
+            key(false, &[OutputFormat::Pdf]),
+            key(true, &[OutputFormat::Pdf]),
+            key(false, &[OutputFormat::Png]),
+        );
+
+        cache.insert(a.clone(), outputs(1));
+        cache.insert(b.clone(), outputs(2));
+        // Touch `a` so `b` is the least recently used
+        assert_eq!(cache.get(&a).unwrap().outputs[&OutputFormat::Pdf], vec![1]);
+        cache.insert(c.clone(), outputs(3));
+
+        assert!(cache.get(&b).is_none());
+        assert!(cache.get(&a).is_some());
+        assert_eq!(cache.get(&c).unwrap().outputs[&OutputFormat::Pdf], vec![3]);
+    }
+}
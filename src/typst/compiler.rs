@@ -1,33 +1,332 @@
+use std::collections::BTreeMap;
+
+use crate::documents::DocumentType;
+use crate::typst::transform::transform_example;
 use crate::typst::world::DocgenWorld;
+use serde::{Deserialize, Serialize};
 use typst::diag::SourceDiagnostic;
+use typst::layout::PagedDocument;
+use typst::syntax::Span;
+
+/// Resolution of PNG previews (2 pixels per point, i.e. 144 DPI)
+const PNG_PIXELS_PER_PT: f32 = 2.0;
+
+/// Width of first-page thumbnails, in pixels
+pub const THUMBNAIL_WIDTH_PX: f32 = 200.0;
+
+/// Environment variable holding the most pages a generated document may have
+pub const MAX_PAGES_ENV: &str = "DOCGEN_MAX_PAGES";
+
+/// Page limit when `MAX_PAGES_ENV` is unset; far beyond any real resume or letter
+const DEFAULT_MAX_PAGES: usize = 50;
+
+/// The page limit from the environment, falling back to the default
+pub fn max_pages_from_env() -> usize {
+    std::env::var(MAX_PAGES_ENV)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|&n: &usize| n > 0)
+        .unwrap_or(DEFAULT_MAX_PAGES)
+}
+
+/// Compiles Typst source into a laid-out document (without exporting it)
+///
+/// Held to the page limit from the environment, like generated documents.
+pub fn compile_document(source: String) -> Result<PagedDocument, Vec<SourceDiagnostic>> {
+    compile_document_with_page_limit(source, max_pages_from_env())
+}
 
-pub fn compile(source: String) -> Result<Vec<u8>, Vec<SourceDiagnostic>> {
-    let world = DocgenWorld::new(source);
+/// Compiles Typst source into a laid-out document, failing once it has more
+/// than `max_pages` pages
+pub fn compile_document_with_page_limit(
+    source: String,
+    max_pages: usize,
+) -> Result<PagedDocument, Vec<SourceDiagnostic>> {
+    let export = ExportOptions {
+        max_pages: Some(max_pages),
+        ..Default::default()
+    };
+    layout(&DocgenWorld::new(source), &export)
+}
 
-    let warned_document = typst::compile(&world);
+fn layout(
+    world: &DocgenWorld,
+    export: &ExportOptions,
+) -> Result<PagedDocument, Vec<SourceDiagnostic>> {
+    let warned_document = typst::compile::<PagedDocument>(world);
 
     // Convert EcoVec to Vec
     let document = warned_document
         .output
         .map_err(|e| e.into_iter().collect::<Vec<_>>())?;
 
-    // Use default options (timestamp: None)
+    if export.treat_warnings_as_errors && !warned_document.warnings.is_empty() {
+        return Err(warned_document.warnings.into_iter().collect());
+    }
+
+    // Exporting holds every page in memory again, so a runaway document is
+    // stopped here rather than rendered
+    if let Some(max_pages) = export.max_pages
+        && document.pages.len() > max_pages
+    {
+        let error = SourceDiagnostic::error(
+            Span::detached(),
+            format!(
+                "Document has {} pages, more than the limit of {}; compilation was aborted before export",
+                document.pages.len(),
+                max_pages
+            ),
+        )
+        .with_hint("shorten the content or split it into several documents");
+        return Err(vec![error]);
+    }
+    Ok(document)
+}
+
+/// Lines shown on each side of the error line in a source excerpt
+const EXCERPT_CONTEXT_LINES: usize = 3;
+
+/// Numbered lines of source around the first diagnostic that has a location
+///
+/// The error line is marked with `>`. A diagnostic without a location (or no
+/// diagnostics at all) falls back to the start of the main source, so there
+/// is always something to attach to a bug report.
+pub fn source_excerpt(world: &DocgenWorld, diags: &[SourceDiagnostic]) -> String {
+    use typst::World;
+
+    let located = diags.iter().find_map(|diag| {
+        let source = world.source(diag.span.id()?).ok()?;
+        let start = source.range(diag.span)?.start;
+        let line = source.lines().byte_to_line(start)?;
+        Some((source, Some(line)))
+    });
+    let (source, error_line) = match located {
+        Some(located) => located,
+        None => match world.source(world.main()) {
+            Ok(source) => (source, None),
+            Err(_) => return String::new(),
+        },
+    };
+
+    let text = source.text();
+    let line_count = text.lines().count();
+    let center = error_line.unwrap_or(EXCERPT_CONTEXT_LINES);
+    let first = center.saturating_sub(EXCERPT_CONTEXT_LINES);
+    let last = (center + EXCERPT_CONTEXT_LINES).min(line_count.saturating_sub(1));
+    let width = (last + 1).to_string().len();
+
+    let mut excerpt = format!("{}\n", source.id().vpath().as_rootless_path().display());
+    for (index, line) in text.lines().enumerate().take(last + 1).skip(first) {
+        let marker = if Some(index) == error_line { '>' } else { ' ' };
+        excerpt.push_str(&format!(
+            "{}{:>width$} | {}\n",
+            marker,
+            index + 1,
+            line,
+            width = width
+        ));
+    }
+    excerpt
+}
+
+/// Options controlling compilation and PDF export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExportOptions {
+    /// Fail a compile that succeeded with warnings, returning the warnings as
+    /// its diagnostics
+    pub treat_warnings_as_errors: bool,
+    /// Fail a compile whose document has more pages than this, before it is
+    /// exported (no limit if None)
+    pub max_pages: Option<usize>,
+}
+
+/// Compiles and exports a trivial document using the bundled fonts
+///
+/// Succeeds only once the font cache is loaded and the compiler works end to
+/// end, so it doubles as a readiness probe.
+pub fn smoke_test() -> Result<(), Vec<SourceDiagnostic>> {
+    let source = "#set page(width: auto, height: auto)\n#set text(font: \"Libertinus Serif\")\nReady";
+    compile(source.to_string(), &ExportOptions::default()).map(|_| ())
+}
+
+/// Compiles every document type's example against its embedded template
+///
+/// Templates are built into the binary, so a broken one would otherwise only
+/// surface on the first request for that document type. The error names each
+/// failing type with its diagnostics.
+pub fn check_templates() -> Result<(), String> {
+    let failures: Vec<String> = DocumentType::ALL
+        .iter()
+        .filter_map(|document_type| {
+            let result = transform_example(document_type).and_then(|source| {
+                compile_document(source).map(|_| ()).map_err(|diags| {
+                    diags
+                        .iter()
+                        .map(|d| d.message.to_string())
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                })
+            });
+            result
+                .err()
+                .map(|error| format!("{} template: {}", document_type.label, error))
+        })
+        .collect();
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+/// Output formats a compiled document can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Pdf,
+    /// Raster preview of the first page
+    Png,
+}
+
+impl OutputFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutputFormat::Pdf => "pdf",
+            OutputFormat::Png => "png",
+        }
+    }
+}
+
+/// Exported bytes keyed by format
+pub type FormatOutputs = BTreeMap<OutputFormat, Vec<u8>>;
+
+pub fn compile(source: String, export: &ExportOptions) -> Result<Vec<u8>, Vec<SourceDiagnostic>> {
+    compile_world(&DocgenWorld::new(source), export)
+}
+
+/// Compiles a prepared world (e.g. one carrying assets) to PDF
+pub fn compile_world(
+    world: &DocgenWorld,
+    export: &ExportOptions,
+) -> Result<Vec<u8>, Vec<SourceDiagnostic>> {
+    let document = layout(world, export)?;
+    Ok(export_pdf(&document))
+}
+
+/// Compiles a prepared world to PDF, also returning its number of pages
+pub fn compile_world_with_page_count(
+    world: &DocgenWorld,
+    export: &ExportOptions,
+) -> Result<(Vec<u8>, usize), Vec<SourceDiagnostic>> {
+    let document = layout(world, export)?;
+    Ok((export_pdf(&document), document.pages.len()))
+}
+
+/// Compiles a prepared world once and exports the document to each format
+pub fn compile_world_to_formats(
+    world: &DocgenWorld,
+    formats: &[OutputFormat],
+    export: &ExportOptions,
+) -> Result<FormatOutputs, Vec<SourceDiagnostic>> {
+    let document = layout(world, export)?;
+    Ok(export_formats(&document, formats))
+}
+
+/// Compiles a prepared world once and exports it to each format plus a
+/// first-page thumbnail
+pub fn compile_world_with_thumbnail(
+    world: &DocgenWorld,
+    formats: &[OutputFormat],
+    export: &ExportOptions,
+) -> Result<(FormatOutputs, Vec<u8>), Vec<SourceDiagnostic>> {
+    let document = layout(world, export)?;
+    Ok((
+        export_formats(&document, formats),
+        export_thumbnail(&document),
+    ))
+}
+
+fn export_formats(document: &PagedDocument, formats: &[OutputFormat]) -> FormatOutputs {
+    formats
+        .iter()
+        .map(|&format| {
+            let bytes = match format {
+                OutputFormat::Pdf => export_pdf(document),
+                OutputFormat::Png => export_png(document),
+            };
+            (format, bytes)
+        })
+        .collect()
+}
+
+fn export_pdf(document: &PagedDocument) -> Vec<u8> {
+    // Default options (timestamp: None)
     let options = typst_pdf::PdfOptions::default();
 
-    match typst_pdf::pdf(&document, &options) {
-        Ok(bytes) => Ok(bytes),
+    match typst_pdf::pdf(document, &options) {
+        Ok(bytes) => bytes,
         Err(_) => panic!("Failed to export PDF"),
     }
 }
 
+fn export_png(document: &PagedDocument) -> Vec<u8> {
+    // Layout always produces at least one page
+    render_first_page(document, PNG_PIXELS_PER_PT)
+}
+
+/// First page scaled to `THUMBNAIL_WIDTH_PX` wide
+fn export_thumbnail(document: &PagedDocument) -> Vec<u8> {
+    let width_pt = document.pages[0].frame.width().to_pt() as f32;
+    render_first_page(document, THUMBNAIL_WIDTH_PX / width_pt.max(1.0))
+}
+
+fn render_first_page(document: &PagedDocument, pixels_per_pt: f32) -> Vec<u8> {
+    let pixmap = typst_render::render(&document.pages[0], pixels_per_pt);
+
+    match pixmap.encode_png() {
+        Ok(bytes) => bytes,
+        Err(_) => panic!("Failed to encode PNG"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_page_limit_aborts_before_export() {
+        let source = "#set page(width: 100pt, height: 100pt)\n#for i in range(6) [#if i > 0 { pagebreak() } #i]";
+        let limited = ExportOptions {
+            max_pages: Some(3),
+            ..Default::default()
+        };
+
+        let diags = compile(source.to_string(), &limited).unwrap_err();
+        assert_eq!(diags.len(), 1);
+        assert!(diags[0].message.contains("6 pages"), "{}", diags[0].message);
+        assert!(
+            diags[0].message.contains("limit of 3"),
+            "{}",
+            diags[0].message
+        );
+
+        let unlimited = compile(source.to_string(), &ExportOptions::default());
+        assert!(unlimited.is_ok());
+    }
+
+    #[test]
+    fn test_all_templates_compile_against_their_examples() {
+        assert_eq!(DocumentType::ALL.len(), 2);
+        if let Err(error) = check_templates() {
+            panic!("{}", error);
+        }
+    }
+
     #[test]
     fn test_compile_minimal() {
         let source = "#set page(width: auto, height: auto)\nHello World".to_string();
-        let result = compile(source);
+        let result = compile(source, &ExportOptions::default());
 
         if let Err(ref e) = result {
             for diag in e {
@@ -42,7 +341,7 @@ mod tests {
     #[test]
     fn test_pdf_content_extraction() {
         let source = "#set page(width: auto, height: auto)\nHello World Verification".to_string();
-        let pdf_bytes = compile(source).expect("Compilation failed");
+        let pdf_bytes = compile(source, &ExportOptions::default()).expect("Compilation failed");
 
         // Extract text from the PDF bytes
         let text = pdf_extract::extract_text_from_mem(&pdf_bytes).expect("Failed to extract text");
@@ -53,4 +352,93 @@ mod tests {
             text
         );
     }
+
+    #[test]
+    fn test_smoke_test_succeeds() {
+        assert!(smoke_test().is_ok());
+    }
+
+    #[test]
+    fn test_compile_to_multiple_formats() {
+        let world = DocgenWorld::new("#set page(width: 100pt, height: 50pt)\nHello".to_string());
+        let outputs = compile_world_to_formats(
+            &world,
+            &[OutputFormat::Pdf, OutputFormat::Png],
+            &ExportOptions::default(),
+        )
+        .expect("Compilation failed");
+
+        assert_eq!(outputs.len(), 2);
+        assert!(outputs[&OutputFormat::Pdf].starts_with(b"%PDF"));
+        assert!(outputs[&OutputFormat::Png].starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+
+    #[test]
+    fn test_thumbnail_has_fixed_width() {
+        let world = DocgenWorld::new("#set page(width: 400pt, height: 200pt)\nHello".to_string());
+        let (outputs, thumbnail) =
+            compile_world_with_thumbnail(&world, &[OutputFormat::Pdf], &ExportOptions::default())
+                .expect("Compilation failed");
+
+        assert!(outputs[&OutputFormat::Pdf].starts_with(b"%PDF"));
+        assert!(thumbnail.starts_with(b"\x89PNG\r\n\x1a\n"));
+        // IHDR width and height, big-endian, follow the 8-byte signature and chunk header
+        let width = u32::from_be_bytes(thumbnail[16..20].try_into().unwrap());
+        let height = u32::from_be_bytes(thumbnail[20..24].try_into().unwrap());
+        assert_eq!(width, THUMBNAIL_WIDTH_PX as u32);
+        assert_eq!(height, THUMBNAIL_WIDTH_PX as u32 / 2);
+    }
+
+    #[test]
+    fn test_warnings_fail_strict_compile() {
+        let source =
+            "#set page(width: auto, height: auto)\n#set text(font: \"No Such Font\")\nHello";
+        assert!(compile(source.to_string(), &ExportOptions::default()).is_ok());
+
+        let strict = ExportOptions {
+            treat_warnings_as_errors: true,
+            ..Default::default()
+        };
+        let diags = compile(source.to_string(), &strict).unwrap_err();
+        assert!(
+            diags
+                .iter()
+                .all(|d| d.severity == typst::diag::Severity::Warning)
+        );
+        assert!(
+            diags
+                .iter()
+                .any(|d| d.message.contains("unknown font family")),
+            "{:?}",
+            diags
+        );
+    }
+
+    #[test]
+    fn test_source_excerpt_marks_error_line() {
+        let source = (1..=20)
+            .map(|i| {
+                if i == 12 {
+                    "#undefined-function()".to_string()
+                } else {
+                    format!("Line {}", i)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let world = DocgenWorld::new(source);
+        let diags = layout(&world, &ExportOptions::default()).unwrap_err();
+
+        let excerpt = source_excerpt(&world, &diags);
+        assert!(excerpt.starts_with("main.typ\n"), "{}", excerpt);
+        assert!(
+            excerpt.contains(">12 | #undefined-function()"),
+            "{}",
+            excerpt
+        );
+        assert!(excerpt.contains(" 9 | Line 9"), "{}", excerpt);
+        assert!(excerpt.contains(" 15 | Line 15"), "{}", excerpt);
+        assert!(!excerpt.contains("| Line 8"), "{}", excerpt);
+        assert!(!excerpt.contains("| Line 16"), "{}", excerpt);
+    }
 }
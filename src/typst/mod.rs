@@ -1,3 +1,8 @@
+pub mod assets;
+pub mod cache;
 pub mod compiler;
+pub mod paper;
+pub mod pool;
+pub mod prelude;
 pub mod transform;
 pub mod world;
@@ -0,0 +1,90 @@
+//! Default paper size of generated documents
+//!
+//! Documents are laid out on US Letter unless the operator sets
+//! `DOCGEN_DEFAULT_PAGE_SIZE`, e.g. to `a4` for deployments outside North
+//! America. The value is read once at startup, and an unrecognized one stops
+//! the server rather than silently falling back to Letter.
+
+use std::sync::OnceLock;
+
+/// Environment variable holding the default paper size
+pub const DEFAULT_PAGE_SIZE_ENV: &str = "DOCGEN_DEFAULT_PAGE_SIZE";
+
+/// Paper sizes the templates can be laid out on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PageSize {
+    /// 8.5 × 11 in
+    #[default]
+    UsLetter,
+    /// 210 × 297 mm
+    A4,
+}
+
+impl PageSize {
+    /// Every accepted value, for error messages
+    pub const ALL: [Self; 2] = [Self::UsLetter, Self::A4];
+
+    /// Typst name of the paper size, as passed to `page(paper: ...)`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PageSize::UsLetter => "us-letter",
+            PageSize::A4 => "a4",
+        }
+    }
+
+    /// Parse a configured size, case-insensitively; `letter` is accepted for `us-letter`
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim().to_ascii_lowercase();
+        match value.as_str() {
+            "letter" => Ok(PageSize::UsLetter),
+            _ => Self::ALL
+                .into_iter()
+                .find(|size| size.as_str() == value)
+                .ok_or_else(|| {
+                    let accepted: Vec<&str> = Self::ALL.iter().map(|s| s.as_str()).collect();
+                    format!(
+                        "unknown page size '{}' (expected one of: {})",
+                        value,
+                        accepted.join(", ")
+                    )
+                }),
+        }
+    }
+}
+
+static DEFAULT_PAGE_SIZE: OnceLock<PageSize> = OnceLock::new();
+
+/// Load the default paper size from the environment
+///
+/// Returns the size in effect. Must be called before the first document is
+/// transformed; later calls have no effect.
+pub fn init_from_env() -> Result<PageSize, String> {
+    let size = match std::env::var(DEFAULT_PAGE_SIZE_ENV) {
+        Ok(value) if !value.trim().is_empty() => PageSize::parse(&value)
+            .map_err(|e| format!("Invalid {}: {}", DEFAULT_PAGE_SIZE_ENV, e))?,
+        _ => PageSize::default(),
+    };
+    let _ = DEFAULT_PAGE_SIZE.set(size);
+    Ok(default_page_size())
+}
+
+/// The configured default paper size (US Letter if none was loaded)
+pub fn default_page_size() -> PageSize {
+    DEFAULT_PAGE_SIZE.get().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_size_parsing() {
+        assert_eq!(PageSize::parse("A4"), Ok(PageSize::A4));
+        assert_eq!(PageSize::parse(" us-letter "), Ok(PageSize::UsLetter));
+        assert_eq!(PageSize::parse("Letter"), Ok(PageSize::UsLetter));
+
+        let error = PageSize::parse("b17").unwrap_err();
+        assert!(error.contains("'b17'"), "{}", error);
+        assert!(error.contains("us-letter, a4"), "{}", error);
+    }
+}
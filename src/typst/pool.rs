@@ -0,0 +1,118 @@
+//! Dedicated thread pool for Typst compilation
+//!
+//! Compiling a document is CPU-bound and can take hundreds of milliseconds, so
+//! it must not run on the async runtime's worker threads. Jobs go to a bounded
+//! rayon pool instead of tokio's blocking pool, so a burst of compiles queues
+//! up here rather than starving unrelated blocking work (file I/O, DNS).
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::LazyLock;
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+/// Environment variable holding the number of compilation threads
+pub const COMPILE_THREADS_ENV: &str = "DOCGEN_COMPILE_THREADS";
+
+static COMPILE_POOL: LazyLock<CompilePool> = LazyLock::new(CompilePool::from_env);
+
+/// The shared pool used for all document compilation
+pub fn compile_pool() -> &'static CompilePool {
+    &COMPILE_POOL
+}
+
+/// A fixed-size pool that runs compile jobs and hands results back to async code
+pub struct CompilePool {
+    pool: ThreadPool,
+}
+
+impl CompilePool {
+    pub fn new(threads: usize) -> Self {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads.max(1))
+            .thread_name(|i| format!("docgen-compile-{}", i))
+            .build()
+            .expect("Failed to build compilation thread pool");
+        Self { pool }
+    }
+
+    /// Size the pool from the environment, defaulting to the number of CPUs
+    pub fn from_env() -> Self {
+        let threads = std::env::var(COMPILE_THREADS_ENV)
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .filter(|&n: &usize| n > 0)
+            .unwrap_or_else(|| {
+                std::thread::available_parallelism()
+                    .map(|n| n.get())
+                    .unwrap_or(1)
+            });
+        Self::new(threads)
+    }
+
+    pub fn threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Run `job` on the pool and wait for its result without blocking the runtime
+    ///
+    /// A panic inside the job is resumed on the awaiting task.
+    pub async fn run<F, T>(&self, job: F) -> T
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.pool.spawn(move || {
+            let _ = tx.send(panic::catch_unwind(AssertUnwindSafe(job)));
+        });
+
+        match rx.await.expect("Compilation pool dropped a job") {
+            Ok(result) => result,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_pool_bounds_concurrent_jobs() {
+        let pool = CompilePool::new(2);
+        assert_eq!(pool.threads(), 2);
+
+        let active = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let job = || {
+            let active = active.clone();
+            let peak = peak.clone();
+            pool.run(move || {
+                let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(50));
+                active.fetch_sub(1, Ordering::SeqCst);
+            })
+        };
+
+        tokio::join!(job(), job(), job(), job(), job(), job());
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_pool_returns_results_and_propagates_panics() {
+        let pool = CompilePool::new(1);
+        assert_eq!(pool.run(|| 40 + 2).await, 42);
+
+        let pool = Arc::new(pool);
+        let panicking = pool.clone();
+        let handle = tokio::spawn(async move { panicking.run(|| -> u32 { panic!("boom") }).await });
+        assert!(handle.await.unwrap_err().is_panic());
+
+        // The pool keeps working after a job panicked
+        assert_eq!(pool.run(|| "ok").await, "ok");
+    }
+}
@@ -0,0 +1,300 @@
+//! Site-wide Typst prelude
+//!
+//! Deployments can set `DOCGEN_PRELUDE_PATH` to a Typst file whose contents
+//! are prepended to every generated document source, e.g. to set fonts,
+//! colors, or a letterhead without editing the per-type templates. The file is
+//! read once at startup and rejected if it binds a name the templates or the
+//! transform rely on.
+
+use crate::typst::transform::{COVER_LETTER_TEMPLATE, RESUME_TEMPLATE};
+use crate::typst::world::TEMPLATE_MODULES;
+use std::collections::BTreeSet;
+use std::sync::{LazyLock, OnceLock};
+use typst::syntax::ast::{self, AstNode};
+use typst::syntax::{Source, Span, SyntaxKind, SyntaxNode};
+
+/// Environment variable holding the path of the prelude file
+pub const PRELUDE_PATH_ENV: &str = "DOCGEN_PRELUDE_PATH";
+
+/// Names the generated sources bind or call around the template
+const GLUE_NAMES: &[&str] = &[
+    "json",
+    "bytes",
+    "json-string",
+    "options-string",
+    "json-data",
+];
+
+/// Names a prelude must not bind, since doing so would change or break every
+/// document: the top-level definitions and imports of the templates and their
+/// modules, the global names they look up, and the `GLUE_NAMES`
+static RESERVED_NAMES: LazyLock<BTreeSet<String>> = LazyLock::new(|| {
+    let mut reserved: BTreeSet<String> = GLUE_NAMES.iter().map(|name| name.to_string()).collect();
+    let templates = [RESUME_TEMPLATE, COVER_LETTER_TEMPLATE]
+        .into_iter()
+        .chain(TEMPLATE_MODULES.iter().map(|(_, text)| *text));
+
+    for template in templates {
+        let root = typst::syntax::parse(template);
+        for child in root.children() {
+            let names = introduced_names(child).unwrap_or_default();
+            reserved.extend(names.into_iter().map(|(name, _)| name));
+        }
+
+        let mut bound = BTreeSet::new();
+        let mut used = BTreeSet::new();
+        collect_names(&root, &mut bound, &mut used);
+        reserved.extend(used.difference(&bound).cloned());
+    }
+    reserved
+});
+
+static PRELUDE: OnceLock<Option<String>> = OnceLock::new();
+
+/// Load the prelude named by the environment, if any
+///
+/// Returns the path that was loaded. Must be called before the first document
+/// is transformed; later calls have no effect.
+pub fn init_from_env() -> Result<Option<String>, String> {
+    let path = std::env::var(PRELUDE_PATH_ENV)
+        .ok()
+        .filter(|p| !p.trim().is_empty());
+
+    let prelude = match &path {
+        Some(path) => Some(load_prelude(path)?),
+        None => None,
+    };
+    let _ = PRELUDE.set(prelude);
+    Ok(path)
+}
+
+/// The loaded prelude, or `None` if none is configured
+pub fn prelude() -> Option<&'static str> {
+    PRELUDE.get().and_then(Option::as_deref)
+}
+
+/// Read and validate a prelude file
+pub fn load_prelude(path: &str) -> Result<String, String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read Typst prelude {}: {}", path, e))?;
+    validate_prelude(&source).map_err(|e| format!("Invalid Typst prelude {}: {}", path, e))?;
+    Ok(source)
+}
+
+/// Reject a prelude that does not parse or binds a reserved name
+///
+/// Every `let` (including destructuring patterns and bindings nested in
+/// blocks), `for` pattern and import is checked. Wildcard imports are rejected
+/// because the names they bind cannot be known without evaluating them.
+pub fn validate_prelude(text: &str) -> Result<(), String> {
+    let source = Source::detached(text);
+    let line = |span: Span| {
+        source
+            .range(span)
+            .and_then(|range| source.lines().byte_to_line(range.start))
+            .map_or(0, |line| line + 1)
+    };
+
+    if let Some(error) = source.root().errors().into_iter().next() {
+        return Err(format!("line {}: {}", line(error.span), error.message));
+    }
+
+    let mut result = Ok(());
+    visit(source.root(), &mut |node| {
+        if result.is_err() {
+            return;
+        }
+        let Some(names) = introduced_names(node) else {
+            result = Err(format!(
+                "line {} imports names that cannot be checked; list the imported items instead",
+                line(node.span())
+            ));
+            return;
+        };
+        if let Some((name, span)) = names
+            .into_iter()
+            .find(|(name, _)| RESERVED_NAMES.contains(name))
+        {
+            result = Err(format!(
+                "line {} redefines '{}', which the document templates rely on",
+                line(span),
+                name
+            ));
+        }
+    });
+    result
+}
+
+/// Names a `let`, `for` or import node binds, with where each is bound
+///
+/// Empty for other nodes, and `None` for an import whose names are only known
+/// at runtime (a wildcard, or a bare import of a computed path).
+fn introduced_names(node: &SyntaxNode) -> Option<Vec<(String, Span)>> {
+    let idents = |idents: Vec<ast::Ident>| {
+        idents
+            .into_iter()
+            .map(|ident| (ident.get().to_string(), ident.span()))
+            .collect()
+    };
+
+    if let Some(binding) = node.cast::<ast::LetBinding>() {
+        Some(idents(binding.kind().bindings()))
+    } else if let Some(for_loop) = node.cast::<ast::ForLoop>() {
+        Some(idents(for_loop.pattern().bindings()))
+    } else if let Some(import) = node.cast::<ast::ModuleImport>() {
+        let mut names: Vec<(String, Span)> = idents(import.new_name().into_iter().collect());
+        match import.imports() {
+            Some(ast::Imports::Wildcard) => return None,
+            Some(ast::Imports::Items(items)) => names.extend(idents(
+                items.iter().map(ast::ImportItem::bound_name).collect(),
+            )),
+            None if names.is_empty() => {
+                names.push((import.bare_name().ok()?.to_string(), import.span()))
+            }
+            None => {}
+        }
+        Some(names)
+    } else {
+        Some(Vec::new())
+    }
+}
+
+/// Collect the names a source binds anywhere and the identifiers it looks up
+///
+/// Field names and argument or dictionary keys are not lookups and are skipped.
+fn collect_names(node: &SyntaxNode, bound: &mut BTreeSet<String>, used: &mut BTreeSet<String>) {
+    visit(node, &mut |node| {
+        let names = introduced_names(node).unwrap_or_default();
+        bound.extend(names.into_iter().map(|(name, _)| name));
+
+        if let Some(closure) = node.cast::<ast::Closure>() {
+            for param in closure.params().children() {
+                let idents = match param {
+                    ast::Param::Pos(pattern) => pattern.bindings(),
+                    ast::Param::Named(named) => vec![named.name()],
+                    ast::Param::Spread(spread) => spread.sink_ident().into_iter().collect(),
+                };
+                bound.extend(idents.into_iter().map(|ident| ident.get().to_string()));
+            }
+        }
+
+        for (index, child) in node.children().enumerate() {
+            let is_key = match node.kind() {
+                SyntaxKind::FieldAccess => index > 0,
+                SyntaxKind::Named => index == 0,
+                SyntaxKind::ImportItemPath | SyntaxKind::RenamedImportItem => true,
+                _ => false,
+            };
+            if let Some(ident) = child.cast::<ast::Ident>()
+                && !is_key
+            {
+                used.insert(ident.get().to_string());
+            }
+        }
+    });
+}
+
+/// Call `f` on `node` and each of its descendants
+fn visit(node: &SyntaxNode, f: &mut impl FnMut(&SyntaxNode)) {
+    f(node);
+    for child in node.children() {
+        visit(child, f);
+    }
+}
+
+/// Prepend `prelude` to a generated source
+pub fn apply_prelude(source: String, prelude: Option<&str>) -> String {
+    match prelude {
+        Some(prelude) => format!("{}\n\n{}", prelude, source),
+        None => source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::documents::Resume;
+    use crate::typst::transform::{RenderOptions, transform_resume};
+
+    /// Fill of the first text run on a page
+    fn first_text_fill(frame: &typst::layout::Frame) -> Option<typst::visualize::Paint> {
+        use typst::layout::FrameItem;
+
+        frame.items().find_map(|(_, item)| match item {
+            FrameItem::Group(group) => first_text_fill(&group.frame),
+            FrameItem::Text(text) => Some(text.fill.clone()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_prelude_changes_text_color() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Test User", "email": "test@example.com" },
+            "work": []
+        }))
+        .unwrap();
+        let source = transform_resume(&resume, &RenderOptions::default()).unwrap();
+
+        let fill = |prelude: Option<&str>| {
+            let world =
+                crate::typst::world::DocgenWorld::new(apply_prelude(source.clone(), prelude));
+            let document = typst::compile::<typst::layout::PagedDocument>(&world)
+                .output
+                .expect("Compilation failed");
+            first_text_fill(&document.pages[0].frame)
+        };
+
+        let plain = fill(None);
+        let colored = fill(Some("#set text(fill: rgb(\"#1f4e79\"))"));
+        assert!(plain.is_some());
+        assert_ne!(plain, colored);
+    }
+
+    #[test]
+    fn test_prelude_rejects_reserved_names() {
+        assert!(validate_prelude("#set text(font: \"New Computer Modern\")").is_ok());
+        assert!(validate_prelude("#let brand = rgb(\"#1f4e79\")").is_ok());
+
+        let error = validate_prelude("#set page(margin: 1in)\n#let resume(data) = []").unwrap_err();
+        assert!(error.contains("line 2"), "{}", error);
+        assert!(error.contains("'resume'"), "{}", error);
+        assert!(validate_prelude("#let json-data = (:)").is_err());
+    }
+
+    #[test]
+    fn test_prelude_checks_every_binding() {
+        // Parameters are local to the function and may reuse reserved names
+        assert!(
+            validate_prelude("#let wrap(json) = json\n#show heading: set text(weight: \"bold\")")
+                .is_ok()
+        );
+
+        let rejected = |prelude: &str, name: &str| {
+            let error = validate_prelude(prelude).unwrap_err();
+            assert!(
+                error.contains(&format!("'{}'", name)),
+                "{}: {}",
+                prelude,
+                error
+            );
+        };
+        rejected("#let (json, x) = (1, 2)", "json");
+        rejected("#let (a: resume) = (a: 1)", "resume");
+        rejected("Letterhead #{ let x = 1; let bytes = 2 }", "bytes");
+        rejected("#for (i, text) in () []", "text");
+        rejected("#import \"components/url.typ\": display-url", "display-url");
+        rejected("#import \"brand.typ\": colors as json", "json");
+        rejected("#import \"contact-line.typ\"", "contact-line");
+
+        // Template internals are reserved too
+        rejected("#let contact-icon = none", "contact-icon");
+        rejected("#let icon-glyphs = (:)", "icon-glyphs");
+        rejected("#let breakable(s) = s", "breakable");
+
+        let error = validate_prelude("#import \"brand.typ\": *").unwrap_err();
+        assert!(error.contains("cannot be checked"), "{}", error);
+        let error = validate_prelude("#set text(\n").unwrap_err();
+        assert!(error.contains("line 1"), "{}", error);
+    }
+}
@@ -1,17 +1,606 @@
+use crate::documents::DocumentType;
 use crate::documents::cover_letter::CoverLetter;
-use crate::documents::resume::Resume;
+use crate::documents::resume::{Proficiency, Profile, Resume};
+use crate::typst::assets::ASSET_DIR;
+use crate::typst::paper::default_page_size;
+use crate::typst::prelude::{apply_prelude, prelude};
+use crate::typst::world::{ICON_FONT_FAMILY, font_family_available};
+use serde::{Deserialize, Serialize};
 use serde_json;
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
 
 /// The raw Typst template content for resumes
-const RESUME_TEMPLATE: &str = include_str!("../../templates/resume.typ");
+pub(crate) const RESUME_TEMPLATE: &str = include_str!("../../templates/resume.typ");
 
 /// The raw Typst template content for cover letters
-const COVER_LETTER_TEMPLATE: &str = include_str!("../../templates/cover_letter.typ");
+pub(crate) const COVER_LETTER_TEMPLATE: &str = include_str!("../../templates/cover_letter.typ");
+
+/// The raw Typst template content for side-by-side resume comparisons
+const COMPARISON_TEMPLATE: &str = include_str!("../../templates/comparison.typ");
+
+/// Asset names the two rendered PDFs of a comparison are loaded from
+pub const COMPARISON_ASSETS: [&str; 2] = ["left.pdf", "right.pdf"];
+
+/// Rendering options supplied alongside the resume data
+///
+/// These control presentation only and are passed to the template under
+/// `data.options`, separate from the resume content itself.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RenderOptions {
+    /// Render icons before contact items
+    #[serde(default)]
+    pub icons: bool,
+
+    /// How icons are drawn when `icons` is set
+    #[serde(default)]
+    pub icon_style: IconStyle,
+
+    /// Section names (see `SECTION_NAMES`) that should start on a new page
+    #[serde(default)]
+    pub page_breaks: Vec<String>,
+
+    /// Heading color per section name (see `SECTION_NAMES`), as `#RGB` or
+    /// `#RRGGBB` hex; other sections keep the default black
+    #[serde(default)]
+    pub section_colors: BTreeMap<String, String>,
+
+    /// Render a small "Generated on <date>" line in the page footer
+    #[serde(default)]
+    pub footer_timestamp: bool,
+
+    /// Show or hide the "Page X of Y" footer, overriding the resume's own
+    /// `showPageNumbers`; unset leaves that setting (default: shown) in charge
+    #[serde(default)]
+    pub page_numbers: Option<bool>,
+
+    /// Allowlisted style tweaks applied by the template
+    #[serde(default)]
+    pub style_overrides: StyleOverrides,
+
+    /// Where the professional summary is rendered
+    #[serde(default)]
+    pub summary_placement: SummaryPlacement,
+
+    /// How the professional summary is set off from the rest of the page
+    #[serde(default)]
+    pub summary_style: SummaryStyle,
+
+    /// How skill groups are laid out
+    #[serde(default)]
+    pub skills_style: SkillsStyle,
+
+    /// How contact details are laid out under the name
+    #[serde(default)]
+    pub contact_style: ContactStyle,
+
+    /// How language proficiency is shown
+    #[serde(default)]
+    pub language_style: LanguageStyle,
+
+    /// Rule drawn under each section heading
+    #[serde(default)]
+    pub divider_style: DividerStyle,
+
+    /// Vertical spacing between entries and their lines
+    #[serde(default)]
+    pub density: Density,
+
+    /// Replace contact details with placeholders (see `Resume::redact_contact`)
+    #[serde(default)]
+    pub redact_contact: bool,
+
+    /// Render at most this many highlights per work, education, and project
+    /// entry (0 or absent means no limit)
+    #[serde(default)]
+    pub max_highlights_per_entry: Option<usize>,
+
+    /// Merge same-named skill categories and drop keywords already listed in
+    /// an earlier category (see `Resume::dedupe_skills`)
+    #[serde(default)]
+    pub dedupe_skills: bool,
+
+    /// Render awards and certifications together under one "Honors &
+    /// Certifications" heading, placed where the first of them is ordered
+    #[serde(default)]
+    pub combine_recognition: bool,
+
+    /// Section ordering preset, used when the resume has no `sectionOrder`
+    #[serde(default)]
+    pub emphasis: Option<Emphasis>,
+
+    /// Keywords set in bold wherever they appear in highlights and the
+    /// summary (case-insensitive, whole words only)
+    #[serde(default)]
+    pub highlight_keywords: Vec<String>,
+
+    /// Render `$...$` segments of publication titles, venues, and summaries
+    /// as inline math; off by default, so dollar signs are shown literally
+    #[serde(default)]
+    pub publication_math: bool,
+}
+
+/// Placement of the professional summary
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryPlacement {
+    /// Directly under the name and contact line
+    #[default]
+    Header,
+    /// As its own "Summary" section ahead of the other sections
+    Section,
+}
+
+impl SummaryPlacement {
+    /// Every accepted value, for schemas and option listings
+    pub const ALL: [Self; 2] = [Self::Header, Self::Section];
+}
+
+/// Presentation of the professional summary
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SummaryStyle {
+    /// Regular body text
+    #[default]
+    Plain,
+    /// A shaded, bordered callout box
+    Box,
+}
+
+impl SummaryStyle {
+    /// Every accepted value, for schemas and option listings
+    pub const ALL: [Self; 2] = [Self::Plain, Self::Box];
+}
+
+/// Rendering options supplied alongside the cover letter data
+///
+/// Passed to the template under `data.options`, like [`RenderOptions`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoverLetterOptions {
+    /// Leave a ruled blank space above the typed name for a signature
+    /// applied later (e.g. by an e-signature service)
+    #[serde(default)]
+    pub signature_space: bool,
+}
+
+impl CoverLetterOptions {
+    /// Serialize the options for the template, adding values resolved at transform time
+    fn to_template_json(&self) -> Result<String, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        value["paper"] = default_page_size().as_str().into();
+        serde_json::to_string(&value)
+    }
+}
+
+/// Layout of the skills section
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillsStyle {
+    /// One line per group: "Languages: Rust, Python"
+    #[default]
+    Inline,
+    /// Group name followed by a bulleted list of its keywords
+    Grouped,
+}
+
+impl SkillsStyle {
+    /// Every accepted value, for schemas and option listings
+    pub const ALL: [Self; 2] = [Self::Inline, Self::Grouped];
+}
+
+/// Layout of the contact details in the resume header
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContactStyle {
+    /// Location on its own line above a pipe-separated contact line
+    #[default]
+    Stacked,
+    /// Location and all contact items on one bullet-separated, wrapping line
+    Inline,
+}
+
+impl ContactStyle {
+    /// Every accepted value, for schemas and option listings
+    pub const ALL: [Self; 2] = [Self::Stacked, Self::Inline];
+}
+
+/// Presentation of language proficiency
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LanguageStyle {
+    /// The fluency text in parentheses: "Spanish (Fluent)"
+    #[default]
+    Text,
+    /// A row of filled and empty dots for recognized fluency values, falling
+    /// back to the text for anything else
+    Dots,
+}
+
+impl LanguageStyle {
+    /// Every accepted value, for schemas and option listings
+    pub const ALL: [Self; 2] = [Self::Text, Self::Dots];
+}
+
+/// Source of the contact icons
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconStyle {
+    /// Glyphs from the bundled icon font, with text labels if it is missing
+    #[default]
+    Font,
+    /// Bundled SVG images, with network-specific GitHub and LinkedIn icons
+    Svg,
+}
+
+impl IconStyle {
+    /// Every accepted value, for schemas and option listings
+    pub const ALL: [Self; 2] = [Self::Font, Self::Svg];
+}
+
+/// SVG sources of the contact icons drawn with `IconStyle::Svg`, by kind
+const CONTACT_ICON_SVGS: &[(&str, &str)] = &[
+    ("email", include_str!("../../templates/icons/email.svg")),
+    ("phone", include_str!("../../templates/icons/phone.svg")),
+    ("github", include_str!("../../templates/icons/github.svg")),
+    (
+        "linkedin",
+        include_str!("../../templates/icons/linkedin.svg"),
+    ),
+    ("link", include_str!("../../templates/icons/link.svg")),
+];
+
+/// Icon kind of a profile link: its network if it has its own icon
+fn profile_icon(profile: &Profile) -> &'static str {
+    match profile.canonical_network() {
+        Some("GitHub") => "github",
+        Some("LinkedIn") => "linkedin",
+        _ => "link",
+    }
+}
+
+/// Rule under section headings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DividerStyle {
+    /// Across the full text width
+    #[default]
+    Full,
+    /// A short rule under the start of the heading
+    Short,
+    /// No rule; headings are set off by size and weight alone
+    None,
+}
+
+impl DividerStyle {
+    /// Every accepted value, for schemas and option listings
+    pub const ALL: [Self; 3] = [Self::Full, Self::Short, Self::None];
+}
+
+/// Vertical spacing of the resume
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Density {
+    /// The regular gaps between entries, header lines, and highlights
+    #[default]
+    Comfortable,
+    /// Tighter gaps, for fitting more on a page
+    Compact,
+}
+
+impl Density {
+    /// Every accepted value, for schemas and option listings
+    pub const ALL: [Self; 2] = [Self::Comfortable, Self::Compact];
+}
+
+/// Section ordering presets for common career stages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Emphasis {
+    /// Work history first, education after skills
+    Experienced,
+    /// Education first, followed by projects and experience
+    Student,
+}
+
+impl Emphasis {
+    /// Every accepted value, for schemas and option listings
+    pub const ALL: [Self; 2] = [Self::Experienced, Self::Student];
+
+    /// The section order this preset stands for
+    pub fn section_order(self) -> [&'static str; 8] {
+        match self {
+            Emphasis::Experienced => [
+                "experience",
+                "projects",
+                "skills",
+                "education",
+                "certifications",
+                "awards",
+                "publications",
+                "languages",
+            ],
+            Emphasis::Student => [
+                "education",
+                "projects",
+                "experience",
+                "skills",
+                "awards",
+                "certifications",
+                "publications",
+                "languages",
+            ],
+        }
+    }
+}
+
+/// Allowlisted style tweaks
+///
+/// Each field maps to a fixed template setting, so no user-supplied Typst
+/// code ever reaches the compiler. Unknown keys are rejected.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StyleOverrides {
+    /// Justify paragraphs (template default: true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub justify: Option<bool>,
+
+    /// Font weight of section headings (template default: bold)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading_weight: Option<HeadingWeight>,
+
+    /// Thickness of the rule under section headings, in points (template default: 0.5)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_thickness: Option<f64>,
+}
+
+/// Largest accepted `rule_thickness`, in points
+pub const MAX_RULE_THICKNESS: f64 = 3.0;
+
+/// Font weights allowed for section headings
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeadingWeight {
+    Regular,
+    Medium,
+    Semibold,
+    Bold,
+    Extrabold,
+}
+
+impl HeadingWeight {
+    /// Every accepted value, for schemas and option listings
+    pub const ALL: [Self; 5] = [
+        Self::Regular,
+        Self::Medium,
+        Self::Semibold,
+        Self::Bold,
+        Self::Extrabold,
+    ];
+}
+
+impl RenderOptions {
+    /// The effective highlight limit, treating 0 as no limit
+    pub fn highlight_limit(&self) -> Option<usize> {
+        self.max_highlights_per_entry.filter(|&max| max > 0)
+    }
+
+    /// Serialize the options for the template, adding values resolved at transform time
+    fn to_template_json(
+        &self,
+        resume: &Resume,
+        preview_section: Option<&str>,
+    ) -> Result<String, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        value["paper"] = default_page_size().as_str().into();
+        if self.icons && font_family_available(ICON_FONT_FAMILY) {
+            value["icon_font"] = serde_json::Value::String(ICON_FONT_FAMILY.to_string());
+        }
+        if self.icons && self.icon_style == IconStyle::Svg {
+            let svgs: BTreeMap<&str, &str> = CONTACT_ICON_SVGS.iter().copied().collect();
+            value["icon_svgs"] = serde_json::to_value(svgs)?;
+            let kinds: Vec<&str> = resume.basics.profiles.iter().map(profile_icon).collect();
+            value["profile_icons"] = serde_json::to_value(kinds)?;
+        }
+        if self.language_style == LanguageStyle::Dots {
+            // One level (or null when unrecognized) per language, in order
+            let levels: Vec<Option<u8>> = resume
+                .languages
+                .iter()
+                .map(|language| language.proficiency().map(Proficiency::level))
+                .collect();
+            value["language_levels"] = serde_json::to_value(levels)?;
+            value["language_max_level"] = Proficiency::LEVELS.into();
+        }
+        if let Some(section) = preview_section {
+            value["preview_section"] = serde_json::Value::String(section.to_string());
+        }
+        if let Some(pattern) = keyword_pattern(&self.highlight_keywords) {
+            value["keyword_pattern"] = serde_json::Value::String(pattern);
+        }
+        if self.publication_math {
+            value["math_segments"] = serde_json::to_value(publication_math(resume))?;
+        }
+        serde_json::to_string(&value)
+    }
+}
+
+/// Case-insensitive regex matching any of the keywords as a whole word
+///
+/// Word boundaries are only required next to word characters, so keywords
+/// like "C++" or ".NET" still match.
+fn keyword_pattern(keywords: &[String]) -> Option<String> {
+    let alternatives: Vec<String> = keywords
+        .iter()
+        .map(|keyword| keyword.trim())
+        .filter(|keyword| !keyword.is_empty())
+        .map(|keyword| {
+            let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+            let mut alternative = String::new();
+            if is_word(keyword.chars().next()) {
+                alternative.push_str("\\b");
+            }
+            for c in keyword.chars() {
+                if "\\.+*?()|[]{}^$".contains(c) {
+                    alternative.push('\\');
+                }
+                alternative.push(c);
+            }
+            if is_word(keyword.chars().last()) {
+                alternative.push_str("\\b");
+            }
+            alternative
+        })
+        .collect();
+
+    (!alternatives.is_empty()).then(|| format!("(?i)(?:{})", alternatives.join("|")))
+}
+
+/// The `$...$` segments of publication text the template may render as math
+///
+/// Text with an odd number of dollar signs has no well-defined segments and
+/// is skipped. A segment is only listed if it parses as plain math (see
+/// `is_safe_math`), so evaluating it in the template can neither run code nor
+/// fail the document; unlisted segments are shown literally.
+fn publication_math(resume: &Resume) -> Vec<String> {
+    let mut segments: Vec<String> = resume
+        .publications
+        .iter()
+        .flat_map(|p| {
+            [
+                Some(p.title.as_str()),
+                p.venue.as_deref(),
+                p.summary.as_deref(),
+            ]
+        })
+        .flatten()
+        .filter(|text| text.matches('$').count() % 2 == 0)
+        .flat_map(|text| text.split('$').skip(1).step_by(2))
+        .filter(|segment| is_safe_math(segment))
+        .map(str::to_string)
+        .collect();
+    segments.sort();
+    segments.dedup();
+    segments
+}
+
+/// Whether a math segment is plain math: it parses without errors, contains
+/// only math markup (letters, numbers, attachments, fractions, roots,
+/// delimiters, shorthands) and names nothing but symbols and operators from
+/// the math scope, such as `alpha`, `arrow.r` or `sin`
+///
+/// Embedded code, function calls and unknown names are rejected without
+/// compiling anything, so checking costs no more than parsing.
+fn is_safe_math(segment: &str) -> bool {
+    let root = typst::syntax::parse_math(segment);
+    !segment.trim().is_empty() && !root.erroneous() && is_plain_math(&root)
+}
+
+fn is_plain_math(node: &typst::syntax::SyntaxNode) -> bool {
+    use typst::syntax::SyntaxKind;
+
+    match node.kind() {
+        SyntaxKind::MathIdent | SyntaxKind::FieldAccess => math_symbol(node).is_some(),
+        SyntaxKind::Math
+        | SyntaxKind::MathText
+        | SyntaxKind::MathShorthand
+        | SyntaxKind::MathAlignPoint
+        | SyntaxKind::MathDelimited
+        | SyntaxKind::MathAttach
+        | SyntaxKind::MathPrimes
+        | SyntaxKind::MathFrac
+        | SyntaxKind::MathRoot
+        | SyntaxKind::Space
+        | SyntaxKind::Linebreak
+        | SyntaxKind::Escape
+        | SyntaxKind::Str
+        | SyntaxKind::Hat
+        | SyntaxKind::Underscore
+        | SyntaxKind::Slash
+        | SyntaxKind::Prime
+        | SyntaxKind::Root
+        | SyntaxKind::LeftParen
+        | SyntaxKind::RightParen => node.children().all(is_plain_math),
+        _ => false,
+    }
+}
+
+/// The math-scope value a name like `alpha` or `arrow.r.long` refers to, if it
+/// is a symbol (with valid modifiers) or a content value such as `sin` or `quad`
+fn math_symbol(node: &typst::syntax::SyntaxNode) -> Option<typst::foundations::Value> {
+    use typst::foundations::{Module, Value};
+    use typst::syntax::SyntaxKind;
+
+    static MATH: LazyLock<Module> = LazyLock::new(typst::math::module);
+
+    let value = match node.kind() {
+        SyntaxKind::MathIdent => MATH.scope().get(node.text())?.read().clone(),
+        SyntaxKind::FieldAccess => {
+            let mut children = node.children();
+            let target = math_symbol(children.next()?)?;
+            let field = children.find(|c| c.kind() == SyntaxKind::Ident)?;
+            match target {
+                Value::Symbol(symbol) => Value::Symbol(symbol.modified((), field.text()).ok()?),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    matches!(value, Value::Symbol(_) | Value::Content(_)).then_some(value)
+}
 
 /// Transforms a Resume struct into a Typst source string
-pub fn transform_resume(resume: &Resume) -> Result<String, serde_json::Error> {
+pub fn transform_resume(
+    resume: &Resume,
+    options: &RenderOptions,
+) -> Result<String, serde_json::Error> {
+    resume_source(resume, options, None)
+}
+
+/// Transforms a single resume section into a Typst source string for previewing
+///
+/// Only the named section is rendered (no header, summary, or footer), on a
+/// page whose height fits the section.
+pub fn transform_resume_section(
+    resume: &Resume,
+    options: &RenderOptions,
+    section: &str,
+) -> Result<String, serde_json::Error> {
+    resume_source(resume, options, Some(section))
+}
+
+fn resume_source(
+    resume: &Resume,
+    options: &RenderOptions,
+    preview_section: Option<&str>,
+) -> Result<String, serde_json::Error> {
+    // Blank optional fields would otherwise render as empty lines or separators
+    let mut resume = resume.clone();
+    resume.clear_empty_fields();
+    if options.redact_contact {
+        resume.redact_contact();
+    }
+    if let Some(max) = options.highlight_limit() {
+        resume.truncate_highlights(max);
+    }
+    if options.dedupe_skills {
+        resume.dedupe_skills();
+    }
+    // An explicit sectionOrder always wins over the preset
+    if resume.section_order.is_none()
+        && let Some(emphasis) = options.emphasis
+    {
+        resume.section_order = Some(
+            emphasis
+                .section_order()
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        );
+    }
+
     // Serialize the resume data to JSON
-    let json_data = serde_json::to_string(resume)?;
+    let json_data = serde_json::to_string(&resume)?;
+    let json_options = options.to_template_json(&resume, preview_section)?;
 
     // Construct the full Typst source
     // We treat the template as a library and import it or just append the call.
@@ -33,21 +622,47 @@ pub fn transform_resume(resume: &Resume) -> Result<String, serde_json::Error> {
 {json}
 `````.text
 
-#let json-data = json.decode(json-string)
+#let options-string = `````
+{options}
+`````.text
+
+#let json-data = json(bytes(json-string)) + (options: json(bytes(options-string)))
 
 #resume(json-data)
 "#,
         template = RESUME_TEMPLATE,
-        json = json_data
+        json = json_data,
+        options = json_options
     );
 
-    Ok(source)
+    Ok(apply_prelude(source, prelude()))
+}
+
+/// Typst source for a document type's example, rendered with default options
+pub fn transform_example(document_type: &DocumentType) -> Result<String, String> {
+    let invalid = |e: serde_json::Error| format!("invalid {} example: {}", document_type.name, e);
+    match document_type.name {
+        "resume" => {
+            let resume: Resume = serde_json::from_str(document_type.example).map_err(invalid)?;
+            transform_resume(&resume, &RenderOptions::default()).map_err(invalid)
+        }
+        "cover_letter" => {
+            let letter: CoverLetter =
+                serde_json::from_str(document_type.example).map_err(invalid)?;
+            transform_cover_letter(&letter, &CoverLetterOptions::default()).map_err(invalid)
+        }
+        other => Err(format!("no template for document type '{}'", other)),
+    }
 }
 
 /// Transforms a CoverLetter struct into a Typst source string
-pub fn transform_cover_letter(cover_letter: &CoverLetter) -> Result<String, serde_json::Error> {
-    // Serialize the cover letter data to JSON
+pub fn transform_cover_letter(
+    cover_letter: &CoverLetter,
+    options: &CoverLetterOptions,
+) -> Result<String, serde_json::Error> {
+    // Serialize the cover letter data and options to JSON
     let json_data = serde_json::to_string(cover_letter)?;
+    let json_options = options.to_template_json()?;
 
     // Construct the full Typst source
     let source = format!(
@@ -57,15 +672,52 @@ pub fn transform_cover_letter(cover_letter: &CoverLetter) -> Result<String, serd
 {json}
 `````.text
 
-#let json-data = json.decode(json-string)
+#let options-string = `````
+{options}
+`````.text
+
+#let json-data = json(bytes(json-string)) + (options: json(bytes(options-string)))
 
 #cover_letter(json-data)
 "#,
         template = COVER_LETTER_TEMPLATE,
-        json = json_data
+        json = json_data,
+        options = json_options
     );
 
-    Ok(source)
+    Ok(apply_prelude(source, prelude()))
+}
+
+/// Typst source laying out two rendered resumes side by side
+///
+/// The PDFs are read from the `COMPARISON_ASSETS`, and `pages` gives the page
+/// count of each. Every landscape page shows the same page of both resumes;
+/// the shorter resume leaves its column empty once it runs out.
+pub fn transform_comparison(pages: [usize; 2]) -> Result<String, serde_json::Error> {
+    let side = |index: usize| {
+        serde_json::json!({
+            "file": format!("{}/{}", ASSET_DIR, COMPARISON_ASSETS[index]),
+            "pages": pages[index],
+        })
+    };
+    let data = serde_json::json!({
+        "left": side(0),
+        "right": side(1),
+        "paper": default_page_size().as_str(),
+    });
+
+    Ok(format!(
+        r#"{template}
+
+#let json-string = `````
+{json}
+`````.text
+
+#comparison(json(bytes(json-string)))
+"#,
+        template = COMPARISON_TEMPLATE,
+        json = serde_json::to_string(&data)?
+    ))
 }
 
 #[cfg(test)]
@@ -92,13 +744,14 @@ mod tests {
             awards: vec![],
             languages: vec![],
             publications: vec![],
+            custom_sections: vec![],
             section_order: None,
             section_titles: None,
             show_header: None,
             show_page_numbers: None,
         };
 
-        let result = transform_resume(&resume);
+        let result = transform_resume(&resume, &RenderOptions::default());
         assert!(result.is_ok());
         let source = result.unwrap();
 
@@ -127,15 +780,16 @@ mod tests {
             awards: vec![],
             languages: vec![],
             publications: vec![],
+            custom_sections: vec![],
             section_order: None,
             section_titles: None,
             show_header: None,
             show_page_numbers: None,
         };
 
-        let source = transform_resume(&resume).unwrap();
+        let source = transform_resume(&resume, &RenderOptions::default()).unwrap();
         // println!("{}", source); // Uncomment to debug
-        let result = crate::typst::compiler::compile(source);
+        let result = crate::typst::compiler::compile(source, &Default::default());
         if let Err(e) = &result {
             for diag in e {
                 println!("Diag: {:?} {}", diag.severity, diag.message);
@@ -163,6 +817,7 @@ mod tests {
             awards: vec![],
             languages: vec![],
             publications: vec![],
+            custom_sections: vec![],
             section_order: Some(vec![
                 "experience".to_string(),
                 "education".to_string(),
@@ -173,13 +828,1214 @@ mod tests {
             show_page_numbers: None,
         };
 
-        let source = transform_resume(&resume).unwrap();
+        let source = transform_resume(&resume, &RenderOptions::default()).unwrap();
         // Verify section order is included in the JSON
         assert!(source.contains("sectionOrder"));
         assert!(source.contains("experience"));
 
         // Verify it compiles successfully
-        let result = crate::typst::compiler::compile(source);
+        let result = crate::typst::compiler::compile(source, &Default::default());
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_transform_with_icons() {
+        let resume = Resume {
+            basics: Basics {
+                name: "Test User".to_string(),
+                email: "test@example.com".to_string(),
+                phone: Some("+1-555-123-4567".to_string()),
+                location: None,
+                summary: None,
+                profiles: vec![],
+            },
+            work: vec![],
+            education: vec![],
+            skills: vec![],
+            projects: vec![],
+            certifications: vec![],
+            awards: vec![],
+            languages: vec![],
+            publications: vec![],
+            custom_sections: vec![],
+            section_order: None,
+            section_titles: None,
+            show_header: None,
+            show_page_numbers: None,
+        };
+
+        let options = RenderOptions {
+            icons: true,
+            ..Default::default()
+        };
+        let source = transform_resume(&resume, &options).unwrap();
+        assert!(
+            source.contains(ICON_FONT_FAMILY),
+            "Icon font should be passed to the template"
+        );
+
+        let world = crate::typst::world::DocgenWorld::new(source);
+        let document = typst::compile::<typst::layout::PagedDocument>(&world)
+            .output
+            .expect("Compilation failed");
+        // Phone and email icons, drawn from the icon font
+        assert_eq!(
+            font_text(&document.pages[0].frame, ICON_FONT_FAMILY),
+            "\u{E001}\u{E000}"
+        );
+    }
+
+    #[test]
+    fn test_icons_fall_back_to_labels_without_icon_font() {
+        let render = |icon_font: &str| {
+            let source = format!(
+                "#import \"components/contact.typ\": contact-line\n\
+                 #set page(width: auto, height: auto)\n\
+                 #contact-line((email: \"a@example.com\", phone: \"555\", \
+                 profiles: ((url: \"https://example.com\"),)), icons: true, icon-font: {})",
+                icon_font
+            );
+            let world = crate::typst::world::DocgenWorld::new(source);
+            let document = typst::compile::<typst::layout::PagedDocument>(&world)
+                .output
+                .expect("Compilation failed");
+            frame_text(&document.pages[0].frame)
+        };
+
+        let labels = render("none");
+        for label in ["Phone:", "Email:", "Web:"] {
+            assert!(labels.contains(label), "Missing '{}' in {}", label, labels);
+        }
+
+        let icons = render(&format!("\"{}\"", ICON_FONT_FAMILY));
+        assert!(!icons.contains("Email:"), "{}", icons);
+        assert!(icons.contains('\u{E000}'), "{}", icons);
+    }
+
+    /// Text set in the given font family, concatenated in layout order
+    fn font_text(frame: &typst::layout::Frame, family: &str) -> String {
+        use typst::layout::FrameItem;
+
+        frame
+            .items()
+            .map(|(_, item)| match item {
+                FrameItem::Group(group) => font_text(&group.frame, family),
+                FrameItem::Text(text) if text.font.info().family == family => text.text.to_string(),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_svg_icons_draw_images() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": {
+                "name": "Test User",
+                "email": "test@example.com",
+                "phone": "+1-555-123-4567",
+                "profiles": [
+                    { "network": "GitHub", "url": "https://github.com/test" },
+                    { "network": "linked in", "url": "https://linkedin.com/in/test" },
+                    { "network": "Portfolio", "url": "https://test.example.com" }
+                ]
+            },
+            "work": []
+        }))
+        .unwrap();
+
+        let options = RenderOptions {
+            icons: true,
+            icon_style: IconStyle::Svg,
+            ..Default::default()
+        };
+        let source = transform_resume(&resume, &options).unwrap();
+        assert!(source.contains(r#""icon_svgs":{"#));
+        assert!(source.contains(r#""profile_icons":["github","linkedin","link"]"#));
+
+        let world = crate::typst::world::DocgenWorld::new(source);
+        let document = typst::compile::<typst::layout::PagedDocument>(&world)
+            .output
+            .expect("Compilation failed");
+        // One image per contact item: phone, email, and three profiles
+        assert_eq!(images(&document.pages[0].frame), 5);
+
+        let font_icons = transform_resume(
+            &resume,
+            &RenderOptions {
+                icons: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(!font_icons.contains(r#""icon_svgs":"#));
+    }
+
+    /// Number of images in a frame
+    fn images(frame: &typst::layout::Frame) -> usize {
+        use typst::layout::FrameItem;
+
+        frame
+            .items()
+            .map(|(_, item)| match item {
+                FrameItem::Group(group) => images(&group.frame),
+                FrameItem::Image(..) => 1,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_page_break_before_publications() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Test User", "email": "test@example.com" },
+            "work": [{ "company": "Tech Corp", "position": "Engineer" }],
+            "publications": [{ "title": "A Paper Worth Its Own Page" }]
+        }))
+        .unwrap();
+
+        let count_pages = |options: &RenderOptions| {
+            let source = transform_resume(&resume, options).unwrap();
+            crate::typst::compiler::compile_document(source)
+                .expect("Compilation failed")
+                .pages
+                .len()
+        };
+
+        let without_break = count_pages(&RenderOptions::default());
+        let with_break = count_pages(&RenderOptions {
+            page_breaks: vec!["publications".to_string()],
+            ..Default::default()
+        });
+
+        assert_eq!(without_break, 1);
+        assert_eq!(with_break, 2);
+    }
+
+    #[test]
+    fn test_serialization_is_deterministic() {
+        let mut data: serde_json::Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/full_resume.json")).unwrap();
+        data["sectionTitles"] = serde_json::json!({
+            "skills": "Core Competencies",
+            "experience": "Employment",
+            "education": "Schooling",
+            "projects": "Selected Work",
+            "awards": "Honors",
+            "languages": "Spoken Languages"
+        });
+        let options = RenderOptions {
+            highlight_keywords: vec!["Rust".to_string(), "Bazel".to_string()],
+            ..Default::default()
+        };
+
+        // Separately parsed copies of the same input produce the same source
+        let render = || {
+            let resume: Resume = serde_json::from_value(data.clone()).unwrap();
+            transform_resume(&resume, &options).unwrap()
+        };
+        let first = render();
+        for _ in 0..8 {
+            assert_eq!(render(), first);
+        }
+    }
+
+    #[test]
+    fn test_custom_section_renders_items() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Test User", "email": "test@example.com" },
+            "work": [{ "company": "Tech Corp", "position": "Engineer" }],
+            "customSections": [{
+                "title": "Volunteering",
+                "items": ["Food bank shifts", "Coding club mentor"]
+            }]
+        }))
+        .unwrap();
+
+        let source = transform_resume(&resume, &RenderOptions::default()).unwrap();
+        assert!(source.contains("Volunteering"));
+
+        let pdf_bytes = crate::typst::compiler::compile(source, &Default::default())
+            .expect("Compilation failed");
+        let text = pdf_extract::extract_text_from_mem(&pdf_bytes).unwrap();
+        assert!(text.contains("Food bank shifts"));
+        assert!(text.contains("Coding club mentor"));
+    }
+
+    #[test]
+    fn test_full_fixture_renders_every_section_in_default_order() {
+        use crate::documents::resume::SECTION_NAMES;
+
+        let fixture: serde_json::Value =
+            serde_json::from_str(include_str!("../../tests/fixtures/full_resume.json")).unwrap();
+
+        // The fixture populates every content field of the schema, and each one
+        // is a known section, so a new section has to be added here to pass
+        let settings = [
+            "sectionOrder",
+            "sectionTitles",
+            "showHeader",
+            "showPageNumbers",
+        ];
+        let schema = schemars::schema_for!(Resume).to_value();
+        for field in schema["properties"].as_object().unwrap().keys() {
+            if settings.contains(&field.as_str()) {
+                continue;
+            }
+            let populated = fixture
+                .get(field)
+                .is_some_and(|value| value.as_array().is_none_or(|items| !items.is_empty()));
+            assert!(populated, "full_resume.json does not populate '{}'", field);
+
+            let section = if field == "work" {
+                "experience"
+            } else {
+                field.as_str()
+            };
+            assert!(
+                ["basics", "customSections"].contains(&field.as_str())
+                    || SECTION_NAMES.contains(&section),
+                "'{}' is not a known section",
+                field
+            );
+        }
+
+        // One marker per section, in the default order; custom sections come last
+        let markers = [
+            ("education", "Lakeside Polytechnic"),
+            ("experience", "Northwind Systems"),
+            ("projects", "Quillmark"),
+            ("certifications", "Kestrel"),
+            ("awards", "Halcyon"),
+            ("publications", "Reproducible Builds"),
+            ("skills", "Bazel"),
+            ("languages", "Portuguese"),
+            ("Volunteering", "Riverside coding workshops"),
+        ];
+        let order: Vec<&str> = markers.iter().map(|(section, _)| *section).collect();
+        assert_eq!(order[..SECTION_NAMES.len()], SECTION_NAMES[..]);
+
+        let resume: Resume = serde_json::from_value(fixture).unwrap();
+        let source = transform_resume(&resume, &RenderOptions::default()).unwrap();
+        let pdf_bytes = crate::typst::compiler::compile(source, &Default::default())
+            .expect("Compilation failed");
+        let text = pdf_extract::extract_text_from_mem(&pdf_bytes).unwrap();
+
+        let mut previous = 0;
+        for (section, marker) in markers {
+            let position = text
+                .find(marker)
+                .unwrap_or_else(|| panic!("'{}' section missing from: {}", section, text));
+            assert!(position > previous, "'{}' section out of order", section);
+            previous = position;
+        }
+    }
+
+    #[test]
+    fn test_empty_optional_fields_are_treated_as_absent() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": {
+                "name": "Test User",
+                "email": "test@example.com",
+                "phone": "",
+                "location": " ",
+                "summary": "",
+                "profiles": [{ "network": "GitHub", "url": "https://github.com/test" }]
+            },
+            "work": [{
+                "company": "Tech Corp",
+                "position": "Engineer",
+                "location": "",
+                "startDate": "",
+                "endDate": "2024"
+            }]
+        }))
+        .unwrap();
+
+        let source = transform_resume(&resume, &RenderOptions::default()).unwrap();
+        assert!(!source.contains("\"phone\":\"\""));
+        assert!(!source.contains("\"summary\":\"\""));
+
+        let pdf_bytes = crate::typst::compiler::compile(source, &Default::default())
+            .expect("Compilation failed");
+        let text = pdf_extract::extract_text_from_mem(&pdf_bytes).unwrap();
+
+        // One separator between email and profile, one in the page footer
+        assert_eq!(text.matches('|').count(), 2, "stray separator in: {}", text);
+        // The blank start counts as missing, leaving the end-only form "– 2024"
+        assert_eq!(
+            text.matches('\u{2013}').count(),
+            1,
+            "unexpected date range in: {}",
+            text
+        );
+        assert!(text.contains("2024"), "end date missing: {}", text);
+    }
+
+    #[test]
+    fn test_section_preview_renders_only_that_section() {
+        let resume: Resume =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+
+        let source =
+            transform_resume_section(&resume, &RenderOptions::default(), "skills").unwrap();
+        let pdf_bytes = crate::typst::compiler::compile(source, &Default::default())
+            .expect("Compilation failed");
+        let text = pdf_extract::extract_text_from_mem(&pdf_bytes).unwrap();
+
+        let skill = &resume.skills[0].keywords[0];
+        assert!(text.contains(skill.as_str()), "skill missing: {}", text);
+        assert!(!text.contains("Jane Smith"), "header rendered: {}", text);
+        assert!(!text.contains("Tech Innovations"), "experience rendered");
+    }
+
+    #[test]
+    fn test_footer_timestamp_shows_current_year() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Test User", "email": "test@example.com" },
+            "work": [{ "company": "Tech Corp", "position": "Engineer" }]
+        }))
+        .unwrap();
+        let options = RenderOptions {
+            footer_timestamp: true,
+            ..Default::default()
+        };
+
+        let source = transform_resume(&resume, &options).unwrap();
+        let pdf_bytes = crate::typst::compiler::compile(source, &Default::default())
+            .expect("Compilation failed");
+        let text = pdf_extract::extract_text_from_mem(&pdf_bytes).unwrap();
+
+        let year = time::OffsetDateTime::now_utc().year().to_string();
+        assert!(text.contains("Generated on"));
+        assert!(text.contains(&year));
+    }
+
+    /// Rightmost extent of any text run in a frame, relative to the page origin
+    fn text_right_edge(frame: &typst::layout::Frame, origin: typst::layout::Point) -> f64 {
+        use typst::layout::FrameItem;
+
+        frame
+            .items()
+            .map(|(pos, item)| {
+                let at = origin + *pos;
+                match item {
+                    FrameItem::Group(group) => text_right_edge(&group.frame, at),
+                    FrameItem::Text(text) => (at.x + text.width()).to_pt(),
+                    _ => 0.0,
+                }
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Lowest text baseline in a frame, relative to the page origin
+    fn text_bottom_edge(frame: &typst::layout::Frame, origin: typst::layout::Point) -> f64 {
+        use typst::layout::FrameItem;
+
+        frame
+            .items()
+            .map(|(pos, item)| {
+                let at = origin + *pos;
+                match item {
+                    FrameItem::Group(group) => text_bottom_edge(&group.frame, at),
+                    FrameItem::Text(_) => at.y.to_pt(),
+                    _ => 0.0,
+                }
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Text of every run set in bold (weight 700 or more) within a frame
+    fn bold_text(frame: &typst::layout::Frame) -> String {
+        use typst::layout::FrameItem;
+
+        frame
+            .items()
+            .map(|(_, item)| match item {
+                FrameItem::Group(group) => bold_text(&group.frame),
+                FrameItem::Text(text) if text.font.info().variant.weight.to_number() >= 700 => {
+                    format!("{} ", text.text)
+                }
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_structured_highlight_renders_bold_lead() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Test User", "email": "test@example.com" },
+            "work": [{
+                "company": "Tech Corp",
+                "position": "Engineer",
+                "highlights": [
+                    { "lead": "Led migration", "text": "that reduced costs by 30%" },
+                    "Plain highlight still works"
+                ]
+            }]
+        }))
+        .unwrap();
+
+        let source = transform_resume(&resume, &RenderOptions::default()).unwrap();
+        let world = crate::typst::world::DocgenWorld::new(source);
+        let document = typst::compile::<typst::layout::PagedDocument>(&world)
+            .output
+            .expect("Compilation failed");
+
+        let bold = bold_text(&document.pages[0].frame);
+        assert!(bold.contains("Led"), "bold runs: {bold}");
+        assert!(bold.contains("migration"), "bold runs: {bold}");
+        assert!(!bold.contains("reduced"), "bold runs: {bold}");
+        assert!(!bold.contains("Plain"), "bold runs: {bold}");
+    }
+
+    #[test]
+    fn test_long_url_wraps_within_margin() {
+        let long_url = format!("https://example.com/{}", "a".repeat(100));
+        assert!(long_url.len() >= 120);
+
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": {
+                "name": "Test User",
+                "email": "test@example.com",
+                "profiles": [{ "network": "Website", "url": long_url }]
+            },
+            "work": [],
+            "projects": [{ "name": "Overflow", "url": long_url }]
+        }))
+        .unwrap();
+
+        let source = transform_resume(&resume, &RenderOptions::default()).unwrap();
+        let world = crate::typst::world::DocgenWorld::new(source);
+        let warned = typst::compile::<typst::layout::PagedDocument>(&world);
+        assert!(warned.warnings.is_empty(), "{:?}", warned.warnings);
+        let document = warned.output.expect("Compilation failed");
+
+        assert_eq!(document.pages.len(), 1);
+        let page = &document.pages[0].frame;
+        let right_margin = page.width().to_pt() - 36.0;
+        let edge = text_right_edge(page, typst::layout::Point::zero());
+        assert!(
+            edge <= right_margin + 1.0,
+            "text extends to {edge}pt, past the {right_margin}pt margin"
+        );
+    }
+
+    #[test]
+    fn test_style_overrides_compile() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Test User", "email": "test@example.com" },
+            "work": [{
+                "company": "Tech Corp",
+                "position": "Engineer",
+                "highlights": ["Kept a long highlight here so the paragraph has something to justify across the line"]
+            }]
+        }))
+        .unwrap();
+        let options: RenderOptions = serde_json::from_value(serde_json::json!({
+            "style_overrides": {
+                "justify": true,
+                "heading_weight": "semibold",
+                "rule_thickness": 1.5
+            }
+        }))
+        .unwrap();
+
+        let source = transform_resume(&resume, &options).unwrap();
+        assert!(source.contains("\"heading_weight\":\"semibold\""));
+
+        let result = crate::typst::compiler::compile(source, &Default::default());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_style_overrides_reject_unknown_keys() {
+        let result = serde_json::from_value::<RenderOptions>(serde_json::json!({
+            "style_overrides": { "raw_typst": "#set page(fill: red)" }
+        }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_summary_placement_changes_text_order() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": {
+                "name": "Test User",
+                "email": "test@example.com",
+                "summary": "Backend engineer focused on reliable distributed systems."
+            },
+            "work": [{ "company": "Tech Corp", "position": "Engineer" }]
+        }))
+        .unwrap();
+
+        let render = |placement: SummaryPlacement| {
+            let options = RenderOptions {
+                summary_placement: placement,
+                ..Default::default()
+            };
+            let source = transform_resume(&resume, &options).unwrap();
+            let pdf_bytes = crate::typst::compiler::compile(source, &Default::default())
+                .expect("Compilation failed");
+            pdf_extract::extract_text_from_mem(&pdf_bytes)
+                .unwrap()
+                .to_lowercase()
+        };
+
+        let header = render(SummaryPlacement::Header);
+        let section = render(SummaryPlacement::Section);
+
+        let summary_at = |text: &str| text.find("backend engineer").expect("summary missing");
+        assert!(!header[..summary_at(&header)].contains("summary"));
+        assert!(section[..summary_at(&section)].contains("summary"));
+    }
+
+    /// Number of filled shapes (backgrounds, not rules) in a frame
+    fn filled_shapes(frame: &typst::layout::Frame) -> usize {
+        use typst::layout::FrameItem;
+
+        frame
+            .items()
+            .map(|(_, item)| match item {
+                FrameItem::Group(group) => filled_shapes(&group.frame),
+                FrameItem::Shape(shape, _) if shape.fill.is_some() => 1,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_summary_box_style_draws_callout() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": {
+                "name": "Test User",
+                "email": "test@example.com",
+                "summary": "Backend engineer focused on reliable distributed systems."
+            },
+            "work": [{ "company": "Tech Corp", "position": "Engineer" }]
+        }))
+        .unwrap();
+
+        let render = |placement: SummaryPlacement, summary_style: SummaryStyle| {
+            let options = RenderOptions {
+                summary_placement: placement,
+                summary_style,
+                ..Default::default()
+            };
+            let source = transform_resume(&resume, &options).unwrap();
+            let world = crate::typst::world::DocgenWorld::new(source);
+            let document = typst::compile::<typst::layout::PagedDocument>(&world)
+                .output
+                .expect("Compilation failed");
+            document.pages[0].frame.clone()
+        };
+
+        for placement in SummaryPlacement::ALL {
+            let plain = render(placement, SummaryStyle::Plain);
+            let boxed = render(placement, SummaryStyle::Box);
+            assert_eq!(
+                filled_shapes(&boxed),
+                filled_shapes(&plain) + 1,
+                "{placement:?}"
+            );
+            assert!(frame_text(&boxed).contains("Backend"));
+        }
+
+        let result: Result<RenderOptions, _> =
+            serde_json::from_value(serde_json::json!({ "summary_style": "banner" }));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_emphasis_presets_order_sections() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Test User", "email": "test@example.com" },
+            "work": [{ "company": "Tech Corp", "position": "Engineer" }],
+            "education": [{ "institution": "State University", "degree": "B.S." }]
+        }))
+        .unwrap();
+
+        let render = |resume: &Resume, emphasis: Emphasis| {
+            let options = RenderOptions {
+                emphasis: Some(emphasis),
+                ..Default::default()
+            };
+            let source = transform_resume(resume, &options).unwrap();
+            let pdf_bytes = crate::typst::compiler::compile(source, &Default::default())
+                .expect("Compilation failed");
+            pdf_extract::extract_text_from_mem(&pdf_bytes).unwrap()
+        };
+        let education_first = |text: &str| {
+            text.find("State University").expect("education missing")
+                < text.find("Tech Corp").expect("experience missing")
+        };
+
+        assert!(education_first(&render(&resume, Emphasis::Student)));
+        assert!(!education_first(&render(&resume, Emphasis::Experienced)));
+
+        // An explicit sectionOrder takes precedence over the preset
+        let mut ordered = resume.clone();
+        ordered.section_order = Some(vec!["experience".to_string(), "education".to_string()]);
+        assert!(!education_first(&render(&ordered, Emphasis::Student)));
+    }
+
+    /// All text in a frame, concatenated in layout order
+    fn frame_text(frame: &typst::layout::Frame) -> String {
+        use typst::layout::FrameItem;
+
+        frame
+            .items()
+            .map(|(_, item)| match item {
+                FrameItem::Group(group) => frame_text(&group.frame),
+                FrameItem::Text(text) => text.text.to_string(),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    /// Whether any text in a frame is set in a math font
+    fn uses_math_font(frame: &typst::layout::Frame) -> bool {
+        use typst::layout::FrameItem;
+
+        frame.items().any(|(_, item)| match item {
+            FrameItem::Group(group) => uses_math_font(&group.frame),
+            FrameItem::Text(text) => text.font.info().family.contains("Math"),
+            _ => false,
+        })
+    }
+
+    #[test]
+    fn test_publication_math_renders_dollar_segments() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Test User", "email": "test@example.com" },
+            "work": [],
+            "publications": [
+                { "title": "Sorting in $n^2$ time", "summary": "Costs $5 per run" },
+                { "title": "Code $#calc.pow(2, 8)$ stays text" }
+            ]
+        }))
+        .unwrap();
+
+        let render = |publication_math: bool| {
+            let options = RenderOptions {
+                publication_math,
+                ..Default::default()
+            };
+            let source = transform_resume(&resume, &options).unwrap();
+            let world = crate::typst::world::DocgenWorld::new(source);
+            let document = typst::compile::<typst::layout::PagedDocument>(&world)
+                .output
+                .expect("Compilation failed");
+            document.pages[0].frame.clone()
+        };
+
+        // Off by default: every dollar sign is literal text
+        let plain = render(false);
+        assert!(
+            frame_text(&plain).contains("$n^2$"),
+            "{}",
+            frame_text(&plain)
+        );
+        assert!(!uses_math_font(&plain));
+
+        let math = render(true);
+        let text = frame_text(&math);
+        assert!(uses_math_font(&math));
+        assert!(!text.contains("$n^2$"), "{}", text);
+
+        // Segments with code, and unpaired dollar signs, stay literal
+        assert!(text.contains("$#calc.pow"), "{}", text);
+        assert!(text.contains("$5"), "{}", text);
+    }
+
+    #[test]
+    fn test_safe_math_allows_only_plain_math() {
+        for segment in [
+            "n^2",
+            "x_1' + y",
+            "alpha beta",
+            "arrow.r.long",
+            "sin x",
+            "(a+b)/2",
+            "√x",
+            "a -> b",
+            "f(x) = [a] {b}",
+        ] {
+            assert!(is_safe_math(segment), "rejected {segment:?}");
+        }
+        for segment in [
+            "",
+            " ",
+            "#calc.pow(2, 8)",
+            "sqrt(x)",
+            "calc",
+            "unknownname",
+            "arrow.nonsense",
+            "text.fill",
+            "x^",
+        ] {
+            assert!(!is_safe_math(segment), "accepted {segment:?}");
+        }
+    }
+
+    #[test]
+    fn test_partial_date_ranges_render_cleanly() {
+        let render = |dates: serde_json::Value| {
+            let mut work = serde_json::json!({ "company": "Tech Corp", "position": "Engineer" });
+            work.as_object_mut()
+                .unwrap()
+                .extend(dates.as_object().unwrap().clone());
+            let resume: Resume = serde_json::from_value(serde_json::json!({
+                "basics": { "name": "Test User", "email": "test@example.com" },
+                "work": [work]
+            }))
+            .unwrap();
+
+            let source = transform_resume(&resume, &RenderOptions::default()).unwrap();
+            let world = crate::typst::world::DocgenWorld::new(source);
+            let document = typst::compile::<typst::layout::PagedDocument>(&world)
+                .output
+                .expect("Compilation failed");
+            // Compare without whitespace so run boundaries don't matter
+            frame_text(&document.pages[0].frame)
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>()
+        };
+        let dashes = |text: &str| text.matches('–').count();
+
+        let both = render(serde_json::json!({ "startDate": "2018", "endDate": "2020" }));
+        assert!(both.contains("2018–2020"), "{both}");
+        assert_eq!(dashes(&both), 1, "{both}");
+
+        let start_only = render(serde_json::json!({ "startDate": "2018" }));
+        assert!(start_only.contains("2018–"), "{start_only}");
+        assert_eq!(dashes(&start_only), 1, "{start_only}");
+
+        let end_only = render(serde_json::json!({ "endDate": "2020" }));
+        assert!(end_only.contains("–2020"), "{end_only}");
+        assert_eq!(dashes(&end_only), 1, "{end_only}");
+
+        let neither = render(serde_json::json!({}));
+        assert_eq!(dashes(&neither), 0, "{neither}");
+        let blank = render(serde_json::json!({ "startDate": " ", "endDate": "" }));
+        assert_eq!(dashes(&blank), 0, "{blank}");
+    }
+
+    #[test]
+    fn test_language_dots_reflect_proficiency() {
+        let render = |fluency: &str, language_style: LanguageStyle| {
+            let resume: Resume = serde_json::from_value(serde_json::json!({
+                "basics": { "name": "Test User", "email": "test@example.com" },
+                "work": [],
+                "languages": [{ "language": "Spanish", "fluency": fluency }]
+            }))
+            .unwrap();
+            let options = RenderOptions {
+                language_style,
+                ..Default::default()
+            };
+            let source = transform_resume(&resume, &options).unwrap();
+            let world = crate::typst::world::DocgenWorld::new(source);
+            let document = typst::compile::<typst::layout::PagedDocument>(&world)
+                .output
+                .expect("Compilation failed");
+            frame_text(&document.pages[0].frame)
+        };
+        let filled = |text: &str| text.matches('●').count();
+
+        let native = render("Native", LanguageStyle::Dots);
+        let intermediate = render("Intermediate", LanguageStyle::Dots);
+        assert_eq!(filled(&native), 5, "{native}");
+        assert_eq!(filled(&intermediate), 2, "{intermediate}");
+        assert_eq!(intermediate.matches('○').count(), 3, "{intermediate}");
+        assert!(!native.contains("Native"), "{native}");
+
+        // Unrecognized values and the default style keep the text
+        let unknown = render("Reads menus", LanguageStyle::Dots);
+        assert!(unknown.contains("Reads"), "{unknown}");
+        assert_eq!(filled(&unknown), 0, "{unknown}");
+        let text = render("Native", LanguageStyle::Text);
+        assert!(text.contains("Native"), "{text}");
+        assert_eq!(filled(&text), 0, "{text}");
+    }
+
+    #[test]
+    fn test_keyword_pattern_escapes_and_bounds() {
+        assert_eq!(keyword_pattern(&[]), None);
+        assert_eq!(keyword_pattern(&[" ".to_string()]), None);
+        assert_eq!(
+            keyword_pattern(&["Rust".to_string(), "C++".to_string()]).unwrap(),
+            "(?i)(?:\\bRust\\b|\\bC\\+\\+)"
+        );
+    }
+
+    #[test]
+    fn test_highlight_keywords_bolds_whole_words() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": {
+                "name": "Test User",
+                "email": "test@example.com",
+                "summary": "Backend engineer writing Rust daily."
+            },
+            "work": [{
+                "company": "Tech Corp",
+                "position": "Engineer",
+                "highlights": ["Rebuilt the billing service in rust, replacing a Rustic legacy stack"]
+            }]
+        }))
+        .unwrap();
+        let options = RenderOptions {
+            highlight_keywords: vec!["Rust".to_string()],
+            ..Default::default()
+        };
+
+        let source = transform_resume(&resume, &options).unwrap();
+        let world = crate::typst::world::DocgenWorld::new(source);
+        let document = typst::compile::<typst::layout::PagedDocument>(&world)
+            .output
+            .expect("Compilation failed");
+
+        let bold = bold_text(&document.pages[0].frame);
+        let words: Vec<&str> = bold.split_whitespace().collect();
+        assert!(words.contains(&"Rust"), "bold runs: {bold}");
+        assert!(words.contains(&"rust"), "bold runs: {bold}");
+        assert!(!bold.contains("Rustic"), "bold runs: {bold}");
+        assert!(!bold.contains("billing"), "bold runs: {bold}");
+    }
+
+    #[test]
+    fn test_dedupe_skills_removes_repeated_keywords() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Test User", "email": "test@example.com" },
+            "work": [],
+            "skills": [
+                { "name": "Languages", "keywords": ["Rust", "Python"] },
+                { "name": "Backend", "keywords": ["rust", "PostgreSQL"] },
+                { "name": "languages ", "keywords": ["Go", "Python"] },
+                { "name": "Systems", "keywords": ["Rust"] }
+            ]
+        }))
+        .unwrap();
+
+        let skills = |options: &RenderOptions| {
+            let source = transform_resume(&resume, options).unwrap();
+            let json = source.split("`````").nth(1).expect("resume data block");
+            let data: serde_json::Value = serde_json::from_str(json).unwrap();
+            data["skills"].clone()
+        };
+
+        // Off by default: the skills are rendered as submitted
+        assert_eq!(
+            skills(&RenderOptions::default()).as_array().unwrap().len(),
+            4
+        );
+
+        let deduped = skills(&RenderOptions {
+            dedupe_skills: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            deduped,
+            serde_json::json!([
+                { "name": "Languages", "keywords": ["Rust", "Python", "Go"] },
+                { "name": "Backend", "keywords": ["PostgreSQL"] }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_combine_recognition_merges_awards_and_certifications() {
+        let resume: Resume =
+            serde_json::from_str(include_str!("../../tests/fixtures/full_resume.json")).unwrap();
+
+        let render = |combine_recognition: bool| {
+            let options = RenderOptions {
+                combine_recognition,
+                ..Default::default()
+            };
+            let source = transform_resume(&resume, &options).unwrap();
+            let pdf_bytes = crate::typst::compiler::compile(source, &Default::default())
+                .expect("Compilation failed");
+            pdf_extract::extract_text_from_mem(&pdf_bytes)
+                .unwrap()
+                .to_lowercase()
+        };
+
+        let separate = render(false);
+        assert!(separate.contains("awards"), "{}", separate);
+
+        let combined = render(true);
+        let position = |needle: &str| {
+            combined
+                .find(needle)
+                .unwrap_or_else(|| panic!("'{}' missing from: {}", needle, combined))
+        };
+        // Honors first, then certifications, before the next section
+        assert!(position("honors") < position("halcyon"));
+        assert!(position("halcyon") < position("kestrel"));
+        assert!(position("kestrel") < position("reproducible builds"));
+        assert!(position("projects") < position("honors"));
+        assert!(!combined.contains("awards"), "{}", combined);
+        assert_eq!(combined.matches("certifications").count(), 1);
+    }
+
+    #[test]
+    fn test_skills_style_changes_layout() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Test User", "email": "test@example.com" },
+            "work": [],
+            "skills": [{ "name": "Languages", "keywords": ["Rust", "Python"] }]
+        }))
+        .unwrap();
+
+        let render = |skills_style: SkillsStyle| {
+            let options = RenderOptions {
+                skills_style,
+                ..Default::default()
+            };
+            let source = transform_resume(&resume, &options).unwrap();
+            let pdf_bytes = crate::typst::compiler::compile(source, &Default::default())
+                .expect("Compilation failed");
+            pdf_extract::extract_text_from_mem(&pdf_bytes).unwrap()
+        };
+
+        let inline = render(SkillsStyle::Inline);
+        assert!(
+            inline.contains("Rust, Python"),
+            "inline list missing: {}",
+            inline
+        );
+        assert!(!inline.contains('•'));
+
+        let grouped = render(SkillsStyle::Grouped);
+        assert!(!grouped.contains("Rust, Python"));
+        assert_eq!(
+            grouped.matches('•').count(),
+            2,
+            "bullets missing: {}",
+            grouped
+        );
+    }
+
+    /// Lengths of the lines stroked exactly `thickness` points thick, in points
+    ///
+    /// Link underlines are lines too, so giving the section rules a distinct
+    /// thickness keeps them out of the count.
+    fn rule_lengths(frame: &typst::layout::Frame, thickness: f64) -> Vec<f64> {
+        use typst::layout::FrameItem;
+        use typst::visualize::Geometry;
+
+        frame
+            .items()
+            .flat_map(|(_, item)| match item {
+                FrameItem::Group(group) => rule_lengths(&group.frame, thickness),
+                FrameItem::Shape(shape, _) => match (&shape.geometry, &shape.stroke) {
+                    (Geometry::Line(end), Some(stroke))
+                        if (stroke.thickness.to_pt() - thickness).abs() < 1e-6 =>
+                    {
+                        vec![end.x.to_pt()]
+                    }
+                    _ => vec![],
+                },
+                _ => vec![],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_divider_styles_change_section_rules() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Test User", "email": "test@example.com" },
+            "work": [{ "company": "Tech Corp", "position": "Engineer" }],
+            "skills": [{ "name": "Languages", "keywords": ["Rust"] }]
+        }))
+        .unwrap();
+
+        // A thickness no link underline has, so only section rules are counted
+        let thickness = 2.0;
+        let render = |divider_style: DividerStyle| {
+            let options = RenderOptions {
+                divider_style,
+                style_overrides: StyleOverrides {
+                    rule_thickness: Some(thickness),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let source = transform_resume(&resume, &options).unwrap();
+            let world = crate::typst::world::DocgenWorld::new(source);
+            let document = typst::compile::<typst::layout::PagedDocument>(&world)
+                .output
+                .expect("Compilation failed");
+            rule_lengths(&document.pages[0].frame, thickness)
+        };
+
+        let full = render(DividerStyle::Full);
+        let short = render(DividerStyle::Short);
+        let none = render(DividerStyle::None);
+
+        // One rule per section heading
+        assert_eq!(full.len(), 2, "{full:?}");
+        assert_eq!(short.len(), 2, "{short:?}");
+        assert!(none.is_empty(), "{none:?}");
+        assert!(short[0] < full[0] / 2.0, "{short:?} vs {full:?}");
+    }
+
+    #[test]
+    fn test_compact_density_takes_less_space() {
+        let resume: Resume =
+            serde_json::from_str(include_str!("../../tests/fixtures/full_resume.json")).unwrap();
+
+        // Pages used, then how far down the last page the text reaches; the
+        // page number footer is turned off so only the body is measured
+        let extent = |density: Density| {
+            let options = RenderOptions {
+                density,
+                page_numbers: Some(false),
+                ..Default::default()
+            };
+            let source = transform_resume(&resume, &options).unwrap();
+            let document =
+                crate::typst::compiler::compile_document(source).expect("Compilation failed");
+            let last = document.pages.last().unwrap();
+            (
+                document.pages.len(),
+                text_bottom_edge(&last.frame, Default::default()),
+            )
+        };
+
+        let comfortable = extent(Density::Comfortable);
+        let compact = extent(Density::Compact);
+        // Fewer pages, or the same pages with the text ending higher up
+        assert!(compact < comfortable, "{compact:?} vs {comfortable:?}");
+
+        let result: Result<RenderOptions, _> =
+            serde_json::from_value(serde_json::json!({ "density": "cozy" }));
+        assert!(result.is_err());
+    }
+
+    /// Fill of the first text run containing `needle`
+    fn text_fill(frame: &typst::layout::Frame, needle: &str) -> Option<typst::visualize::Paint> {
+        use typst::layout::FrameItem;
+
+        frame.items().find_map(|(_, item)| match item {
+            FrameItem::Group(group) => text_fill(&group.frame, needle),
+            FrameItem::Text(text) if text.text.contains(needle) => Some(text.fill.clone()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_section_colors_tint_only_that_section() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": { "name": "Test User", "email": "test@example.com" },
+            "work": [{ "company": "Tech Corp", "position": "Engineer" }],
+            "skills": [{ "name": "Languages", "keywords": ["Rust"] }]
+        }))
+        .unwrap();
+
+        let render = |options: &RenderOptions| {
+            let source = transform_resume(&resume, options).unwrap();
+            let world = crate::typst::world::DocgenWorld::new(source);
+            let document = typst::compile::<typst::layout::PagedDocument>(&world)
+                .output
+                .expect("Compilation failed");
+            document.pages[0].frame.clone()
+        };
+
+        let baseline = render(&RenderOptions::default());
+        let colored = render(&RenderOptions {
+            section_colors: BTreeMap::from([("skills".to_string(), "#1a73e8".to_string())]),
+            ..Default::default()
+        });
+
+        let blue = typst::visualize::Paint::Solid(typst::visualize::Color::from_u8(
+            0x1a, 0x73, 0xe8, 0xff,
+        ));
+        assert_eq!(text_fill(&colored, "Technical"), Some(blue.clone()));
+        assert_ne!(text_fill(&baseline, "Technical"), Some(blue));
+        assert_eq!(
+            text_fill(&colored, "Experience"),
+            text_fill(&baseline, "Experience")
+        );
+    }
+
+    #[test]
+    fn test_inline_contact_style_uses_fewer_lines() {
+        let resume: Resume = serde_json::from_value(serde_json::json!({
+            "basics": {
+                "name": "Test User",
+                "email": "test@example.com",
+                "phone": "555-0100",
+                "location": "Springfield"
+            },
+            "work": []
+        }))
+        .unwrap();
+
+        let header_lines = |contact_style: ContactStyle| {
+            let options = RenderOptions {
+                contact_style,
+                ..Default::default()
+            };
+            let source = transform_resume(&resume, &options).unwrap();
+            let pdf_bytes = crate::typst::compiler::compile(source, &Default::default())
+                .expect("Compilation failed");
+            let text = pdf_extract::extract_text_from_mem(&pdf_bytes).unwrap();
+            text.lines()
+                .filter(|line| {
+                    ["Springfield", "555-0100", "test@example.com"]
+                        .iter()
+                        .any(|item| line.contains(item))
+                })
+                .count()
+        };
+
+        assert_eq!(header_lines(ContactStyle::Stacked), 2);
+        assert_eq!(header_lines(ContactStyle::Inline), 1);
+    }
+
+    #[test]
+    fn test_redact_contact_hides_personal_details() {
+        let resume: Resume =
+            serde_json::from_str(include_str!("../../tests/fixtures/sample_resume.json")).unwrap();
+        let options = RenderOptions {
+            redact_contact: true,
+            ..Default::default()
+        };
+
+        let source = transform_resume(&resume, &options).unwrap();
+        let pdf_bytes = crate::typst::compiler::compile(source, &Default::default())
+            .expect("Compilation failed");
+        let text = pdf_extract::extract_text_from_mem(&pdf_bytes).unwrap();
+
+        assert!(!text.contains(&resume.basics.email), "email rendered");
+        assert!(!text.contains(&resume.basics.name), "name rendered");
+        assert!(text.contains("Tech Innovations"), "content dropped");
+    }
+
+    #[test]
+    fn test_signature_space_moves_typed_name_down() {
+        let cover_letter: CoverLetter = serde_json::from_value(serde_json::json!({
+            "sender": { "name": "Jane Doe", "email": "jane@example.com" },
+            "recipient": { "company": "Tech Corp" },
+            "opening": "I am writing to apply.",
+            "body": ["I have relevant experience."],
+            "closing": "Thank you."
+        }))
+        .unwrap();
+
+        let name_position = |signature_space: bool| {
+            let options = CoverLetterOptions { signature_space };
+            let source = transform_cover_letter(&cover_letter, &options).unwrap();
+            let document =
+                crate::typst::compiler::compile_document(source).expect("Compilation failed");
+            assert_eq!(document.pages.len(), 1);
+            text_bottom_edge(&document.pages[0].frame, Default::default())
+        };
+
+        // The typed name is the last line; the reserved space pushes it at least two lines lower
+        let plain = name_position(false);
+        let reserved = name_position(true);
+        assert!(reserved - plain > 20.0, "{} vs {}", plain, reserved);
+    }
 }
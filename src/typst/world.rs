@@ -1,8 +1,20 @@
+//! The Typst world documents are compiled in
+//!
+//! Compilation is sandboxed: a document can only see the sources and files
+//! held in memory by its [`DocgenWorld`], namely the main source, the bundled
+//! template modules, and the assets supplied with the request. Nothing is read
+//! from disk and no packages are downloaded, so `#read`, `#image`, `#include`,
+//! or `#import` of anything else fails with a "not found" diagnostic instead
+//! of touching the host.
+
 use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use time::OffsetDateTime;
+use sha2::{Digest, Sha256};
+use time::{OffsetDateTime, UtcOffset};
 use typst::Library;
-use typst::diag::{FileError, FileResult};
+use typst::diag::{FileError, FileResult, PackageError};
 use typst::foundations::{Bytes, Datetime};
 use typst::syntax::{FileId, Source, VirtualPath};
 use typst::text::{Font, FontBook};
@@ -17,39 +29,163 @@ use typst::utils::LazyHash;
 // Library::builder() also comes from LibraryExt.
 // Let's assume the compiler suggestion is correct.
 use typst::LibraryExt;
+
+use crate::typst::assets::{ASSET_DIR, AssetMap};
 use typst::World;
 
-pub struct DocgenWorld {
-    library: LazyHash<Library>,
+/// Shared Typst modules that templates can `#import`, keyed by their path
+/// relative to the project root (where the transformed `main.typ` lives).
+pub const TEMPLATE_MODULES: &[(&str, &str)] = &[
+    (
+        "components/contact.typ",
+        include_str!("../../templates/components/contact.typ"),
+    ),
+    (
+        "components/url.typ",
+        include_str!("../../templates/components/url.typ"),
+    ),
+];
+
+/// Font family of the bundled contact icon font
+pub const ICON_FONT_FAMILY: &str = "Docgen Icons";
+
+/// Envelope, phone and link icons at U+E000–U+E002, built by
+/// `templates/fonts/build_icons.py`
+const ICON_FONT: &[u8] = include_bytes!("../../templates/fonts/docgen-icons.ttf");
+
+/// Returns true if a font of the given family is loaded into every world
+pub fn font_family_available(family: &str) -> bool {
+    font_cache().book.contains_family(&family.to_lowercase())
+}
+
+/// Bundled fonts, parsed once per process and shared by every world
+struct FontCache {
     book: LazyHash<FontBook>,
     fonts: Vec<Font>,
+}
+
+static FONT_CACHE: OnceLock<FontCache> = OnceLock::new();
+
+/// Number of times the bundled fonts have been parsed
+static FONT_LOADS: AtomicUsize = AtomicUsize::new(0);
+
+fn font_cache() -> &'static FontCache {
+    FONT_CACHE.get_or_init(|| {
+        FONT_LOADS.fetch_add(1, Ordering::Relaxed);
+
+        // Load fonts from typst-assets, plus the icon font
+        let fonts: Vec<Font> = typst_assets::fonts()
+            .chain([ICON_FONT])
+            .flat_map(|bytes| Font::new(Bytes::new(bytes), 0))
+            .collect();
+
+        FontCache {
+            book: LazyHash::new(FontBook::from_fonts(&fonts)),
+            fonts,
+        }
+    })
+}
+
+pub struct DocgenWorld {
+    library: LazyHash<Library>,
+    font_cache: &'static FontCache,
     main: FileId,
     sources: HashMap<FileId, Source>,
+    files: HashMap<FileId, Bytes>,
     now: OffsetDateTime,
 }
 
 impl DocgenWorld {
     pub fn new(source: String) -> Self {
-        // Load fonts from typst-assets
-        let fonts: Vec<Font> = typst_assets::fonts()
-            .flat_map(|bytes| Font::new(Bytes::new(bytes), 0))
-            .collect();
+        Self::with_modules(source, TEMPLATE_MODULES)
+    }
 
-        let book = FontBook::from_fonts(&fonts);
+    /// Parse the bundled fonts ahead of time so the first compile doesn't pay for it
+    pub fn prewarm() {
+        font_cache();
+    }
 
+    /// Create a world whose source map also contains the given importable modules
+    pub fn with_modules(source: String, modules: &[(&str, &str)]) -> Self {
         let main_id = FileId::new(None, VirtualPath::new("main.typ"));
         let mut sources = HashMap::new();
         sources.insert(main_id, Source::new(main_id, source));
 
+        for (path, text) in modules {
+            let id = FileId::new(None, VirtualPath::new(path));
+            sources.insert(id, Source::new(id, text.to_string()));
+        }
+
         Self {
             library: LazyHash::new(Library::default()),
-            book: LazyHash::new(book),
-            fonts,
+            font_cache: font_cache(),
             main: main_id,
             sources,
+            files: HashMap::new(),
             now: OffsetDateTime::now_utc(),
         }
     }
+
+    /// Fix the clock used for `datetime.today()` instead of reading the system time
+    ///
+    /// Lets tests pin the date and hour that `fingerprint` and the compiled
+    /// output depend on.
+    #[cfg(test)]
+    fn with_now(mut self, now: OffsetDateTime) -> Self {
+        self.now = now;
+        self
+    }
+
+    /// SHA-256 digest of everything a compilation of this world can observe
+    ///
+    /// Covers every source and file by path, and the UTC date and hour, which
+    /// together with any whole-hour offset determine `datetime.today()`. Two
+    /// worlds with the same fingerprint compile to the same document; unlike a
+    /// 64-bit hash, a collision between different worlds is not a practical
+    /// concern.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        // Length-prefix every field so adjacent entries cannot run together
+        let mut digest_field = |data: &[u8]| {
+            hasher.update((data.len() as u64).to_le_bytes());
+            hasher.update(data);
+        };
+
+        // Map iteration order is random, so digest entries sorted by path
+        let mut sources: Vec<_> = self
+            .sources
+            .iter()
+            .map(|(id, source)| (id.vpath().as_rootless_path(), source.text().as_bytes()))
+            .collect();
+        sources.sort();
+        let mut files: Vec<_> = self
+            .files
+            .iter()
+            .map(|(id, bytes)| (id.vpath().as_rootless_path(), &bytes[..]))
+            .collect();
+        files.sort();
+
+        for entries in [sources, files] {
+            digest_field(&(entries.len() as u64).to_le_bytes());
+            for (path, data) in entries {
+                digest_field(path.as_os_str().as_encoded_bytes());
+                digest_field(data);
+            }
+        }
+
+        digest_field(&self.now.year().to_le_bytes());
+        digest_field(&[self.now.month() as u8, self.now.day(), self.now.hour()]);
+        hasher.finalize().into()
+    }
+
+    /// Make decoded assets available to the document under `assets/<name>`
+    pub fn with_assets(mut self, assets: &AssetMap) -> Self {
+        for (name, bytes) in assets {
+            let id = FileId::new(None, VirtualPath::new(format!("{}/{}", ASSET_DIR, name)));
+            self.files.insert(id, bytes.clone());
+        }
+        self
+    }
 }
 
 impl World for DocgenWorld {
@@ -58,7 +194,7 @@ impl World for DocgenWorld {
     }
 
     fn book(&self) -> &LazyHash<FontBook> {
-        &self.book
+        &self.font_cache.book
     }
 
     fn main(&self) -> FileId {
@@ -66,23 +202,187 @@ impl World for DocgenWorld {
     }
 
     fn source(&self, id: FileId) -> FileResult<Source> {
-        self.sources
-            .get(&id)
-            .cloned()
-            .ok_or_else(|| FileError::NotFound(id.vpath().as_rootless_path().into()))
+        self.sources.get(&id).cloned().ok_or_else(|| not_found(id))
     }
 
     fn file(&self, id: FileId) -> FileResult<Bytes> {
-        // For now, we don't support external files (images, etc.)
-        Err(FileError::NotFound(id.vpath().as_rootless_path().into()))
+        // Only assets supplied with the request are readable; nothing on disk is
+        self.files.get(&id).cloned().ok_or_else(|| not_found(id))
     }
 
     fn font(&self, index: usize) -> Option<Font> {
-        self.fonts.get(index).cloned()
+        self.font_cache.fonts.get(index).cloned()
     }
 
-    fn today(&self, _offset: Option<i64>) -> Option<Datetime> {
-        let date = self.now.date();
+    fn today(&self, offset: Option<i64>) -> Option<Datetime> {
+        // Without an explicit offset the date is reported in UTC
+        let now = match offset {
+            Some(hours) => {
+                let hours = i8::try_from(hours).ok()?;
+                self.now.to_offset(UtcOffset::from_hms(hours, 0, 0).ok()?)
+            }
+            None => self.now,
+        };
+        let date = now.date();
         Datetime::from_ymd(date.year(), date.month() as u8, date.day())
     }
 }
+
+/// Error for a file outside the world's in-memory sources and assets
+///
+/// Package files get a package error, so an `#import "@preview/..."` is
+/// reported as a missing package rather than as a missing path.
+fn not_found(id: FileId) -> FileError {
+    match id.package() {
+        Some(spec) => FileError::Package(PackageError::NotFound(spec.clone())),
+        None => FileError::NotFound(id.vpath().as_rootless_path().into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_from_module_map() {
+        let modules = [(
+            "components/greeting.typ",
+            "#let greet(name) = [Hello, #name!]",
+        )];
+        let source = "#import \"components/greeting.typ\": greet\n#set page(width: auto, height: auto)\n#greet(\"Module\")";
+        let world = DocgenWorld::with_modules(source.to_string(), &modules);
+
+        let document = typst::compile::<typst::layout::PagedDocument>(&world)
+            .output
+            .expect("Importing from the in-memory module map should compile");
+        assert_eq!(document.pages.len(), 1);
+    }
+
+    #[test]
+    fn test_icon_font_is_bundled() {
+        assert!(font_family_available(ICON_FONT_FAMILY));
+        assert!(!font_family_available("Definitely Not A Font"));
+
+        let world = DocgenWorld::new(String::new());
+        let index = world
+            .book()
+            .select_family(&ICON_FONT_FAMILY.to_lowercase())
+            .next()
+            .expect("Icon font should be in the font book");
+        let font = world.font(index).unwrap();
+        for icon in '\u{E000}'..='\u{E002}' {
+            assert!(
+                font.ttf().glyph_index(icon).is_some(),
+                "Icon font should have a glyph for U+{:04X}",
+                icon as u32
+            );
+        }
+    }
+
+    #[test]
+    fn test_template_modules_are_registered() {
+        let world = DocgenWorld::new(String::new());
+        for (path, _) in TEMPLATE_MODULES {
+            let id = FileId::new(None, VirtualPath::new(path));
+            assert!(
+                world.source(id).is_ok(),
+                "Module {} should be in the source map",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_prewarm_loads_fonts_once() {
+        DocgenWorld::prewarm();
+        let loads = FONT_LOADS.load(Ordering::Relaxed);
+        assert_eq!(loads, 1);
+
+        let world = DocgenWorld::new("Hello".to_string());
+        let _ = DocgenWorld::new("Again".to_string());
+        assert!(world.font(0).is_some());
+        assert_eq!(FONT_LOADS.load(Ordering::Relaxed), loads);
+    }
+
+    #[test]
+    fn test_injected_now_is_used_for_today() {
+        // 2021-03-04 23:30 UTC
+        let fixed = OffsetDateTime::from_unix_timestamp(1_614_900_600).unwrap();
+        let world = DocgenWorld::new(String::new()).with_now(fixed);
+
+        assert_eq!(world.today(None), Datetime::from_ymd(2021, 3, 4));
+        assert_eq!(world.today(Some(2)), Datetime::from_ymd(2021, 3, 5));
+
+        let source = "#set page(width: auto, height: auto)\n#datetime.today().display()";
+        let world = DocgenWorld::new(source.to_string()).with_now(fixed);
+        let document = typst::compile::<typst::layout::PagedDocument>(&world)
+            .output
+            .expect("Compilation failed");
+        let pdf = typst_pdf::pdf(&document, &Default::default()).unwrap();
+        let text = pdf_extract::extract_text_from_mem(&pdf).unwrap();
+        assert!(text.contains("2021-03-04"), "{}", text);
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_source_assets_and_date() {
+        let fixed = OffsetDateTime::from_unix_timestamp(1_614_900_600).unwrap();
+        let world = |source: &str| DocgenWorld::new(source.to_string()).with_now(fixed);
+
+        assert_eq!(world("Hello").fingerprint(), world("Hello").fingerprint());
+        assert_ne!(world("Hello").fingerprint(), world("Hello!").fingerprint());
+
+        let assets = AssetMap::from([("a.png".to_string(), Bytes::new(vec![1u8]))]);
+        assert_ne!(
+            world("Hello").fingerprint(),
+            world("Hello").with_assets(&assets).fingerprint()
+        );
+
+        let next_hour = DocgenWorld::new("Hello".to_string())
+            .with_now(fixed + time::Duration::hours(1))
+            .fingerprint();
+        assert_ne!(world("Hello").fingerprint(), next_hour);
+    }
+
+    #[test]
+    fn test_sandbox_rejects_files_and_packages_cleanly() {
+        let attempts = [
+            ("#read(\"/etc/passwd\")", "file not found"),
+            ("#read(\"../../Cargo.toml\")", "file not found"),
+            ("#image(\"assets/missing.png\")", "file not found"),
+            (
+                "#import \"@preview/cetz:0.3.0\": canvas",
+                "package not found",
+            ),
+        ];
+
+        for (code, expected) in attempts {
+            let world = DocgenWorld::new(code.to_string());
+            let errors = typst::compile::<typst::layout::PagedDocument>(&world)
+                .output
+                .expect_err(code);
+            let messages: Vec<String> = errors.iter().map(|e| e.message.to_string()).collect();
+            assert!(
+                messages.iter().any(|m| m.contains(expected)),
+                "{}: {:?}",
+                code,
+                messages
+            );
+        }
+    }
+
+    #[test]
+    fn test_assets_are_readable() {
+        let encoded = std::collections::BTreeMap::from([(
+            "dot.png".to_string(),
+            "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAIAAACQd1PeAAAADElEQVR4nGP4z8AAAAMBAQDJ/pLvAAAAAElFTkSuQmCC"
+                .to_string(),
+        )]);
+        let (assets, warnings) = crate::typst::assets::decode_assets(&encoded);
+        assert!(warnings.is_empty());
+
+        let source = "#set page(width: auto, height: auto)\n#image(\"assets/dot.png\", width: 1cm)";
+        let world = DocgenWorld::new(source.to_string()).with_assets(&assets);
+        let result = typst::compile::<typst::layout::PagedDocument>(&world);
+        assert!(result.output.is_ok(), "{:?}", result.output.err());
+    }
+}
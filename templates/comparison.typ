@@ -0,0 +1,27 @@
+// Two rendered resumes side by side: page n of each shares landscape page n
+#let comparison(data) = {
+  set page(
+    paper: data.at("paper", default: "us-letter"),
+    flipped: true,
+    margin: 0.4in,
+  )
+
+  let sides = (data.left, data.right)
+  let spreads = calc.max(..sides.map(side => side.pages))
+
+  for index in range(spreads) {
+    if index > 0 { pagebreak() }
+    grid(
+      columns: (1fr, 1fr),
+      rows: 1fr,
+      // Hairline between the two resumes
+      stroke: (x, y) => if x == 1 { (left: 0.5pt + luma(180)) },
+      inset: (x, y) => if x == 1 { (left: 0.2in) } else { (right: 0.2in) },
+      ..sides.map(side => if index < side.pages {
+        image(side.file, format: "pdf", page: index + 1, width: 100%, height: 100%, fit: "contain")
+      } else {
+        []
+      }),
+    )
+  }
+}
@@ -0,0 +1,58 @@
+// Shared contact line used by document headers.
+// Items are separated by pipes and wrap naturally on narrow pages. With
+// `inline: true` the location joins the line and items are separated by bullets.
+
+#import "url.typ": display-url
+
+// Icon glyphs in the bundled icon font, which the transform passes as
+// `icon-font` when it is loaded
+#let icon-glyphs = (email: "\u{E000}", phone: "\u{E001}", link: "\u{E002}")
+
+// Text labels used when icons are requested but the icon font is unavailable
+#let icon-labels = (email: "Email:", phone: "Phone:", link: "Web:")
+
+// With `icon-svgs` (SVG source by kind, supplied by the transform) icons are
+// drawn as images instead; kinds without an SVG fall back to the font
+#let contact-icon(kind, icons, icon-font, icon-svgs: none) = {
+  if icons {
+    if icon-svgs != none and kind in icon-svgs {
+      box(baseline: 0.15em, image(bytes(icon-svgs.at(kind)), format: "svg", height: 0.9em))
+    } else {
+      // The glyphs and labels don't distinguish networks
+      let kind = if kind in icon-glyphs { kind } else { "link" }
+      if icon-font != none {
+        text(font: icon-font, fallback: false, icon-glyphs.at(kind))
+      } else {
+        text(weight: "bold", icon-labels.at(kind))
+      }
+    }
+    h(3pt)
+  }
+}
+
+// `profile-icons` names the icon kind of each profile, in order
+#let contact-line(basics, icons: false, icon-font: none, icon-svgs: none, profile-icons: (), inline: false) = {
+  let icon(kind) = contact-icon(kind, icons, icon-font, icon-svgs: icon-svgs)
+  let contact = ()
+  if inline and "location" in basics and basics.location != none {
+    contact.push([#basics.location])
+  }
+  if "phone" in basics and basics.phone != none {
+    contact.push([#icon("phone")#basics.phone])
+  }
+  contact.push([#icon("email")#link("mailto:" + basics.email)[#underline(basics.email)]])
+  if "profiles" in basics {
+    for (i, p) in basics.profiles.enumerate() {
+      let kind = profile-icons.at(i, default: "link")
+      contact.push([#icon(kind)#link(p.url)[#underline(display-url(p.url))]])
+    }
+  }
+  let separator = if inline [  •  ] else [  |  ]
+  par(justify: true)[
+    #text(size: 9pt)[
+      #for (i, item) in contact.enumerate() [
+        #if i > 0 { separator }#item
+      ]
+    ]
+  ]
+}
@@ -0,0 +1,17 @@
+// Display helpers for URLs and other long unbroken tokens
+
+// Longest run of characters allowed without a break opportunity
+#let max-unbroken = 24
+
+// Insert zero-width break opportunities so long tokens wrap instead of
+// overflowing the margin: after URL separators, then inside any run that is
+// still longer than max-unbroken
+#let breakable(s) = {
+  let zws = "\u{200B}"
+  let separated = s.replace(regex("[/.?&=#_-]"), m => m.text + zws)
+  let long-run = regex("[^\\s\u{200B}]{" + str(max-unbroken) + "}")
+  separated.replace(long-run, m => m.text + zws)
+}
+
+// A URL as shown on the page: without the scheme, wrappable
+#let display-url(url) = breakable(url.replace("https://", "").replace("http://", ""))
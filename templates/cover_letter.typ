@@ -2,7 +2,7 @@
   set text(font: "Libertinus Serif", size: 11pt)
 
   set page(
-    paper: "us-letter",
+    paper: data.options.at("paper", default: "us-letter"),
     margin: (x: 1in, y: 1in),
   )
 
@@ -98,10 +98,14 @@
 
   sig + ","
 
-  v(3em)
+  // Optionally reserve a ruled space for a signature applied later
+  if data.options.at("signature_space", default: false) {
+    v(5em)
+    line(length: 16em, stroke: 0.5pt)
+    v(0.3em)
+  } else {
+    v(3em)
+  }
 
   data.sender.name
 }
-
-// Main document entry point
-#show: cover_letter
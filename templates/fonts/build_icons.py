@@ -0,0 +1,309 @@
+#!/usr/bin/env python3
+"""Build docgen-icons.ttf, the icon font the contact line draws icons with.
+
+The font holds three straight-line glyphs in the Private Use Area:
+
+    U+E000  email  (envelope)
+    U+E001  phone  (handset)
+    U+E002  link   (arrow pointing out)
+
+Run from this directory to regenerate the font after changing a glyph:
+
+    python3 build_icons.py
+
+Only the standard library is needed. The outlines are drawn on a 1000 unit em
+with y pointing up; every glyph is 1000 units wide.
+"""
+
+import struct
+
+FAMILY = "Docgen Icons"
+POSTSCRIPT_NAME = "DocgenIcons-Regular"
+OUTPUT = "docgen-icons.ttf"
+
+UNITS_PER_EM = 1000
+ASCENDER = 800
+DESCENDER = -200
+ADVANCE = 1000
+FIRST_CODEPOINT = 0xE000
+
+
+def rect(x0, y0, x1, y1):
+    return [(x0, y0), (x0, y1), (x1, y1), (x1, y0)]
+
+
+# Each glyph is a list of (points, is_hole) contours
+GLYPHS = [
+    (
+        "email",
+        [
+            (rect(60, 80, 940, 720), False),
+            (rect(130, 150, 870, 650), True),
+            # Flap: a V-shaped band hanging from the top edge of the opening
+            ([(130, 650), (190, 650), (500, 420), (810, 650), (870, 650), (500, 350)], False),
+        ],
+    ),
+    (
+        "phone",
+        [
+            (rect(290, 0, 710, 780), False),
+            (rect(350, 160, 650, 700), True),
+            (rect(470, 50, 530, 110), True),
+        ],
+    ),
+    (
+        "link",
+        [
+            # Head with its apex at the top right
+            ([(440, 760), (840, 760), (840, 360)], False),
+            # Shaft from the bottom left up to the head's base
+            ([(160, 0), (76, 84), (598, 602), (682, 518)], False),
+        ],
+    ),
+]
+
+
+def signed_area(points):
+    return sum(
+        x0 * y1 - x1 * y0
+        for (x0, y0), (x1, y1) in zip(points, points[1:] + points[:1])
+    ) / 2
+
+
+def oriented(points, is_hole):
+    """Filled contours run clockwise and holes counter-clockwise."""
+    clockwise = signed_area(points) < 0
+    return points if clockwise != is_hole else points[::-1]
+
+
+def glyph_data(contours):
+    contours = [oriented(points, is_hole) for points, is_hole in contours]
+    points = [point for contour in contours for point in contour]
+    xs = [x for x, _ in points]
+    ys = [y for _, y in points]
+
+    data = struct.pack(">hhhhh", len(contours), min(xs), min(ys), max(xs), max(ys))
+    end = -1
+    for contour in contours:
+        end += len(contour)
+        data += struct.pack(">H", end)
+    data += struct.pack(">H", 0)  # no instructions
+    data += bytes([0x01] * len(points))  # on-curve, 16-bit deltas
+
+    for coordinates in (xs, ys):
+        previous = 0
+        for value in coordinates:
+            data += struct.pack(">h", value - previous)
+            previous = value
+
+    return data + b"\0" * (-len(data) % 4), (min(xs), min(ys), max(xs), max(ys)), len(points), len(contours)
+
+
+def checksum(data):
+    data += b"\0" * (-len(data) % 4)
+    return sum(struct.unpack(f">{len(data) // 4}I", data)) & 0xFFFFFFFF
+
+
+def cmap_table(glyph_count):
+    last = FIRST_CODEPOINT + glyph_count - 1
+    starts = [FIRST_CODEPOINT, 0xFFFF]
+    ends = [last, 0xFFFF]
+    deltas = [(1 - FIRST_CODEPOINT) % 0x10000, 1]
+    segments = len(starts)
+    search_range = 2 * 2 ** (segments.bit_length() - 1)
+
+    subtable = struct.pack(
+        ">HHHHHHH",
+        4,
+        16 + 8 * segments,
+        0,
+        2 * segments,
+        search_range,
+        segments.bit_length() - 1,
+        2 * segments - search_range,
+    )
+    subtable += struct.pack(f">{segments}H", *ends) + struct.pack(">H", 0)
+    subtable += struct.pack(f">{segments}H", *starts)
+    subtable += struct.pack(f">{segments}H", *deltas)
+    subtable += struct.pack(f">{segments}H", *([0] * segments))
+
+    header = struct.pack(">HH", 0, 2)
+    header += struct.pack(">HHI", 0, 3, 4 + 2 * 8)
+    header += struct.pack(">HHI", 3, 1, 4 + 2 * 8)
+    return header + subtable
+
+
+def name_table():
+    names = {
+        1: FAMILY,
+        2: "Regular",
+        3: f"{POSTSCRIPT_NAME};1.000",
+        4: FAMILY,
+        5: "Version 1.000",
+        6: POSTSCRIPT_NAME,
+    }
+    strings = b""
+    records = b""
+    for name_id, text in names.items():
+        encoded = text.encode("utf-16-be")
+        records += struct.pack(">HHHHHH", 3, 1, 0x409, name_id, len(encoded), len(strings))
+        strings += encoded
+    return struct.pack(">HHH", 0, len(names), 6 + len(records)) + records + strings
+
+
+def build():
+    glyphs = [(b"", (0, 0, 0, 0), 0, 0)] + [glyph_data(contours) for _, contours in GLYPHS]
+    count = len(glyphs)
+    x_min = min(bbox[0] for _, bbox, _, _ in glyphs[1:])
+    y_min = min(bbox[1] for _, bbox, _, _ in glyphs[1:])
+    x_max = max(bbox[2] for _, bbox, _, _ in glyphs[1:])
+    y_max = max(bbox[3] for _, bbox, _, _ in glyphs[1:])
+
+    glyf = b"".join(data for data, _, _, _ in glyphs)
+    offsets = [0]
+    for data, _, _, _ in glyphs:
+        offsets.append(offsets[-1] + len(data))
+    loca = struct.pack(f">{len(offsets)}I", *offsets)
+    hmtx = b"".join(struct.pack(">Hh", ADVANCE, bbox[0]) for _, bbox, _, _ in glyphs)
+
+    head = struct.pack(
+        ">IIIIHHqqhhhhHHhhh",
+        0x00010000,
+        0x00010000,
+        0,  # checksum adjustment, filled in below
+        0x5F0F3CF5,
+        0x000B,
+        UNITS_PER_EM,
+        0,
+        0,
+        x_min,
+        y_min,
+        x_max,
+        y_max,
+        0,
+        8,
+        2,
+        1,  # long loca offsets
+        0,
+    )
+    hhea = struct.pack(
+        ">IhhhHhhhhhhhhhhhH",
+        0x00010000,
+        ASCENDER,
+        DESCENDER,
+        0,
+        ADVANCE,
+        x_min,
+        ADVANCE - x_max,
+        x_max,
+        1,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        count,
+    )
+    maxp = struct.pack(
+        ">IHHHHHHHHHHHHHH",
+        0x00010000,
+        count,
+        max(points for _, _, points, _ in glyphs),
+        max(contours for _, _, _, contours in glyphs),
+        0,
+        0,
+        2,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+        0,
+    )
+    os2 = struct.pack(
+        ">HhHHHhhhhhhhhhhh10sIIII4sHHHhhhHHIIhhHHH",
+        4,
+        ADVANCE,
+        400,
+        5,
+        0,
+        650,
+        600,
+        0,
+        75,
+        650,
+        600,
+        0,
+        350,
+        50,
+        300,
+        0,
+        b"\0" * 10,
+        0,
+        1 << 28,  # Private Use Area
+        0,
+        0,
+        b"NONE",
+        0x0040,  # regular
+        FIRST_CODEPOINT,
+        FIRST_CODEPOINT + count - 2,
+        ASCENDER,
+        DESCENDER,
+        0,
+        ASCENDER,
+        -DESCENDER,
+        1,
+        0,
+        500,
+        700,
+        0,
+        0x20,
+        1,
+    )
+    post = struct.pack(">IIhhIIIII", 0x00030000, 0, -100, 50, 0, 0, 0, 0, 0)
+
+    tables = {
+        b"OS/2": os2,
+        b"cmap": cmap_table(count - 1),
+        b"glyf": glyf,
+        b"head": head,
+        b"hhea": hhea,
+        b"hmtx": hmtx,
+        b"loca": loca,
+        b"maxp": maxp,
+        b"name": name_table(),
+        b"post": post,
+    }
+
+    entry_selector = len(tables).bit_length() - 1
+    search_range = 16 * 2**entry_selector
+    font = struct.pack(
+        ">IHHHH",
+        0x00010000,
+        len(tables),
+        search_range,
+        entry_selector,
+        16 * len(tables) - search_range,
+    )
+    offset = len(font) + 16 * len(tables)
+    body = b""
+    for tag, data in sorted(tables.items()):
+        font += struct.pack(">4sIII", tag, checksum(data), offset + len(body), len(data))
+        body += data + b"\0" * (-len(data) % 4)
+    font += body
+
+    # head.checkSumAdjustment makes the whole file sum to a fixed value
+    head_offset = font.index(head)
+    adjustment = (0xB1B0AFBA - checksum(font)) & 0xFFFFFFFF
+    font = font[: head_offset + 8] + struct.pack(">I", adjustment) + font[head_offset + 12 :]
+
+    with open(OUTPUT, "wb") as file:
+        file.write(font)
+
+
+if __name__ == "__main__":
+    build()
@@ -1,26 +1,71 @@
+#import "components/contact.typ": contact-line
+#import "components/url.typ": display-url
+
 #let resume(data) = {
   set text(font: "Libertinus Serif", size: 10pt)
 
-  // Extract configuration options with defaults
+  // Render options supplied by the transform (not part of the resume data)
+  let options = if "options" in data { data.options } else { (:) }
+
+  // Extract configuration options with defaults; the page_numbers render
+  // option wins over the resume's own setting
   let show-page-numbers = if "showPageNumbers" in data { data.showPageNumbers } else { true }
+  let page-numbers-option = options.at("page_numbers", default: none)
+  if page-numbers-option != none { show-page-numbers = page-numbers-option }
+  let icons = options.at("icons", default: false)
+  let icon-font = options.at("icon_font", default: none)
+  let icon-svgs = options.at("icon_svgs", default: none)
+  let profile-icons = options.at("profile_icons", default: ())
+  let page-breaks = options.at("page_breaks", default: ())
+  let footer-timestamp = options.at("footer_timestamp", default: false)
+  let summary-placement = options.at("summary_placement", default: "header")
+  let summary-style = options.at("summary_style", default: "plain")
+  let skills-style = options.at("skills_style", default: "inline")
+  let contact-style = options.at("contact_style", default: "stacked")
+  let divider-style = options.at("divider_style", default: "full")
+  let section-colors = options.at("section_colors", default: (:))
+  let combine-recognition = options.at("combine_recognition", default: false)
+  let language-levels = options.at("language_levels", default: ())
+  let language-max-level = options.at("language_max_level", default: 5)
+  let preview-section = options.at("preview_section", default: none)
+  let keyword-pattern = options.at("keyword_pattern", default: none)
+  let math-segments = options.at("math_segments", default: ())
+  let style = options.at("style_overrides", default: (:))
+  let justify = style.at("justify", default: true)
+  let heading-weight = style.at("heading_weight", default: "bold")
+  let rule-thickness = style.at("rule_thickness", default: 0.5) * 1pt
+
+  // Vertical rhythm; the compact density tightens the gaps between entries,
+  // their header lines, and their highlights
+  let compact = options.at("density", default: "comfortable") == "compact"
+  let entry-spacing = if compact { 0.4em } else { 0.65em }
+  let item-spacing = if compact { 2pt } else { 4pt }
 
   set page(
-    paper: "us-letter",
+    paper: options.at("paper", default: "us-letter"),
     margin: (x: 0.5in, y: 0.5in),
-    footer: if show-page-numbers {
+    footer: if show-page-numbers or footer-timestamp {
       context {
         set text(size: 9pt)
-        let page-num = counter(page).get().first()
-        let page-count = counter(page).final().first()
-        align(center)[#data.basics.name | Page #page-num of #page-count]
+        if show-page-numbers {
+          let page-num = counter(page).get().first()
+          let page-count = counter(page).final().first()
+          align(center)[#data.basics.name | Page #page-num of #page-count]
+        }
+        if footer-timestamp {
+          let generated = datetime.today().display("[month repr:long] [day], [year]")
+          align(center, text(size: 7pt, fill: luma(120))[Generated on #generated])
+        }
       }
     },
   )
-  set par(justify: true)
+  // Previews render a single section on a page that grows to fit it
+  set page(height: auto, footer: none) if preview-section != none
+  set par(justify: justify)
 
   // Prevent orphaned headlines and widow/orphan lines
-  set par(leading: 0.65em, spacing: 0.65em)
-  set block(spacing: 0.65em)
+  set par(leading: 0.65em, spacing: entry-spacing)
+  set block(spacing: entry-spacing)
 
   // Helper for section headers with custom title support
   let section-header(default-title, section-name: none) = {
@@ -30,10 +75,17 @@
         title = data.sectionTitles.at(section-name)
       }
     }
-    v(4pt)
-    text(size: 12pt, weight: "bold", smallcaps(title))
-    v(-4pt)
-    line(length: 100%, stroke: 0.5pt)
+    // Validated as #RGB or #RRGGBB hex before it reaches the template
+    let color = black
+    if section-name != none and section-name in section-colors {
+      color = rgb(section-colors.at(section-name))
+    }
+    v(item-spacing)
+    text(size: 12pt, weight: heading-weight, fill: color, smallcaps(title))
+    if divider-style != "none" {
+      v(-4pt)
+      line(length: if divider-style == "short" { 3em } else { 100% }, stroke: rule-thickness + color)
+    }
   }
 
   // Helper for entry headers (4-quadrant layout)
@@ -41,7 +93,8 @@
     grid(
       columns: (1fr, auto),
       rows: (auto, auto),
-      gutter: 4pt,
+      column-gutter: 4pt,
+      row-gutter: item-spacing,
       text(weight: "bold")[#top-left],
       align(right)[#top-right],
       text(style: "italic")[#bottom-left],
@@ -49,11 +102,23 @@
     )
   }
 
-  // Format date range
+  // Bold the requested keywords (highlight_keywords) within a piece of content
+  let mark-keywords(body) = if keyword-pattern == none { body } else {
+    show regex(keyword-pattern): strong
+    body
+  }
+
+  // Highlights are plain strings or (lead, text) pairs with a bold lead-in
+  let highlight(h) = mark-keywords(if type(h) == dictionary [#strong(h.lead) #h.text] else [#h])
+
+  // Format date range; a missing end keeps a trailing dash ("2018 –") and a
+  // missing start a leading one ("– 2020"), and blank values count as missing
   let format-dates(start, end) = {
+    let start = if type(start) == str and start.trim() == "" { none } else { start }
+    let end = if type(end) == str and end.trim() == "" { none } else { end }
     if start != none and end != none [#start -- #end]
-    else if start != none [#start]
-    else if end != none [#end]
+    else if start != none [#start --]
+    else if end != none [-- #end]
   }
 
   // === SECTION RENDERERS ===
@@ -78,9 +143,9 @@
             GPA: #edu.gpa
           ]
           if "highlights" in edu and edu.highlights.len() > 0 [
-            #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: 4pt)
+            #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: item-spacing)
             #for h in edu.highlights [
-              - #h
+              - #highlight(h)
             ]
           ]
         }
@@ -101,9 +166,9 @@
             GPA: #edu.gpa
           ]
           #if "highlights" in edu and edu.highlights.len() > 0 [
-            #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: 4pt)
+            #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: item-spacing)
             #for h in edu.highlights [
-              - #h
+              - #highlight(h)
             ]
           ]
         ]
@@ -111,6 +176,11 @@
     }
   }
 
+  // Company name, preceded by its logo when one was resolved to an asset
+  let company-name(w) = if "logo" in w and w.logo != none [
+    #box(image("assets/" + w.logo, height: 0.9em), baseline: 0.1em)#h(4pt)#w.company
+  ] else [#w.company]
+
   let render-experience() = {
     if "work" in data and data.work.len() > 0 {
       // Wrap header with first entry to prevent orphaned headlines
@@ -122,15 +192,15 @@
             w.position,
             format-dates(
               if "startDate" in w { w.startDate } else { none },
-              if "endDate" in w { w.endDate } else { none }
+              if "endDate" in w { w.endDate } else if w.at("current", default: false) { "Present" } else { none }
             ),
-            w.company,
+            company-name(w),
             if "location" in w and w.location != none [#w.location]
           )
           if "highlights" in w and w.highlights.len() > 0 [
-            #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: 4pt)
+            #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: item-spacing)
             #for h in w.highlights [
-              - #h
+              - #highlight(h)
             ]
           ]
         }
@@ -142,15 +212,15 @@
             w.position,
             format-dates(
               if "startDate" in w { w.startDate } else { none },
-              if "endDate" in w { w.endDate } else { none }
+              if "endDate" in w { w.endDate } else if w.at("current", default: false) { "Present" } else { none }
             ),
-            w.company,
+            company-name(w),
             if "location" in w and w.location != none [#w.location]
           )
           #if "highlights" in w and w.highlights.len() > 0 [
-            #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: 4pt)
+            #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: item-spacing)
             #for h in w.highlights [
-              - #h
+              - #highlight(h)
             ]
           ]
         ]
@@ -173,7 +243,7 @@
                 #h(4pt) | #h(4pt) #text(style: "italic", size: 9pt)[#p.keywords.join(", ")]
               ]
               #if "url" in p and p.url != none [
-                #h(4pt) | #h(4pt) #link(p.url)[#underline(text(size: 9pt)[#p.url.replace("https://", "").replace("http://", "")])]
+                #h(4pt) | #h(4pt) #link(p.url)[#underline(text(size: 9pt)[#display-url(p.url)])]
               ]
             ],
             align(right)[
@@ -187,9 +257,9 @@
             #text(style: "italic", size: 9pt)[#p.description]
           ]
           if "highlights" in p and p.highlights.len() > 0 [
-            #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: 4pt)
+            #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: item-spacing)
             #for h in p.highlights [
-              - #h
+              - #highlight(h)
             ]
           ]
         }
@@ -205,7 +275,7 @@
                 #h(4pt) | #h(4pt) #text(style: "italic", size: 9pt)[#p.keywords.join(", ")]
               ]
               #if "url" in p and p.url != none [
-                #h(4pt) | #h(4pt) #link(p.url)[#underline(text(size: 9pt)[#p.url.replace("https://", "").replace("http://", "")])]
+                #h(4pt) | #h(4pt) #link(p.url)[#underline(text(size: 9pt)[#display-url(p.url)])]
               ]
             ],
             align(right)[
@@ -219,9 +289,9 @@
             #text(style: "italic", size: 9pt)[#p.description]
           ]
           #if "highlights" in p and p.highlights.len() > 0 [
-            #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: 4pt)
+            #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: item-spacing)
             #for h in p.highlights [
-              - #h
+              - #highlight(h)
             ]
           ]
         ]
@@ -229,97 +299,94 @@
     }
   }
 
-  let render-certifications() = {
-    if "certifications" in data and data.certifications.len() > 0 {
-      // Wrap header with first entry to prevent orphaned headlines
-      block(breakable: false)[
-        #section-header("Certifications", section-name: "certifications")
-        #if data.certifications.len() > 0 {
-          let cert = data.certifications.at(0)
-          grid(
-            columns: (1fr, auto),
-            [
-              *#cert.name*
-              #if "issuer" in cert and cert.issuer != none [
-                #h(4pt) | #h(4pt) #text(style: "italic")[#cert.issuer]
-              ]
-            ],
-            align(right)[
-              #if "date" in cert and cert.date != none [#cert.date]
-            ]
-          )
-          if "url" in cert and cert.url != none [
-            #link(cert.url)[#underline(text(size: 9pt)[#cert.url.replace("https://", "").replace("http://", "")])]
-          ]
-        }
+  // One certification: name and issuer, date on the right, then its link
+  let certification-entry(cert) = {
+    grid(
+      columns: (1fr, auto),
+      [
+        *#cert.name*
+        #if "issuer" in cert and cert.issuer != none [
+          #h(4pt) | #h(4pt) #text(style: "italic")[#cert.issuer]
+        ]
+      ],
+      align(right)[
+        #if "date" in cert and cert.date != none [#cert.date]
       ]
-      // Render remaining entries
-      for cert in data.certifications.slice(1) [
-        #block(breakable: false)[
-          #grid(
-            columns: (1fr, auto),
-            [
-              *#cert.name*
-              #if "issuer" in cert and cert.issuer != none [
-                #h(4pt) | #h(4pt) #text(style: "italic")[#cert.issuer]
-              ]
-            ],
-            align(right)[
-              #if "date" in cert and cert.date != none [#cert.date]
-            ]
-          )
-          #if "url" in cert and cert.url != none [
-            #link(cert.url)[#underline(text(size: 9pt)[#cert.url.replace("https://", "").replace("http://", "")])]
-          ]
+    )
+    if "url" in cert and cert.url != none [
+      #link(cert.url)[#underline(text(size: 9pt)[#display-url(cert.url)])]
+    ]
+  }
+
+  // One award: title and awarder, date on the right, then its summary
+  let award-entry(award) = {
+    grid(
+      columns: (1fr, auto),
+      [
+        *#award.title*
+        #if "awarder" in award and award.awarder != none [
+          #h(4pt) | #h(4pt) #text(style: "italic")[#award.awarder]
         ]
+      ],
+      align(right)[
+        #if "date" in award and award.date != none [#award.date]
       ]
-    }
+    )
+    if "summary" in award and award.summary != none [
+      #text(size: 9pt)[#award.summary]
+    ]
   }
 
-  let render-awards() = {
-    if "awards" in data and data.awards.len() > 0 {
+  // A header followed by unbreakable entries
+  let entry-section(header, entries) = {
+    if entries.len() > 0 {
       // Wrap header with first entry to prevent orphaned headlines
       block(breakable: false)[
-        #section-header("Awards", section-name: "awards")
-        #if data.awards.len() > 0 {
-          let award = data.awards.at(0)
-          grid(
-            columns: (1fr, auto),
-            [
-              *#award.title*
-              #if "awarder" in award and award.awarder != none [
-                #h(4pt) | #h(4pt) #text(style: "italic")[#award.awarder]
-              ]
-            ],
-            align(right)[
-              #if "date" in award and award.date != none [#award.date]
-            ]
-          )
-          if "summary" in award and award.summary != none [
-            #text(size: 9pt)[#award.summary]
-          ]
-        }
-      ]
-      // Render remaining entries
-      for award in data.awards.slice(1) [
-        #block(breakable: false)[
-          #grid(
-            columns: (1fr, auto),
-            [
-              *#award.title*
-              #if "awarder" in award and award.awarder != none [
-                #h(4pt) | #h(4pt) #text(style: "italic")[#award.awarder]
-              ]
-            ],
-            align(right)[
-              #if "date" in award and award.date != none [#award.date]
-            ]
-          )
-          #if "summary" in award and award.summary != none [
-            #text(size: 9pt)[#award.summary]
-          ]
-        ]
+        #header
+        #entries.at(0)
       ]
+      for entry in entries.slice(1) {
+        block(breakable: false, entry)
+      }
+    }
+  }
+
+  let entries-of(key) = if key in data and data.at(key) != none { data.at(key) } else { () }
+
+  let render-certifications() = entry-section(
+    section-header("Certifications", section-name: "certifications"),
+    entries-of("certifications").map(certification-entry),
+  )
+
+  let render-awards() = entry-section(
+    section-header("Awards", section-name: "awards"),
+    entries-of("awards").map(award-entry),
+  )
+
+  // Awards and certifications under one heading (combine_recognition),
+  // honors first to match it
+  let render-recognition() = entry-section(
+    section-header("Honors & Certifications"),
+    entries-of("awards").map(award-entry) + entries-of("certifications").map(certification-entry),
+  )
+
+  // Publication text with the $...$ segments the transform approved set as
+  // inline math (publication_math); anything else, dollar signs included,
+  // stays literal
+  let pub-text(s) = {
+    let parts = s.split("$")
+    if math-segments.len() == 0 or calc.even(parts.len()) {
+      s
+    } else {
+      for (i, part) in parts.enumerate() {
+        if calc.even(i) {
+          part
+        } else if part in math-segments {
+          eval(part, mode: "math")
+        } else {
+          "$" + part + "$"
+        }
+      }
     }
   }
 
@@ -333,15 +400,15 @@
           grid(
             columns: (1fr, auto),
             [
-              *#pub.title*
+              *#pub-text(pub.title)*
               #if "authors" in pub and pub.authors.len() > 0 [
                 \ #text(style: "italic", size: 9pt)[#pub.authors.join(", ")]
               ]
               #if "venue" in pub and pub.venue != none [
-                \ #text(size: 9pt)[#pub.venue]
+                \ #text(size: 9pt)[#pub-text(pub.venue)]
               ]
               #if "url" in pub and pub.url != none [
-                \ #link(pub.url)[#underline(text(size: 9pt)[#pub.url.replace("https://", "").replace("http://", "")])]
+                \ #link(pub.url)[#underline(text(size: 9pt)[#display-url(pub.url)])]
               ]
             ],
             align(right)[
@@ -349,7 +416,7 @@
             ]
           )
           if "summary" in pub and pub.summary != none [
-            #text(size: 9pt)[#pub.summary]
+            #text(size: 9pt)[#pub-text(pub.summary)]
           ]
         }
       ]
@@ -359,15 +426,15 @@
           #grid(
             columns: (1fr, auto),
             [
-              *#pub.title*
+              *#pub-text(pub.title)*
               #if "authors" in pub and pub.authors.len() > 0 [
                 \ #text(style: "italic", size: 9pt)[#pub.authors.join(", ")]
               ]
               #if "venue" in pub and pub.venue != none [
-                \ #text(size: 9pt)[#pub.venue]
+                \ #text(size: 9pt)[#pub-text(pub.venue)]
               ]
               #if "url" in pub and pub.url != none [
-                \ #link(pub.url)[#underline(text(size: 9pt)[#pub.url.replace("https://", "").replace("http://", "")])]
+                \ #link(pub.url)[#underline(text(size: 9pt)[#display-url(pub.url)])]
               ]
             ],
             align(right)[
@@ -375,7 +442,7 @@
             ]
           )
           #if "summary" in pub and pub.summary != none [
-            #text(size: 9pt)[#pub.summary]
+            #text(size: 9pt)[#pub-text(pub.summary)]
           ]
         ]
       ]
@@ -387,9 +454,17 @@
       // Wrap header with content to prevent orphaned headlines
       block(breakable: false)[
         #section-header("Technical Skills", section-name: "skills")
-        #for skill in data.skills [
-          *#skill.name:* #skill.keywords.join(", ")
-          #linebreak()
+        #if skills-style == "grouped" [
+          #set list(marker: text(size: 0.7em)[•], body-indent: 0.5em, spacing: item-spacing)
+          #for skill in data.skills [
+            *#skill.name*
+            #list(..skill.keywords.map(k => [#k]))
+          ]
+        ] else [
+          #for skill in data.skills [
+            *#skill.name:* #skill.keywords.join(", ")
+            #linebreak()
+          ]
         ]
       ]
     }
@@ -400,8 +475,11 @@
       // Wrap header with content to prevent orphaned headlines
       block(breakable: false)[
         #section-header("Languages", section-name: "languages")
-        #let lang-items = data.languages.map(lang => {
-          if "fluency" in lang and lang.fluency != none [*#lang.language* (#lang.fluency)]
+        #let lang-items = data.languages.enumerate().map(((i, lang)) => {
+          // Levels are only supplied for the dots style; unrecognized fluency is null
+          let level = language-levels.at(i, default: none)
+          if level != none [*#lang.language* #box(text(size: 8pt, tracking: 1pt, "●" * level + "○" * (language-max-level - level)))]
+          else if "fluency" in lang and lang.fluency != none [*#lang.language* (#lang.fluency)]
           else [*#lang.language*]
         })
         #lang-items.join("  •  ")
@@ -409,64 +487,137 @@
     }
   }
 
-  // Section dispatcher
+  let custom-sections = if "customSections" in data and data.customSections != none {
+    data.customSections
+  } else {
+    ()
+  }
+
+  let render-custom(section) = {
+    if section.items.len() > 0 {
+      // Wrap header with content to prevent orphaned headlines
+      block(breakable: false)[
+        #section-header(section.title)
+        #for item in section.items [
+          - #item
+        ]
+      ]
+    }
+  }
+
+  // Sections merged by combine_recognition
+  let recognition = ("awards", "certifications")
+
+  // Section dispatcher (custom sections are addressed by their title)
   let render-section(name) = {
     if name == "education" { render-education() }
     else if name == "experience" { render-experience() }
     else if name == "projects" { render-projects() }
+    else if combine-recognition and name in recognition { render-recognition() }
     else if name == "certifications" { render-certifications() }
     else if name == "awards" { render-awards() }
     else if name == "publications" { render-publications() }
     else if name == "skills" { render-skills() }
     else if name == "languages" { render-languages() }
+    else {
+      for section in custom-sections.filter(c => c.title == name) {
+        render-custom(section)
+      }
+    }
   }
 
   // Default section order
   let default-order = ("education", "experience", "projects", "certifications", "awards", "publications", "skills", "languages")
 
   // Determine section order to use
-  let section-order = if "sectionOrder" in data and data.sectionOrder != none {
+  let section-order = if preview-section != none {
+    (preview-section,)
+  } else if "sectionOrder" in data and data.sectionOrder != none {
     data.sectionOrder
   } else {
     default-order
   }
 
+  // The combined section takes the place of whichever of the two comes first
+  if combine-recognition {
+    let first = section-order.find(name => name in recognition)
+    section-order = section-order.filter(name => name not in recognition or name == first)
+  }
+
   // === HEADER ===
-  align(center)[
-    #text(2em, weight: "bold", smallcaps(data.basics.name))
+  if preview-section == none {
+    align(center)[
+      #text(2em, weight: "bold", smallcaps(data.basics.name))
+
+      // Location line (if present; inline contact style puts it on the contact line)
+      #if contact-style != "inline" and "location" in data.basics and data.basics.location != none [
+        #text(size: 10pt)[#data.basics.location]
+      ]
 
-    // Location line (if present)
-    #if "location" in data.basics and data.basics.location != none [
-      #text(size: 10pt)[#data.basics.location]
+      // Contact line
+      #contact-line(
+        data.basics,
+        icons: icons,
+        icon-font: icon-font,
+        icon-svgs: icon-svgs,
+        profile-icons: profile-icons,
+        inline: contact-style == "inline",
+      )
     ]
+  }
 
-    // Contact line
-    #let contact = ()
-    #if "phone" in data.basics and data.basics.phone != none { contact.push(data.basics.phone) }
-    #contact.push(link("mailto:" + data.basics.email)[#underline(data.basics.email)])
-    #if "profiles" in data.basics {
-      for p in data.basics.profiles {
-         contact.push(link(p.url)[#underline(p.url.replace("https://", "").replace("http://", ""))])
-      }
+  // === SUMMARY ===
+  if preview-section == none and "summary" in data.basics and data.basics.summary != none {
+    // The box style sets the summary off as a shaded callout
+    let summary = if summary-style == "box" {
+      block(
+        width: 100%,
+        fill: luma(242),
+        stroke: 0.5pt + luma(160),
+        inset: 8pt,
+        radius: 2pt,
+        mark-keywords[#data.basics.summary],
+      )
+    } else {
+      mark-keywords[#data.basics.summary]
     }
-    #par(justify: true)[
-      #text(size: 9pt)[
-        #for (i, item) in contact.enumerate() [
-          #if i > 0 [  |  ]#item
-        ]
+
+    if summary-placement == "section" {
+      block(breakable: false)[
+        #section-header("Summary", section-name: "summary")
+        #summary
       ]
+    } else [
+      #v(10pt)
+      #summary
+      #v(10pt)
     ]
-  ]
+  }
 
-  // === SUMMARY ===
-  if "summary" in data.basics and data.basics.summary != none [
-    #v(10pt)
-    #data.basics.summary
-    #v(10pt)
-  ]
+  // Whether a section has any entries to render
+  let has-entries(name) = {
+    if combine-recognition and name in recognition {
+      recognition.any(key => entries-of(key).len() > 0)
+    } else if name in default-order {
+      let key = if name == "experience" { "work" } else { name }
+      key in data and data.at(key) != none and data.at(key).len() > 0
+    } else {
+      custom-sections.any(c => c.title == name and c.items.len() > 0)
+    }
+  }
 
   // === RENDER SECTIONS IN ORDER ===
   for section in section-order {
+    if section in page-breaks and has-entries(section) {
+      pagebreak(weak: true)
+    }
     render-section(section)
   }
+
+  // Custom sections not positioned via sectionOrder follow the standard ones
+  for section in custom-sections {
+    if preview-section == none and section.title not in section-order {
+      render-custom(section)
+    }
+  }
 }